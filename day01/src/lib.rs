@@ -0,0 +1,77 @@
+// Copyright 2021 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use itertools::Itertools;
+use std::io;
+use std::path::Path;
+use utils::input_read::read_parsed_line_input;
+use utils::Solver;
+
+pub fn part1(input: &[usize]) -> usize {
+    input.iter().tuple_windows().filter(|(a, b)| a < b).count()
+}
+
+pub fn part2(input: &[usize]) -> usize {
+    input
+        .iter()
+        .tuple_windows()
+        .map(|(a, b, c)| a + b + c)
+        .tuple_windows()
+        .filter(|(a, b)| a < b)
+        .count()
+}
+
+/// Counts the increases in the sonar sweep depth measurements.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Day01;
+
+impl Solver for Day01 {
+    const DAY: u8 = 1;
+
+    type Input = Vec<usize>;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(&self, input_file: &Path) -> io::Result<Self::Input> {
+        read_parsed_line_input(input_file)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Self::Output1 {
+        part1(input)
+    }
+
+    fn part2(&self, input: &Self::Input) -> Self::Output2 {
+        part2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_sample_input() {
+        let input = vec![199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+        let expected = 7;
+        assert_eq!(expected, part1(&input))
+    }
+
+    #[test]
+    fn part2_sample_input() {
+        let input = vec![199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+        let expected = 5;
+
+        assert_eq!(expected, part2(&input))
+    }
+}
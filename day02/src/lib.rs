@@ -0,0 +1,201 @@
+// Copyright 2021 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::{self, Display, Formatter};
+use std::io;
+use std::num::ParseIntError;
+use std::path::Path;
+use std::str::FromStr;
+use utils::input_read::read_parsed_line_input;
+use utils::Solver;
+
+const FORWARD_CMD: &str = "forward";
+const DOWN_CMD: &str = "down";
+const UP_CMD: &str = "up";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InvalidCommand {
+    Empty,
+    MissingMagnitude,
+    InvalidMagnitude(ParseIntError),
+    UnknownDirection(String),
+}
+
+impl Display for InvalidCommand {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            InvalidCommand::Empty => write!(f, "the command is empty"),
+            InvalidCommand::MissingMagnitude => write!(f, "the command has no magnitude"),
+            InvalidCommand::InvalidMagnitude(err) => {
+                write!(f, "the magnitude is not a valid number - {err}")
+            }
+            InvalidCommand::UnknownDirection(direction) => {
+                write!(f, "{direction} is not a valid direction")
+            }
+        }
+    }
+}
+
+impl std::error::Error for InvalidCommand {}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Command {
+    Forward(i64),
+    Down(i64),
+    Up(i64),
+}
+
+impl FromStr for Command {
+    type Err = InvalidCommand;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut cmd_magnitude = s.split_ascii_whitespace();
+        let raw_cmd = cmd_magnitude.next().ok_or(InvalidCommand::Empty)?;
+        let magnitude = cmd_magnitude
+            .next()
+            .ok_or(InvalidCommand::MissingMagnitude)?
+            .parse()
+            .map_err(InvalidCommand::InvalidMagnitude)?;
+
+        match raw_cmd {
+            FORWARD_CMD => Ok(Command::Forward(magnitude)),
+            DOWN_CMD => Ok(Command::Down(magnitude)),
+            UP_CMD => Ok(Command::Up(magnitude)),
+            other => Err(InvalidCommand::UnknownDirection(other.to_string())),
+        }
+    }
+}
+
+struct Submarine {
+    x_pos: i64,
+    y_pos: i64,
+    aim: i64,
+}
+
+impl Submarine {
+    fn new() -> Submarine {
+        Submarine {
+            x_pos: 0,
+            y_pos: 0,
+            aim: 0,
+        }
+    }
+
+    fn move_in_direction(&mut self, cmd: Command) {
+        match cmd {
+            Command::Forward(magnitude) => self.x_pos += magnitude,
+            Command::Down(magnitude) => self.y_pos += magnitude,
+            Command::Up(magnitude) => self.y_pos -= magnitude,
+        }
+    }
+
+    fn steer_in_direction(&mut self, cmd: Command) {
+        match cmd {
+            Command::Forward(magnitude) => {
+                self.x_pos += magnitude;
+                self.y_pos += magnitude * self.aim
+            }
+            Command::Down(magnitude) => self.aim += magnitude,
+            Command::Up(magnitude) => self.aim -= magnitude,
+        }
+    }
+}
+
+pub fn part1(input: &[Command]) -> i64 {
+    let mut sub = Submarine::new();
+    for &cmd in input {
+        sub.move_in_direction(cmd)
+    }
+    sub.x_pos * sub.y_pos
+}
+
+pub fn part2(input: &[Command]) -> i64 {
+    let mut sub = Submarine::new();
+    for &cmd in input {
+        sub.steer_in_direction(cmd)
+    }
+    sub.x_pos * sub.y_pos
+}
+
+/// Pilots the submarine according to the planned course.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Day02;
+
+impl Solver for Day02 {
+    const DAY: u8 = 2;
+
+    type Input = Vec<Command>;
+    type Output1 = i64;
+    type Output2 = i64;
+
+    fn parse(&self, input_file: &Path) -> io::Result<Self::Input> {
+        read_parsed_line_input(input_file)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Self::Output1 {
+        part1(input)
+    }
+
+    fn part2(&self, input: &Self::Input) -> Self::Output2 {
+        part2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_sample_input() {
+        let input = vec![
+            Command::Forward(5),
+            Command::Down(5),
+            Command::Forward(8),
+            Command::Up(3),
+            Command::Down(8),
+            Command::Forward(2),
+        ];
+        let expected = 150;
+        assert_eq!(expected, part1(&input))
+    }
+
+    #[test]
+    fn part2_sample_input() {
+        let input = vec![
+            Command::Forward(5),
+            Command::Down(5),
+            Command::Forward(8),
+            Command::Up(3),
+            Command::Down(8),
+            Command::Forward(2),
+        ];
+        let expected = 900;
+        assert_eq!(expected, part2(&input))
+    }
+
+    #[test]
+    fn command_parsing() {
+        assert_eq!(Command::Up(42), "up 42".parse().unwrap());
+        assert_eq!(Command::Down(123), "down 123".parse().unwrap());
+        assert_eq!(Command::Forward(1), "forward 1".parse().unwrap());
+        assert_eq!(
+            Err(InvalidCommand::UnknownDirection("sideways".to_string())),
+            "sideways 1".parse::<Command>()
+        );
+        assert_eq!(
+            Err(InvalidCommand::MissingMagnitude),
+            "up".parse::<Command>()
+        );
+    }
+}
@@ -0,0 +1,489 @@
+// Copyright 2021 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::{self, Display, Formatter};
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+use utils::grid::Grid;
+use utils::input_read::read_into_string_groups;
+use utils::render::{render_grid, Glyphs};
+use utils::Solver;
+
+const GRID_SIZE: usize = 5;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MalformedBingoCard {
+    InvalidNumber {
+        row: usize,
+        column: usize,
+        found: String,
+    },
+    WrongRowCount(usize),
+    WrongColumnCount {
+        row: usize,
+        found: usize,
+    },
+}
+
+impl Display for MalformedBingoCard {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            MalformedBingoCard::InvalidNumber { row, column, found } => {
+                write!(f, "{found} at {row}:{column} is not a valid bingo number")
+            }
+            MalformedBingoCard::WrongRowCount(found) => {
+                write!(f, "the card has {found} rows instead of {GRID_SIZE}")
+            }
+            MalformedBingoCard::WrongColumnCount { row, found } => write!(
+                f,
+                "row {row} of the card has {found} numbers instead of {GRID_SIZE}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for MalformedBingoCard {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MalformedBingoGame {
+    MissingDrawnNumbers,
+    InvalidDrawnNumber(String),
+    NoCards,
+    MalformedCard {
+        card: usize,
+        source: MalformedBingoCard,
+    },
+}
+
+impl Display for MalformedBingoGame {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            MalformedBingoGame::MissingDrawnNumbers => write!(f, "the drawn numbers are missing"),
+            MalformedBingoGame::InvalidDrawnNumber(found) => {
+                write!(f, "{found} is not a valid drawn number")
+            }
+            MalformedBingoGame::NoCards => write!(f, "there are no bingo cards"),
+            MalformedBingoGame::MalformedCard { card, source } => {
+                write!(f, "bingo card {card} is malformed - {source}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for MalformedBingoGame {}
+
+#[derive(Debug, Default, Clone)]
+struct BingoField {
+    value: u8,
+    marked: bool,
+}
+
+impl Display for BingoField {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.marked {
+            write!(f, "[{:>2}]", self.value)
+        } else {
+            write!(f, " {:>2} ", self.value)
+        }
+    }
+}
+
+impl BingoField {
+    fn new(value: u8) -> Self {
+        BingoField {
+            value,
+            marked: false,
+        }
+    }
+
+    fn mark(&mut self) {
+        self.marked = true
+    }
+
+    fn is_marked(&self) -> bool {
+        self.marked
+    }
+}
+
+// card is defined to be a 5x5 grid
+#[derive(Debug, Clone)]
+struct BingoBoard {
+    rows: [[BingoField; GRID_SIZE]; GRID_SIZE],
+}
+
+impl FromStr for BingoBoard {
+    type Err = MalformedBingoCard;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut rows: [[BingoField; GRID_SIZE]; GRID_SIZE] = Default::default();
+        let lines = s.lines().collect::<Vec<_>>();
+        if lines.len() != GRID_SIZE {
+            return Err(MalformedBingoCard::WrongRowCount(lines.len()));
+        }
+        for (i, row) in lines.into_iter().enumerate() {
+            let values = row.split_ascii_whitespace().collect::<Vec<_>>();
+            if values.len() != GRID_SIZE {
+                return Err(MalformedBingoCard::WrongColumnCount {
+                    row: i,
+                    found: values.len(),
+                });
+            }
+            for (j, val) in values.into_iter().enumerate() {
+                let val = val.parse().map_err(|_| MalformedBingoCard::InvalidNumber {
+                    row: i,
+                    column: j,
+                    found: val.to_string(),
+                })?;
+                rows[i][j] = BingoField::new(val);
+            }
+        }
+
+        Ok(BingoBoard { rows })
+    }
+}
+
+impl Display for BingoBoard {
+    /// Draws the numbers with the marked ones in brackets, or just the pattern of the marked
+    /// fields when using the alternate form, i.e. `{:#}`.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if f.alternate() {
+            return write!(f, "{}", render_grid(&self.marks(), Glyphs::BLOCKS));
+        }
+        for row in &self.rows {
+            for value in row {
+                write!(f, "{value}")?
+            }
+            writeln!(f)?
+        }
+        Ok(())
+    }
+}
+
+impl BingoBoard {
+    fn marks(&self) -> Grid<bool> {
+        let rows = self
+            .rows
+            .iter()
+            .map(|row| row.iter().map(BingoField::is_marked).collect())
+            .collect();
+        Grid::from_rows(rows).expect("the card is always a non-empty square")
+    }
+
+    fn check_win_condition(&self) -> bool {
+        for i in 0..GRID_SIZE {
+            if self.check_row(i) {
+                return true;
+            }
+            if self.check_column(i) {
+                return true;
+            }
+        }
+
+        false
+    }
+
+    fn check_row(&self, row: usize) -> bool {
+        self.rows[row].iter().all(|field| field.is_marked())
+    }
+
+    fn check_column(&self, column: usize) -> bool {
+        for row in &self.rows {
+            if !row[column].is_marked() {
+                return false;
+            }
+        }
+        true
+    }
+
+    fn mark_value(&mut self, value: u8) {
+        for row in self.rows.iter_mut() {
+            for field in row.iter_mut() {
+                if field.value == value {
+                    field.mark();
+                    return;
+                }
+            }
+        }
+    }
+
+    fn calculate_score(&self) -> usize {
+        let mut score = 0;
+        for row in self.rows.iter() {
+            for field in row.iter() {
+                if !field.is_marked() {
+                    score += field.value as usize
+                }
+            }
+        }
+        score
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BingoGame {
+    currently_played: usize,
+    drawn_numbers: Vec<u8>,
+    boards: Vec<BingoBoard>,
+}
+
+impl TryFrom<&[String]> for BingoGame {
+    type Error = MalformedBingoGame;
+
+    fn try_from(input: &[String]) -> Result<Self, Self::Error> {
+        let (drawn, cards) = input
+            .split_first()
+            .ok_or(MalformedBingoGame::MissingDrawnNumbers)?;
+        let drawn_numbers = drawn
+            .trim()
+            .split(',')
+            .map(|val| {
+                val.parse()
+                    .map_err(|_| MalformedBingoGame::InvalidDrawnNumber(val.to_string()))
+            })
+            .collect::<Result<_, _>>()?;
+        if cards.is_empty() {
+            return Err(MalformedBingoGame::NoCards);
+        }
+        let boards = cards
+            .iter()
+            .enumerate()
+            .map(|(card, val)| {
+                val.parse()
+                    .map_err(|source| MalformedBingoGame::MalformedCard { card, source })
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(BingoGame {
+            currently_played: 0,
+            drawn_numbers,
+            boards,
+        })
+    }
+}
+
+fn read_game<P: AsRef<Path>>(path: P) -> io::Result<BingoGame> {
+    let groups = read_into_string_groups(path)?;
+    BingoGame::try_from(groups.as_slice())
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+impl BingoGame {
+    fn play_round(&mut self, drawn: u8) -> Option<usize> {
+        for board in self.boards.iter_mut() {
+            board.mark_value(drawn);
+            if board.check_win_condition() {
+                return Some(board.calculate_score() * drawn as usize);
+            }
+        }
+
+        None
+    }
+
+    fn play_round_with_removal(&mut self, drawn: u8) -> Option<usize> {
+        let mut to_remove = Vec::new();
+        let boards = self.boards.len();
+        for (i, board) in self.boards.iter_mut().enumerate().rev() {
+            board.mark_value(drawn);
+            if board.check_win_condition() {
+                if boards == 1 {
+                    return Some(board.calculate_score() * drawn as usize);
+                } else {
+                    to_remove.push(i)
+                }
+            }
+        }
+
+        for remove in to_remove {
+            self.boards.remove(remove);
+        }
+
+        None
+    }
+
+    fn draw_number(&mut self) -> u8 {
+        let value = self
+            .drawn_numbers
+            .get(self.currently_played)
+            .expect("run out of values to draw");
+        self.currently_played += 1;
+        *value
+    }
+
+    fn play(&mut self) -> usize {
+        loop {
+            let drawn = self.draw_number();
+            if let Some(winning_score) = self.play_round(drawn) {
+                return winning_score;
+            }
+        }
+    }
+
+    fn play_until_final_board(&mut self) -> usize {
+        loop {
+            let drawn = self.draw_number();
+            if let Some(winning_score) = self.play_round_with_removal(drawn) {
+                return winning_score;
+            }
+        }
+    }
+}
+
+pub fn part1(mut game: BingoGame) -> usize {
+    game.play()
+}
+
+pub fn part2(mut game: BingoGame) -> usize {
+    game.play_until_final_board()
+}
+
+/// Plays bingo against the giant squid.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Day04;
+
+impl Solver for Day04 {
+    const DAY: u8 = 4;
+
+    type Input = BingoGame;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(&self, input_file: &Path) -> io::Result<Self::Input> {
+        read_game(input_file)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Self::Output1 {
+        part1(input.clone())
+    }
+
+    fn part2(&self, input: &Self::Input) -> Self::Output2 {
+        part2(input.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_sample_input() {
+        let input = vec![
+            "7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1".to_string(),
+            r#"22 13 17 11  0
+8  2 23  4 24
+21  9 14 16  7
+6 10  3 18  5
+1 12 20 15 19"#
+                .to_string(),
+            r#"3 15  0  2 22
+9 18 13 17  5
+19  8  7 25 23
+20 11 10 24  4
+14 21 16 12  6"#
+                .to_string(),
+            r#"14 21 17 24  4
+10 16 15  9 19
+18  8 23 26 20
+22 11 13  6  5
+2  0 12  3  7"#
+                .to_string(),
+        ];
+
+        let expected = 4512;
+
+        assert_eq!(
+            expected,
+            part1(BingoGame::try_from(input.as_slice()).unwrap())
+        )
+    }
+
+    #[test]
+    fn part2_sample_input() {
+        let input = vec![
+            "7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1".to_string(),
+            r#"22 13 17 11  0
+8  2 23  4 24
+21  9 14 16  7
+6 10  3 18  5
+1 12 20 15 19"#
+                .to_string(),
+            r#"3 15  0  2 22
+9 18 13 17  5
+19  8  7 25 23
+20 11 10 24  4
+14 21 16 12  6"#
+                .to_string(),
+            r#"14 21 17 24  4
+10 16 15  9 19
+18  8 23 26 20
+22 11 13  6  5
+2  0 12  3  7"#
+                .to_string(),
+        ];
+
+        let expected = 1924;
+
+        assert_eq!(
+            expected,
+            part2(BingoGame::try_from(input.as_slice()).unwrap())
+        )
+    }
+
+    #[test]
+    fn malformed_game() {
+        let card = "1 2 3 4 5\n6 7 8 9 10\n11 12 13 14 15\n16 17 18 19 20\n21 22 23 24 25";
+        assert!(BingoGame::try_from([String::from("1,2"), card.to_string()].as_slice()).is_ok());
+
+        assert_eq!(
+            Err(MalformedBingoGame::NoCards),
+            BingoGame::try_from([String::from("1,2")].as_slice()).map(|_| ())
+        );
+        assert_eq!(
+            Err(MalformedBingoGame::InvalidDrawnNumber("x".to_string())),
+            BingoGame::try_from([String::from("1,x"), card.to_string()].as_slice()).map(|_| ())
+        );
+        assert_eq!(
+            Err(MalformedBingoGame::MalformedCard {
+                card: 1,
+                source: MalformedBingoCard::WrongColumnCount { row: 4, found: 4 }
+            }),
+            BingoGame::try_from(
+                [
+                    String::from("1,2"),
+                    card.to_string(),
+                    card.replace(" 25", "")
+                ]
+                .as_slice()
+            )
+            .map(|_| ())
+        );
+    }
+
+    #[test]
+    fn displaying_marked_fields() {
+        let mut board: BingoBoard = "14 21 17 24  4
+10 16 15  9 19
+18  8 23 26 20
+22 11 13  6  5
+ 2  0 12  3  7"
+            .parse()
+            .unwrap();
+        for value in [14, 21, 16, 7] {
+            board.mark_value(value)
+        }
+
+        assert!(format!("{board}").starts_with("[14][21] 17  24   4 \n"));
+        assert_eq!("██⠀⠀⠀\n⠀█⠀⠀⠀\n⠀⠀⠀⠀⠀\n⠀⠀⠀⠀⠀\n⠀⠀⠀⠀█", format!("{board:#}"));
+    }
+}
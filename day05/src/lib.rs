@@ -0,0 +1,311 @@
+// Copyright 2021 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::fmt::{self, Display, Formatter};
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+use utils::geometry::Vec2;
+use utils::input_read::read_parsed_line_input;
+use utils::render::FrameSink;
+use utils::{Solver, Visualize};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MalformedVentLine {
+    /// The start and the end of the line have to be separated by ` -> `.
+    MissingArrow,
+    InvalidPoint(String),
+}
+
+impl Display for MalformedVentLine {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            MalformedVentLine::MissingArrow => {
+                write!(f, "the line endpoints are not separated by ' -> '")
+            }
+            MalformedVentLine::InvalidPoint(raw) => write!(f, "{raw} is not a valid x,y point"),
+        }
+    }
+}
+
+impl std::error::Error for MalformedVentLine {}
+
+#[derive(Debug)]
+pub struct VentLine {
+    start: Vec2,
+    end: Vec2,
+}
+
+impl Display for VentLine {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} -> {}", self.start, self.end)
+    }
+}
+
+fn parse_point(raw: &str) -> Result<Vec2, MalformedVentLine> {
+    raw.parse()
+        .map_err(|_| MalformedVentLine::InvalidPoint(raw.to_string()))
+}
+
+impl FromStr for VentLine {
+    type Err = MalformedVentLine;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (start, end) = s
+            .split_once(" -> ")
+            .ok_or(MalformedVentLine::MissingArrow)?;
+
+        Ok(VentLine {
+            start: parse_point(start)?,
+            end: parse_point(end)?,
+        })
+    }
+}
+
+impl VentLine {
+    fn is_vertical(&self) -> bool {
+        self.start.x == self.end.x
+    }
+
+    fn is_horizontal(&self) -> bool {
+        self.start.y == self.end.y
+    }
+
+    // in the case of this task all the lines are horizontal, vertical or diagonal at 45 degrees,
+    // so walking along them in unit steps of each axis is guaranteed to reach the end
+    fn covered_points(&self) -> Vec<Vec2> {
+        let step = (self.end - self.start).signum();
+        let mut point = self.start;
+        let mut points = vec![point];
+        while point != self.end {
+            point += step;
+            points.push(point);
+        }
+        points
+    }
+}
+
+pub fn part1(input: &[VentLine]) -> usize {
+    let mut coverage: HashMap<_, i32> = HashMap::new();
+
+    input
+        .iter()
+        .filter(|line| line.is_vertical() || line.is_horizontal())
+        .for_each(|line| {
+            for covered_point in line.covered_points() {
+                *coverage.entry(covered_point).or_default() += 1i32;
+            }
+        });
+
+    coverage.values().filter(|&&count| count >= 2).count()
+}
+
+pub fn part2(input: &[VentLine]) -> usize {
+    let mut coverage: HashMap<_, i32> = HashMap::new();
+
+    input.iter().for_each(|line| {
+        for covered_point in line.covered_points() {
+            *coverage.entry(covered_point).or_default() += 1i32;
+        }
+    });
+
+    coverage.values().filter(|&&count| count >= 2).count()
+}
+
+/// Counts the points covered by multiple hydrothermal vent lines.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Day05;
+
+impl Solver for Day05 {
+    const DAY: u8 = 5;
+
+    type Input = Vec<VentLine>;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(&self, input_file: &Path) -> io::Result<Self::Input> {
+        read_parsed_line_input(input_file)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Self::Output1 {
+        part1(input)
+    }
+
+    fn part2(&self, input: &Self::Input) -> Self::Output2 {
+        part2(input)
+    }
+}
+
+impl Visualize for Day05 {
+    // thresholded heatmap of the vents, the n-th frame lights up the points covered by at least n lines
+    fn visualize(&self, input: &Self::Input, sink: &mut dyn FrameSink) -> io::Result<()> {
+        let mut coverage: HashMap<_, usize> = HashMap::new();
+        for line in input {
+            for covered_point in line.covered_points() {
+                *coverage.entry(covered_point).or_default() += 1;
+            }
+        }
+
+        // the vents are never located at negative coordinates
+        let pixels = coverage
+            .into_iter()
+            .filter_map(|(point, count)| {
+                let pixel = (
+                    usize::try_from(point.x).ok()?,
+                    usize::try_from(point.y).ok()?,
+                );
+                Some((pixel, count))
+            })
+            .collect::<Vec<_>>();
+        let width = pixels
+            .iter()
+            .map(|((x, _), _)| x + 1)
+            .max()
+            .unwrap_or_default();
+        let height = pixels
+            .iter()
+            .map(|((_, y), _)| y + 1)
+            .max()
+            .unwrap_or_default();
+        let max_coverage = pixels
+            .iter()
+            .map(|&(_, count)| count)
+            .max()
+            .unwrap_or_default();
+
+        for threshold in 1..=max_coverage {
+            let lit = pixels
+                .iter()
+                .filter(|&&(_, count)| count >= threshold)
+                .map(|&(pixel, _)| pixel)
+                .collect::<Vec<_>>();
+            sink.push_frame((width, height), &lit)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utils::render::FrameBuffer;
+
+    #[test]
+    fn point_cover() {
+        let line1 = VentLine {
+            start: Vec2::new(1, 1),
+            end: Vec2::new(1, 3),
+        };
+        assert_eq!(
+            vec![Vec2::new(1, 1), Vec2::new(1, 2), Vec2::new(1, 3)],
+            line1.covered_points()
+        );
+
+        let line2 = VentLine {
+            start: Vec2::new(9, 7),
+            end: Vec2::new(7, 7),
+        };
+        assert_eq!(
+            vec![Vec2::new(9, 7), Vec2::new(8, 7), Vec2::new(7, 7)],
+            line2.covered_points()
+        );
+
+        let line3 = VentLine {
+            start: Vec2::new(9, 7),
+            end: Vec2::new(7, 9),
+        };
+        assert_eq!(
+            vec![Vec2::new(9, 7), Vec2::new(8, 8), Vec2::new(7, 9)],
+            line3.covered_points()
+        );
+    }
+
+    #[test]
+    fn part1_sample_input() {
+        let input = vec![
+            "0,9 -> 5,9".parse().unwrap(),
+            "8,0 -> 0,8".parse().unwrap(),
+            "9,4 -> 3,4".parse().unwrap(),
+            "2,2 -> 2,1".parse().unwrap(),
+            "7,0 -> 7,4".parse().unwrap(),
+            "6,4 -> 2,0".parse().unwrap(),
+            "0,9 -> 2,9".parse().unwrap(),
+            "3,4 -> 1,4".parse().unwrap(),
+            "0,0 -> 8,8".parse().unwrap(),
+            "5,5 -> 8,2".parse().unwrap(),
+        ];
+
+        let expected = 5;
+
+        assert_eq!(expected, part1(&input))
+    }
+
+    #[test]
+    fn part2_sample_input() {
+        let input = vec![
+            "0,9 -> 5,9".parse().unwrap(),
+            "8,0 -> 0,8".parse().unwrap(),
+            "9,4 -> 3,4".parse().unwrap(),
+            "2,2 -> 2,1".parse().unwrap(),
+            "7,0 -> 7,4".parse().unwrap(),
+            "6,4 -> 2,0".parse().unwrap(),
+            "0,9 -> 2,9".parse().unwrap(),
+            "3,4 -> 1,4".parse().unwrap(),
+            "0,0 -> 8,8".parse().unwrap(),
+            "5,5 -> 8,2".parse().unwrap(),
+        ];
+
+        let expected = 12;
+
+        assert_eq!(expected, part2(&input))
+    }
+
+    #[test]
+    fn visualizing_coverage() {
+        let input = vec![
+            "0,9 -> 5,9".parse().unwrap(),
+            "8,0 -> 0,8".parse().unwrap(),
+            "9,4 -> 3,4".parse().unwrap(),
+            "2,2 -> 2,1".parse().unwrap(),
+            "7,0 -> 7,4".parse().unwrap(),
+            "6,4 -> 2,0".parse().unwrap(),
+            "0,9 -> 2,9".parse().unwrap(),
+            "3,4 -> 1,4".parse().unwrap(),
+            "0,0 -> 8,8".parse().unwrap(),
+            "5,5 -> 8,2".parse().unwrap(),
+        ];
+
+        let mut frames = FrameBuffer::default();
+        Day05.visualize(&input, &mut frames).unwrap();
+        assert_eq!(3, frames.frames.len());
+        assert!(frames.frames.iter().all(|(size, _)| *size == (10, 10)));
+        assert_eq!(12, frames.frames[1].1.len());
+        let mut most_covered = frames.frames[2].1.clone();
+        most_covered.sort_unstable();
+        assert_eq!(vec![(4, 4), (6, 4)], most_covered);
+    }
+
+    #[test]
+    fn malformed_lines() {
+        assert_eq!(
+            Err(MalformedVentLine::MissingArrow),
+            "0,9 5,9".parse::<VentLine>().map(|_| ())
+        );
+        assert_eq!(
+            Err(MalformedVentLine::InvalidPoint("5;9".to_string())),
+            "0,9 -> 5;9".parse::<VentLine>().map(|_| ())
+        );
+    }
+}
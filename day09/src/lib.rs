@@ -0,0 +1,270 @@
+// Copyright 2021 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+use utils::disjoint_set::DisjointSet;
+use utils::grid::{Grid, GridError, Position};
+use utils::input_read::read_parsed;
+use utils::render::FrameSink;
+use utils::{Solver, Visualize};
+
+/// Height of the locations that are not part of any basin.
+const MAX_HEIGHT: u8 = 9;
+
+#[derive(Debug)]
+pub struct HeightMap {
+    grid: Grid<u8>,
+}
+
+impl FromStr for HeightMap {
+    type Err = GridError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(HeightMap { grid: s.parse()? })
+    }
+}
+
+impl HeightMap {
+    fn risk_level(&self, point: Position) -> usize {
+        self.grid[point] as usize + 1
+    }
+
+    fn is_low_point(&self, point: Position) -> bool {
+        let height = self.grid[point];
+        self.grid
+            .neighbours4(point)
+            .all(|neighbour| self.grid[neighbour] > height)
+    }
+
+    fn low_points(&self) -> impl Iterator<Item = Position> + '_ {
+        self.grid
+            .iter_with_pos()
+            .map(|(point, _)| point)
+            .filter(|&point| self.is_low_point(point))
+    }
+
+    /// Labels every location with the index of its basin, numbered in the order of their first
+    /// locations in row-major order, or `None` if it's too high to be part of any basin.
+    pub fn basin_labels(&self) -> Grid<Option<usize>> {
+        let width = self.grid.width();
+        let index = |(x, y): Position| y * width + x;
+        let in_basin = |point: Position| self.grid[point] != MAX_HEIGHT;
+
+        // joining every location with its right and bottom neighbours covers all the adjacent pairs
+        let mut basins = DisjointSet::new(width * self.grid.height());
+        for (point, _) in self.grid.iter_with_pos() {
+            if !in_basin(point) {
+                continue;
+            }
+            let (x, y) = point;
+            for neighbour in [(x + 1, y), (x, y + 1)] {
+                if self.grid.get(neighbour).is_some() && in_basin(neighbour) {
+                    basins.union(index(point), index(neighbour));
+                }
+            }
+        }
+
+        let mut labels = self.grid.map(|_| None);
+        let mut root_labels = HashMap::new();
+        for (point, _) in self.grid.iter_with_pos() {
+            if in_basin(point) {
+                let root = basins.find(index(point));
+                let next_label = root_labels.len();
+                labels[point] = Some(*root_labels.entry(root).or_insert(next_label));
+            }
+        }
+        labels
+    }
+
+    /// Draws every basin with its own letter, cycling through the alphabet,
+    /// and the locations that aren't part of any basin with `#`.
+    pub fn render_basins(&self) -> String {
+        let mut rendered = String::new();
+        for (position, label) in self.basin_labels().iter_with_pos() {
+            if position.0 == 0 && position.1 > 0 {
+                rendered.push('\n');
+            }
+            rendered.push(match label {
+                Some(label) => char::from(b'a' + (label % 26) as u8),
+                None => '#',
+            });
+        }
+        rendered
+    }
+
+    fn basin_sizes(&self) -> Vec<usize> {
+        let mut sizes = Vec::new();
+        for (_, &label) in self.basin_labels().iter_with_pos() {
+            if let Some(label) = label {
+                // labels are assigned in the order of their first appearance
+                if label == sizes.len() {
+                    sizes.push(0)
+                }
+                sizes[label] += 1;
+            }
+        }
+        sizes
+    }
+}
+
+pub fn part1(height_map: &HeightMap) -> usize {
+    height_map
+        .low_points()
+        .map(|point| height_map.risk_level(point))
+        .sum()
+}
+
+pub fn part2(height_map: &HeightMap) -> usize {
+    let mut sizes = height_map.basin_sizes();
+    sizes.sort_by_key(|&size| Reverse(size));
+
+    sizes.iter().take(3).product()
+}
+
+/// Finds the low points and the basins of the heightmap.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Day09;
+
+impl Solver for Day09 {
+    const DAY: u8 = 9;
+
+    type Input = HeightMap;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(&self, input_file: &Path) -> io::Result<Self::Input> {
+        read_parsed(input_file)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Self::Output1 {
+        part1(input)
+    }
+
+    fn part2(&self, input: &Self::Input) -> Self::Output2 {
+        part2(input)
+    }
+}
+
+impl Visualize for Day09 {
+    // all the basins, followed by the three largest ones whose sizes make up the answer
+    fn visualize(&self, input: &Self::Input, sink: &mut dyn FrameSink) -> io::Result<()> {
+        let size = (input.grid.width(), input.grid.height());
+        let labels = input.basin_labels();
+        let mut sizes = input
+            .basin_sizes()
+            .into_iter()
+            .enumerate()
+            .collect::<Vec<_>>();
+        sizes.sort_by_key(|&(_, size)| Reverse(size));
+        let largest = sizes
+            .iter()
+            .take(3)
+            .map(|&(label, _)| label)
+            .collect::<Vec<_>>();
+
+        let basins = labels
+            .iter_with_pos()
+            .filter_map(|(point, label)| label.map(|label| (point, label)))
+            .collect::<Vec<_>>();
+        let all = basins.iter().map(|&(point, _)| point).collect::<Vec<_>>();
+        sink.push_frame(size, &all)?;
+
+        let largest = basins
+            .iter()
+            .filter(|(_, label)| largest.contains(label))
+            .map(|&(point, _)| point)
+            .collect::<Vec<_>>();
+        sink.push_frame(size, &largest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utils::render::FrameBuffer;
+
+    #[test]
+    fn part1_sample_input() {
+        let input: HeightMap = "2199943210
+3987894921
+9856789892
+8767896789
+9899965678"
+            .parse()
+            .unwrap();
+
+        let expected = 15;
+
+        assert_eq!(expected, part1(&input))
+    }
+
+    #[test]
+    fn part2_sample_input() {
+        let input: HeightMap = "2199943210
+3987894921
+9856789892
+8767896789
+9899965678"
+            .parse()
+            .unwrap();
+
+        let expected = 1134;
+
+        assert_eq!(expected, part2(&input))
+    }
+
+    #[test]
+    fn labelling_basins() {
+        let input: HeightMap = "2199943210
+3987894921
+9856789892
+8767896789
+9899965678"
+            .parse()
+            .unwrap();
+
+        let labels = input.basin_labels();
+        assert_eq!(Some(0), labels[(0, 0)]);
+        assert_eq!(None, labels[(2, 0)]);
+        assert_eq!(Some(1), labels[(9, 0)]);
+        assert_eq!(labels[(2, 2)], labels[(4, 3)]);
+        assert_eq!(vec![3, 9, 14, 9], input.basin_sizes());
+        assert_eq!(
+            "aa###bbbbb\na#ccc#b#bb\n#ccccc#d#b\nccccc#ddd#\n#c###ddddd",
+            input.render_basins()
+        );
+    }
+
+    #[test]
+    fn visualizing_basins() {
+        let input: HeightMap = "2199943210
+3987894921
+9856789892
+8767896789
+9899965678"
+            .parse()
+            .unwrap();
+
+        let mut frames = FrameBuffer::default();
+        Day09.visualize(&input, &mut frames).unwrap();
+        assert_eq!(2, frames.frames.len());
+        assert_eq!((10, 5), frames.frames[0].0);
+        assert_eq!(3 + 9 + 14 + 9, frames.frames[0].1.len());
+        assert_eq!(14 + 9 + 9, frames.frames[1].1.len());
+    }
+}
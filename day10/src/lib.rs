@@ -0,0 +1,340 @@
+// Copyright 2021 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::{self, Display, Formatter};
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+use utils::input_read::read_parsed_line_input;
+use utils::Solver;
+
+struct Stack<T> {
+    inner: Vec<T>,
+    size: usize,
+}
+
+impl<T> Stack<T> {
+    fn new() -> Self {
+        Stack {
+            inner: Vec::new(),
+            size: 0,
+        }
+    }
+
+    fn push(&mut self, value: T) {
+        self.inner.push(value)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.inner.pop()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+}
+
+impl<T: Clone> Clone for Stack<T> {
+    fn clone(&self) -> Self {
+        Stack {
+            inner: self.inner.clone(),
+            size: self.size,
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+struct Bracket {
+    typ: BracketType,
+    opening: bool,
+}
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub struct InvalidBracket {
+    position: usize,
+    found: char,
+}
+
+impl Display for InvalidBracket {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "'{}' at position {} is not a valid bracket",
+            self.found, self.position
+        )
+    }
+}
+
+impl std::error::Error for InvalidBracket {}
+
+impl TryFrom<char> for Bracket {
+    type Error = char;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            '(' => Ok(Bracket::new(BracketType::Parentheses, true)),
+            ')' => Ok(Bracket::new(BracketType::Parentheses, false)),
+            '[' => Ok(Bracket::new(BracketType::Square, true)),
+            ']' => Ok(Bracket::new(BracketType::Square, false)),
+            '{' => Ok(Bracket::new(BracketType::Curly, true)),
+            '}' => Ok(Bracket::new(BracketType::Curly, false)),
+            '<' => Ok(Bracket::new(BracketType::Angle, true)),
+            '>' => Ok(Bracket::new(BracketType::Angle, false)),
+            n => Err(n),
+        }
+    }
+}
+
+/// Single line of the navigation subsystem.
+#[derive(Debug, Clone)]
+pub struct Line(Vec<Bracket>);
+
+impl FromStr for Line {
+    type Err = InvalidBracket;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.chars()
+            .enumerate()
+            .map(|(position, c)| {
+                Bracket::try_from(c).map_err(|found| InvalidBracket { position, found })
+            })
+            .collect::<Result<_, _>>()
+            .map(Line)
+    }
+}
+
+impl Bracket {
+    fn new(typ: BracketType, opening: bool) -> Self {
+        Bracket { typ, opening }
+    }
+
+    fn is_opening(&self) -> bool {
+        self.opening
+    }
+
+    fn inverse(&self) -> Bracket {
+        Bracket {
+            typ: self.typ,
+            opening: !self.opening,
+        }
+    }
+
+    fn error_score(&self) -> usize {
+        self.typ.error_score()
+    }
+
+    fn completion_score(&self) -> usize {
+        self.typ.completion_score()
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+enum BracketType {
+    Square,
+    Curly,
+    Angle,
+    Parentheses,
+}
+
+impl BracketType {
+    fn error_score(&self) -> usize {
+        match self {
+            BracketType::Parentheses => 3,
+            BracketType::Square => 57,
+            BracketType::Curly => 1197,
+            BracketType::Angle => 25137,
+        }
+    }
+
+    fn completion_score(&self) -> usize {
+        match self {
+            BracketType::Parentheses => 1,
+            BracketType::Square => 2,
+            BracketType::Curly => 3,
+            BracketType::Angle => 4,
+        }
+    }
+}
+
+#[derive(Debug)]
+enum LineError {
+    Incomplete,
+    Corrupted(Bracket),
+}
+
+impl LineError {
+    fn is_incomplete(&self) -> bool {
+        matches!(self, LineError::Incomplete)
+    }
+}
+
+fn validate_line(line: &Line) -> Result<(), LineError> {
+    let mut stack = Stack::new();
+
+    for &bracket in &line.0 {
+        if bracket.is_opening() {
+            stack.push(bracket)
+        } else {
+            let popped = match stack.pop() {
+                None => return Err(LineError::Corrupted(bracket)),
+                Some(bracket) => bracket,
+            };
+            if popped.inverse() != bracket {
+                return Err(LineError::Corrupted(bracket));
+            }
+        }
+    }
+
+    if !stack.is_empty() {
+        Err(LineError::Incomplete)
+    } else {
+        Ok(())
+    }
+}
+
+fn complete_line(incomplete_line: &Line) -> Vec<Bracket> {
+    let mut stack = Stack::new();
+
+    // first, fill up the stack with available characters
+    for &bracket in &incomplete_line.0 {
+        if bracket.is_opening() {
+            stack.push(bracket)
+        } else {
+            stack.pop();
+        }
+    }
+
+    let mut completion_brackets = Vec::new();
+
+    while let Some(popped) = stack.pop() {
+        completion_brackets.push(popped.inverse())
+    }
+
+    completion_brackets
+}
+
+fn calculate_completion_score(completion_brackets: Vec<Bracket>) -> usize {
+    let mut score = 0;
+
+    for bracket in completion_brackets {
+        score *= 5;
+        score += bracket.completion_score()
+    }
+
+    score
+}
+
+pub fn part1(input: &[Line]) -> usize {
+    input
+        .iter()
+        .map(|line| match validate_line(line) {
+            Err(LineError::Corrupted(bracket)) => bracket.error_score(),
+            _ => 0,
+        })
+        .sum()
+}
+
+pub fn part2(input: &[Line]) -> usize {
+    let mut scores = input
+        .iter()
+        .filter(|line| match validate_line(line) {
+            Err(err) => err.is_incomplete(),
+            _ => false,
+        })
+        .map(|incomplete_line| calculate_completion_score(complete_line(incomplete_line)))
+        .collect::<Vec<_>>();
+
+    scores.sort_unstable();
+    scores[scores.len() / 2]
+}
+
+/// Scores the corrupted and the incomplete navigation subsystem lines.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Day10;
+
+impl Solver for Day10 {
+    const DAY: u8 = 10;
+
+    type Input = Vec<Line>;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(&self, input_file: &Path) -> io::Result<Self::Input> {
+        read_parsed_line_input(input_file)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Self::Output1 {
+        part1(input)
+    }
+
+    fn part2(&self, input: &Self::Input) -> Self::Output2 {
+        part2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_sample_input() {
+        let input = vec![
+            "[({(<(())[]>[[{[]{<()<>>".parse().unwrap(),
+            "[(()[<>])]({[<{<<[]>>(".parse().unwrap(),
+            "{([(<{}[<>[]}>{[]{[(<()>".parse().unwrap(),
+            "(((({<>}<{<{<>}{[]{[]{}".parse().unwrap(),
+            "[[<[([]))<([[{}[[()]]]".parse().unwrap(),
+            "[{[{({}]{}}([{[{{{}}([]".parse().unwrap(),
+            "{<[[]]>}<{[{[{[]{()[[[]".parse().unwrap(),
+            "[<(<(<(<{}))><([]([]()".parse().unwrap(),
+            "<{([([[(<>()){}]>(<<{{".parse().unwrap(),
+            "<{([{{}}[<[[[<>{}]]]>[]]".parse().unwrap(),
+        ];
+
+        let expected = 26397;
+
+        assert_eq!(expected, part1(&input))
+    }
+
+    #[test]
+    fn part2_sample_input() {
+        let input = vec![
+            "[({(<(())[]>[[{[]{<()<>>".parse().unwrap(),
+            "[(()[<>])]({[<{<<[]>>(".parse().unwrap(),
+            "{([(<{}[<>[]}>{[]{[(<()>".parse().unwrap(),
+            "(((({<>}<{<{<>}{[]{[]{}".parse().unwrap(),
+            "[[<[([]))<([[{}[[()]]]".parse().unwrap(),
+            "[{[{({}]{}}([{[{{{}}([]".parse().unwrap(),
+            "{<[[]]>}<{[{[{[]{()[[[]".parse().unwrap(),
+            "[<(<(<(<{}))><([]([]()".parse().unwrap(),
+            "<{([([[(<>()){}]>(<<{{".parse().unwrap(),
+            "<{([{{}}[<[[[<>{}]]]>[]]".parse().unwrap(),
+        ];
+
+        let expected = 288957;
+
+        assert_eq!(expected, part2(&input))
+    }
+
+    #[test]
+    fn invalid_brackets() {
+        assert_eq!(
+            Err(InvalidBracket {
+                position: 3,
+                found: 'x'
+            }),
+            "[({x)}]".parse::<Line>().map(|_| ())
+        );
+    }
+}
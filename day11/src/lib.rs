@@ -0,0 +1,291 @@
+// Copyright 2021 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashSet;
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+use utils::grid::{Grid, GridError, Position};
+use utils::input_read::read_parsed;
+use utils::render::FrameSink;
+use utils::{Solver, Visualize};
+
+#[derive(Debug, Clone)]
+pub struct SquidGrid {
+    grid: Grid<u8>,
+}
+
+impl FromStr for SquidGrid {
+    type Err = GridError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(SquidGrid { grid: s.parse()? })
+    }
+}
+
+impl SquidGrid {
+    /// Current energy levels of the octopuses.
+    pub fn grid(&self) -> &Grid<u8> {
+        &self.grid
+    }
+
+    fn flash(&mut self, octopus: Position, flashed: &mut HashSet<Position>) {
+        flashed.insert(octopus);
+
+        for adjacent in self.grid.neighbours8(octopus) {
+            self.grid[adjacent] += 1;
+
+            // if adjacent's energy went above 9 and it hasn't flashed during this step,
+            // it should flash
+            if self.grid[adjacent] > 9 && !flashed.contains(&adjacent) {
+                self.flash(adjacent, flashed);
+            }
+        }
+    }
+
+    fn flash_all(&mut self, to_flash: Vec<Position>) -> HashSet<Position> {
+        let mut flashed = HashSet::new();
+
+        for octopus in to_flash {
+            if !flashed.contains(&octopus) {
+                self.flash(octopus, &mut flashed);
+            }
+        }
+
+        flashed
+    }
+
+    fn simulate_step(&mut self) -> usize {
+        // First, the energy level of each octopus increases by 1.
+        for squid in self.grid.iter_mut() {
+            *squid += 1;
+        }
+        let to_flash = self
+            .grid
+            .iter_with_pos()
+            .filter(|(_, &squid)| squid > 9)
+            .map(|(octopus, _)| octopus)
+            .collect();
+
+        // Then, any octopus with an energy level greater than 9 flashes.
+        let flashed = self.flash_all(to_flash);
+        let flashed_count = flashed.len();
+
+        for octopus in flashed {
+            // Finally, any octopus that flashed during this step has its energy level set to 0, as it used all of its energy to flash.
+            self.grid[octopus] = 0;
+        }
+        flashed_count
+    }
+
+    /// Total number of flashes during the specified number of steps.
+    pub fn flashes_after(&self, steps: usize) -> usize {
+        let mut grid = self.clone();
+        (0..steps).map(|_| grid.simulate_step()).sum()
+    }
+
+    /// Keeps simulating until the energy levels at the end of a step satisfy the predicate
+    /// and returns the number of that step. Never returns if the predicate is never satisfied.
+    pub fn simulate_until<P>(&mut self, predicate: P) -> usize
+    where
+        P: Fn(&Grid<u8>) -> bool,
+    {
+        let mut step = 0;
+        loop {
+            step += 1;
+            self.simulate_step();
+            if predicate(&self.grid) {
+                return step;
+            }
+        }
+    }
+}
+
+pub fn part1(grid: SquidGrid) -> usize {
+    grid.flashes_after(100)
+}
+
+pub fn part2(mut grid: SquidGrid) -> usize {
+    // all the octopuses flashed simultaneously if all of them got reset
+    grid.simulate_until(|grid| grid.iter_with_pos().all(|(_, &energy)| energy == 0))
+}
+
+/// Simulates the flashing dumbo octopuses.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Day11;
+
+impl Solver for Day11 {
+    const DAY: u8 = 11;
+
+    type Input = SquidGrid;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(&self, input_file: &Path) -> io::Result<Self::Input> {
+        read_parsed(input_file)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Self::Output1 {
+        part1(input.clone())
+    }
+
+    fn part2(&self, input: &Self::Input) -> Self::Output2 {
+        part2(input.clone())
+    }
+}
+
+/// Number of steps after which the visualisation stops waiting for the octopuses to synchronise.
+pub const MAX_VISUALIZED_STEPS: usize = 1000;
+
+// octopuses flashing during every step, up until all of them flash simultaneously
+fn push_flash_frames(
+    input: &SquidGrid,
+    sink: &mut dyn FrameSink,
+    max_steps: usize,
+) -> io::Result<()> {
+    let mut grid = input.clone();
+    let size = (grid.grid.width(), grid.grid.height());
+    for _ in 0..max_steps {
+        grid.simulate_step();
+        let flashed = grid
+            .grid
+            .iter_with_pos()
+            .filter(|(_, &energy)| energy == 0)
+            .map(|(octopus, _)| octopus)
+            .collect::<Vec<_>>();
+        sink.push_frame(size, &flashed)?;
+        if flashed.len() == size.0 * size.1 {
+            return Ok(());
+        }
+    }
+    Err(io::Error::other(format!(
+        "the octopuses did not synchronise within {max_steps} steps"
+    )))
+}
+
+impl Visualize for Day11 {
+    fn visualize(&self, input: &Self::Input, sink: &mut dyn FrameSink) -> io::Result<()> {
+        push_flash_frames(input, sink, MAX_VISUALIZED_STEPS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utils::render::FrameBuffer;
+
+    #[test]
+    fn part1_sample_input() {
+        let input: SquidGrid = "5483143223
+2745854711
+5264556173
+6141336146
+6357385478
+4167524645
+2176841721
+6882881134
+4846848554
+5283751526"
+            .parse()
+            .unwrap();
+
+        let expected = 1656;
+
+        assert_eq!(expected, part1(input))
+    }
+
+    #[test]
+    fn part2_sample_input() {
+        let input: SquidGrid = "5483143223
+2745854711
+5264556173
+6141336146
+6357385478
+4167524645
+2176841721
+6882881134
+4846848554
+5283751526"
+            .parse()
+            .unwrap();
+
+        let expected = 195;
+
+        assert_eq!(expected, part2(input))
+    }
+
+    #[test]
+    fn step_queries() {
+        let input: SquidGrid = "5483143223
+2745854711
+5264556173
+6141336146
+6357385478
+4167524645
+2176841721
+6882881134
+4846848554
+5283751526"
+            .parse()
+            .unwrap();
+
+        assert_eq!(204, input.flashes_after(10));
+        let mut grid = input.clone();
+        let first_flash = grid.simulate_until(|grid| grid.iter_with_pos().any(|(_, &e)| e == 0));
+        assert_eq!(2, first_flash);
+        assert_eq!(
+            35,
+            grid.grid().iter_with_pos().filter(|(_, &e)| e == 0).count()
+        );
+    }
+
+    #[test]
+    fn rectangular_grid() {
+        let input: SquidGrid = "19\n11\n11".parse().unwrap();
+        assert_eq!((2, 3), (input.grid().width(), input.grid().height()));
+        assert_eq!(1, input.flashes_after(1));
+    }
+
+    #[test]
+    fn visualizing_flashes() {
+        let input: SquidGrid = "5483143223
+2745854711
+5264556173
+6141336146
+6357385478
+4167524645
+2176841721
+6882881134
+4846848554
+5283751526"
+            .parse()
+            .unwrap();
+
+        let mut frames = FrameBuffer::default();
+        Day11.visualize(&input, &mut frames).unwrap();
+        assert_eq!(195, frames.frames.len());
+        assert_eq!(
+            1656,
+            frames.frames[..100]
+                .iter()
+                .map(|(_, flashed)| flashed.len())
+                .sum::<usize>()
+        );
+        assert_eq!(100, frames.frames[194].1.len());
+
+        let mut frames = FrameBuffer::default();
+        assert!(push_flash_frames(&input, &mut frames, 100).is_err());
+        assert_eq!(100, frames.frames.len());
+    }
+}
@@ -0,0 +1,156 @@
+// Copyright 2021 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{Graph, GraphError, Node};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use utils::memo::Memo;
+
+/// Maximum number of caves whose visits fit in the `u64` mask.
+const MAX_CAVES: usize = u64::BITS as usize;
+
+// cave the path currently is in, the mask of the visited small caves and the small cave
+// (if any) that got visited more than once alongside the number of its visits
+type PathState = (usize, u64, Option<(usize, usize)>);
+
+/// Graph with the caves replaced by their indices, where the visited small caves are tracked
+/// in a bitmask rather than a map keyed by the cave names.
+#[derive(Debug)]
+pub(crate) struct CompactGraph {
+    neighbours: Vec<Vec<usize>>,
+    is_big: Vec<bool>,
+    start: usize,
+    end: usize,
+}
+
+impl CompactGraph {
+    pub(crate) fn new(graph: &Graph) -> Result<Self, GraphError> {
+        let caves = graph.nodes.len();
+        if caves > MAX_CAVES {
+            return Err(GraphError::TooManyCaves(caves));
+        }
+
+        let find = |predicate: fn(&Node) -> bool| graph.nodes.iter().position(predicate);
+
+        Ok(CompactGraph {
+            neighbours: graph.neighbours.clone(),
+            is_big: graph.nodes.iter().map(|node| node.is_big).collect(),
+            start: find(Node::is_start).ok_or(GraphError::MissingStart)?,
+            end: find(Node::is_end).ok_or(GraphError::MissingEnd)?,
+        })
+    }
+
+    /// Counts all paths from `start` to `end`, where a single small cave can be visited
+    /// up to `budget` times and all the remaining ones at most once.
+    pub(crate) fn count_paths(&self, budget: usize) -> usize {
+        self.count_from(
+            &mut Memo::new(),
+            (self.start, 1 << self.start, None),
+            budget,
+        )
+    }
+
+    /// Equivalent of `count_paths`, but each branch leaving the `start` cave is counted
+    /// in a separate rayon task with its own visited mask and cache.
+    #[cfg(feature = "parallel")]
+    pub(crate) fn count_paths_parallel(&self, budget: usize) -> usize {
+        let visited = 1 << self.start;
+        self.neighbours[self.start]
+            .par_iter()
+            .map(|&next| {
+                let visited = if self.is_big[next] {
+                    visited
+                } else {
+                    visited | 1 << next
+                };
+                self.count_from(&mut Memo::new(), (next, visited, None), budget)
+            })
+            .sum()
+    }
+
+    fn count_from(
+        &self,
+        memo: &mut Memo<PathState, usize>,
+        state: PathState,
+        budget: usize,
+    ) -> usize {
+        let (cave, visited, revisited) = state;
+        if cave == self.end {
+            return 1;
+        }
+
+        memo.get_or_insert_with(state, |memo| {
+            let mut paths = 0;
+            for &next in &self.neighbours[cave] {
+                let bit = 1 << next;
+                let next_state = if self.is_big[next] {
+                    (next, visited, revisited)
+                } else if visited & bit == 0 {
+                    (next, visited | bit, revisited)
+                } else if next == self.start || next == self.end {
+                    continue;
+                } else {
+                    match revisited {
+                        None if budget > 1 => (next, visited, Some((next, 2))),
+                        Some((small, visits)) if small == next && visits < budget => {
+                            (next, visited, Some((small, visits + 1)))
+                        }
+                        _ => continue,
+                    }
+                };
+                paths += self.count_from(memo, next_state, budget)
+            }
+            paths
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{count_paths_using, Algorithm, Edge};
+
+    #[test]
+    fn matches_the_recursive_counting() {
+        for seed in 0..5 {
+            let input = generators::day12::cave_system(seed, 8)
+                .lines()
+                .map(|edge| edge.parse().unwrap())
+                .collect::<Vec<Edge>>();
+            let graph = CompactGraph::new(&Graph::construct(&input).unwrap()).unwrap();
+
+            for budget in 1..=3 {
+                let expected = count_paths_using(&input, budget, Algorithm::Sequential).unwrap();
+                assert_eq!(expected, graph.count_paths(budget));
+                #[cfg(feature = "parallel")]
+                assert_eq!(expected, graph.count_paths_parallel(budget));
+            }
+        }
+    }
+
+    #[test]
+    fn too_many_caves() {
+        let input = (0..MAX_CAVES)
+            .map(|cave| format!("start-c{cave}").parse().unwrap())
+            .chain(["c0-end".parse().unwrap()])
+            .collect::<Vec<Edge>>();
+        let graph = Graph::construct(&input).unwrap();
+        assert_eq!(
+            Err(GraphError::TooManyCaves(MAX_CAVES + 2)),
+            CompactGraph::new(&graph).map(|_| ())
+        );
+        // the memoized counting falls back to the sequential one instead of failing
+        assert_eq!(Ok(1), count_paths_using(&input, 1, Algorithm::Memoized));
+    }
+}
@@ -0,0 +1,811 @@
+// Copyright 2021 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::bitmask::CompactGraph;
+use std::collections::HashMap;
+use std::fmt::{Debug, Display, Formatter};
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+use utils::input_read::read_parsed_line_input;
+use utils::Solver;
+
+mod bitmask;
+
+/// Number of times a single small cave is allowed to be visited in the first part of the puzzle.
+pub const PART1_VISIT_BUDGET: usize = 1;
+
+/// Number of times a single small cave is allowed to be visited in the second part of the puzzle.
+pub const PART2_VISIT_BUDGET: usize = 2;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Algorithm {
+    /// Caches the path counts keyed on the current cave and the bitmask of the visited small caves.
+    #[default]
+    Memoized,
+    Sequential,
+    /// Distributes the branches going out of the `start` node across the rayon thread pool.
+    #[cfg(feature = "parallel")]
+    Parallel,
+}
+
+impl FromStr for Algorithm {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "memoized" => Ok(Algorithm::Memoized),
+            "sequential" => Ok(Algorithm::Sequential),
+            #[cfg(feature = "parallel")]
+            "parallel" => Ok(Algorithm::Parallel),
+            other => Err(format!("{other} is not a valid algorithm")),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum GraphError {
+    /// The node looks like `start` or `end`, but with different casing, e.g. `START`.
+    InconsistentTerminalNode(String),
+    /// The edge connects the node to itself.
+    SelfLoop(Node),
+    /// The same pair of nodes has been connected more than once.
+    DuplicateEdge(Node, Node),
+    /// Two big caves are adjacent, meaning there are infinitely many paths between them.
+    AdjacentBigCaves(Node, Node),
+    MissingStart,
+    MissingEnd,
+    /// The graph has more caves than the bitmask of the visited ones can hold.
+    TooManyCaves(usize),
+}
+
+impl Display for GraphError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GraphError::InconsistentTerminalNode(name) => write!(
+                f,
+                "node '{name}' is inconsistent with either 'start' or 'end' node"
+            ),
+            GraphError::SelfLoop(node) => write!(f, "node '{node}' is connected to itself"),
+            GraphError::DuplicateEdge(from, to) => {
+                write!(f, "edge '{from}-{to}' is specified more than once")
+            }
+            GraphError::AdjacentBigCaves(from, to) => write!(
+                f,
+                "big caves '{from}' and '{to}' are adjacent resulting in infinite number of paths"
+            ),
+            GraphError::MissingStart => write!(f, "the graph does not contain the 'start' node"),
+            GraphError::MissingEnd => write!(f, "the graph does not contain the 'end' node"),
+            GraphError::TooManyCaves(caves) => {
+                write!(
+                    f,
+                    "the graph contains {caves} caves, which is too many to track"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for GraphError {}
+
+/// Identifier of the cave within its [`Graph`], i.e. the order of its first appearance
+/// in the input.
+pub type NodeId = usize;
+
+#[derive(Debug)]
+pub struct Graph {
+    edges: HashMap<Node, Vec<Node>>,
+    // the same edges with the caves identified by their ids
+    nodes: Vec<Node>,
+    neighbours: Vec<Vec<NodeId>>,
+}
+
+impl Graph {
+    pub fn construct(raw_edges: &[Edge]) -> Result<Self, GraphError> {
+        let mut edges: HashMap<_, Vec<_>> = HashMap::new();
+        for edge in raw_edges.iter().cloned() {
+            edge.validate()?;
+            if edges
+                .get(&edge.from)
+                .map(|neighbours| neighbours.contains(&edge.to))
+                .unwrap_or_default()
+            {
+                return Err(GraphError::DuplicateEdge(edge.from, edge.to));
+            }
+
+            edges
+                .entry(edge.from.clone())
+                .or_default()
+                .push(edge.to.clone());
+            edges.entry(edge.to).or_default().push(edge.from);
+        }
+
+        if !edges.keys().any(|node| node.is_start()) {
+            return Err(GraphError::MissingStart);
+        }
+        if !edges.keys().any(|node| node.is_end()) {
+            return Err(GraphError::MissingEnd);
+        }
+
+        let mut ids = HashMap::new();
+        let mut nodes = Vec::new();
+        for node in raw_edges.iter().flat_map(|edge| [&edge.from, &edge.to]) {
+            if !ids.contains_key(node) {
+                ids.insert(node, nodes.len());
+                nodes.push(node.clone());
+            }
+        }
+        let neighbours = nodes
+            .iter()
+            .map(|node| edges[node].iter().map(|neighbour| ids[neighbour]).collect())
+            .collect();
+
+        Ok(Graph {
+            edges,
+            nodes,
+            neighbours,
+        })
+    }
+
+    /// Cave identified by the id, as yielded by [`enumerate_paths`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the id does not belong to this graph.
+    pub fn node(&self, id: NodeId) -> &Node {
+        &self.nodes[id]
+    }
+
+    /// Enumerates all paths from `start` to `end`, where every small cave is visited at most once,
+    /// apart from a single one that can be visited twice if `allow_double_small` is set.
+    pub fn all_paths(&self, allow_double_small: bool) -> impl Iterator<Item = Vec<&Node>> {
+        let budget = if allow_double_small {
+            PART2_VISIT_BUDGET
+        } else {
+            PART1_VISIT_BUDGET
+        };
+        enumerate_paths(self, budget).map(|path| path.into_iter().map(|id| self.node(id)).collect())
+    }
+
+    /// Counts all paths from `start` to `end`, where a single small cave can be visited
+    /// up to `budget` times and all the remaining ones at most once.
+    /// The memoized and parallel algorithms fall back to the sequential one if the graph has
+    /// too many caves for their bitmask.
+    pub fn count_paths(&self, budget: usize, algorithm: Algorithm) -> usize {
+        match algorithm {
+            Algorithm::Memoized => match CompactGraph::new(self) {
+                Ok(compact) => compact.count_paths(budget),
+                Err(_) => self.count_paths_sequentially(budget),
+            },
+            Algorithm::Sequential => self.count_paths_sequentially(budget),
+            #[cfg(feature = "parallel")]
+            Algorithm::Parallel => self.count_paths_in_parallel(budget),
+        }
+    }
+
+    fn count_paths_sequentially(&self, budget: usize) -> usize {
+        // the existence of the start node is checked during graph construction
+        let start = self.start().unwrap();
+        start.count_paths(self, HashMap::new(), None, budget)
+    }
+
+    // each branch leaving the `start` node is explored in a separate rayon task
+    #[cfg(feature = "parallel")]
+    fn count_paths_in_parallel(&self, budget: usize) -> usize {
+        match CompactGraph::new(self) {
+            Ok(compact) => compact.count_paths_parallel(budget),
+            Err(_) => self.count_paths_sequentially(budget),
+        }
+    }
+
+    fn start(&self) -> Option<&Node> {
+        self.edges.keys().find(|node| node.is_start())
+    }
+}
+
+/// Edge, as found in the input, that isn't in the form of `from-to`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MalformedEdge(pub String);
+
+impl Display for MalformedEdge {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "'{}' is not a valid edge", self.0)
+    }
+}
+
+impl std::error::Error for MalformedEdge {}
+
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+pub struct Node {
+    name: String,
+    is_big: bool,
+}
+
+impl Display for Node {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.name, f)
+    }
+}
+
+impl Node {
+    fn new(name: &str) -> Self {
+        Node {
+            name: name.to_owned(),
+            is_big: name.to_ascii_uppercase() == name,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn is_big(&self) -> bool {
+        self.is_big
+    }
+
+    fn is_end(&self) -> bool {
+        self.name == "end"
+    }
+
+    fn is_start(&self) -> bool {
+        self.name == "start"
+    }
+
+    // `visits` keeps track of how many times each small cave has been entered on the current path,
+    // while `revisited` is the single small cave (if any) that has been allowed to exceed a single visit
+    fn count_paths(
+        &self,
+        graph: &Graph,
+        mut visits: HashMap<Node, usize>,
+        revisited: Option<&Node>,
+        budget: usize,
+    ) -> usize {
+        if self.is_end() {
+            return 1;
+        }
+        if !self.is_big {
+            *visits.entry(self.clone()).or_default() += 1;
+        }
+
+        let mut paths = 0;
+        // every node in the graph has at least a single neighbour by construction
+        for node in &graph.edges[self] {
+            let node_visits = visits.get(node).copied().unwrap_or_default();
+            if node.is_big || node_visits == 0 {
+                paths += node.count_paths(graph, visits.clone(), revisited, budget)
+            } else if node_visits < budget
+                && !node.is_end()
+                && !node.is_start()
+                && revisited.map(|revisited| revisited == node).unwrap_or(true)
+            {
+                paths += node.count_paths(graph, visits.clone(), Some(node), budget)
+            }
+        }
+        paths
+    }
+}
+
+/// Lazily walks the graph in depth-first manner, yielding every path from `start` to `end`
+/// that respects the small cave visit budget.
+pub struct Paths<'a> {
+    graph: &'a Graph,
+    budget: usize,
+    // caves on the current path alongside index of the next neighbour to explore
+    stack: Vec<(NodeId, usize)>,
+    visits: Vec<usize>,
+    revisited: Option<NodeId>,
+}
+
+impl<'a> Paths<'a> {
+    fn new(graph: &'a Graph, budget: usize) -> Self {
+        let mut paths = Paths {
+            graph,
+            budget,
+            stack: Vec::new(),
+            visits: vec![0; graph.nodes.len()],
+            revisited: None,
+        };
+        if let Some(start) = graph.nodes.iter().position(Node::is_start) {
+            paths.enter(start)
+        }
+        paths
+    }
+
+    fn can_visit(&self, id: NodeId) -> bool {
+        let node = self.graph.node(id);
+        let node_visits = self.visits[id];
+        node.is_big
+            || node_visits == 0
+            || (node_visits < self.budget
+                && !node.is_end()
+                && !node.is_start()
+                && self
+                    .revisited
+                    .map(|revisited| revisited == id)
+                    .unwrap_or(true))
+    }
+
+    fn enter(&mut self, id: NodeId) {
+        self.stack.push((id, 0));
+        if !self.graph.node(id).is_big {
+            self.visits[id] += 1;
+            if self.visits[id] > 1 {
+                self.revisited = Some(id)
+            }
+        }
+    }
+
+    fn leave(&mut self) {
+        if let Some((id, _)) = self.stack.pop() {
+            if !self.graph.node(id).is_big {
+                self.visits[id] -= 1;
+                if self.visits[id] <= 1 && self.revisited == Some(id) {
+                    self.revisited = None
+                }
+            }
+        }
+    }
+}
+
+impl Iterator for Paths<'_> {
+    type Item = Vec<NodeId>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (id, next_neighbour) = *self.stack.last()?;
+            if self.graph.node(id).is_end() {
+                let path = self.stack.iter().map(|&(id, _)| id).collect();
+                self.leave();
+                return Some(path);
+            }
+
+            match self.graph.neighbours[id].get(next_neighbour).copied() {
+                Some(neighbour) => {
+                    self.stack.last_mut().unwrap().1 += 1;
+                    if self.can_visit(neighbour) {
+                        self.enter(neighbour)
+                    }
+                }
+                None => self.leave(),
+            }
+        }
+    }
+}
+
+/// Enumerates all paths from `start` to `end`, where a single small cave can be visited
+/// up to `budget` times and all the remaining ones at most once.
+/// The caves are given by their ids, which can be resolved with [`Graph::node`].
+pub fn enumerate_paths(graph: &Graph, budget: usize) -> impl Iterator<Item = Vec<NodeId>> + '_ {
+    Paths::new(graph, budget)
+}
+
+#[derive(Debug, Clone)]
+pub struct Edge {
+    from: Node,
+    to: Node,
+}
+
+impl Edge {
+    fn validate(&self) -> Result<(), GraphError> {
+        for node in [&self.from, &self.to] {
+            if !node.is_start()
+                && !node.is_end()
+                && (node.name.eq_ignore_ascii_case("start")
+                    || node.name.eq_ignore_ascii_case("end"))
+            {
+                return Err(GraphError::InconsistentTerminalNode(node.name.clone()));
+            }
+        }
+
+        if self.from == self.to {
+            return Err(GraphError::SelfLoop(self.from.clone()));
+        }
+
+        if self.from.is_big && self.to.is_big {
+            return Err(GraphError::AdjacentBigCaves(
+                self.from.clone(),
+                self.to.clone(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for Edge {
+    type Err = MalformedEdge;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let malformed = || MalformedEdge(s.to_string());
+        let (from, to) = s.split_once('-').ok_or_else(malformed)?;
+        if from.is_empty() || to.is_empty() || to.contains('-') {
+            return Err(malformed());
+        }
+
+        Ok(Edge {
+            from: Node::new(from),
+            to: Node::new(to),
+        })
+    }
+}
+
+/// Constructs the graph and counts all its paths from `start` to `end` using the chosen algorithm,
+/// where a single small cave can be visited up to `budget` times and all the remaining ones
+/// at most once. See [`Graph::count_paths`] for the details.
+pub fn count_paths_using(
+    input: &[Edge],
+    budget: usize,
+    algorithm: Algorithm,
+) -> Result<usize, GraphError> {
+    Ok(Graph::construct(input)?.count_paths(budget, algorithm))
+}
+
+/// Reads the edges and validates the cave system they form.
+pub fn read_graph<P: AsRef<Path>>(path: P) -> io::Result<Graph> {
+    let edges: Vec<Edge> = read_parsed_line_input(path)?;
+    Graph::construct(&edges).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+pub fn part1(graph: &Graph) -> usize {
+    graph.count_paths(PART1_VISIT_BUDGET, Algorithm::default())
+}
+
+pub fn part2(graph: &Graph) -> usize {
+    graph.count_paths(PART2_VISIT_BUDGET, Algorithm::default())
+}
+
+/// Counts the paths through the cave system using the chosen algorithm.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Day12 {
+    pub algorithm: Algorithm,
+}
+
+impl Solver for Day12 {
+    const DAY: u8 = 12;
+
+    type Input = Graph;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(&self, input_file: &Path) -> io::Result<Self::Input> {
+        read_graph(input_file)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Self::Output1 {
+        input.count_paths(PART1_VISIT_BUDGET, self.algorithm)
+    }
+
+    fn part2(&self, input: &Self::Input) -> Self::Output2 {
+        input.count_paths(PART2_VISIT_BUDGET, self.algorithm)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn part1_sample_input1() {
+        let input = vec![
+            "start-A".parse().unwrap(),
+            "start-b".parse().unwrap(),
+            "A-c".parse().unwrap(),
+            "A-b".parse().unwrap(),
+            "b-d".parse().unwrap(),
+            "A-end".parse().unwrap(),
+            "b-end".parse().unwrap(),
+        ];
+
+        let expected = 10;
+
+        assert_eq!(expected, part1(&Graph::construct(&input).unwrap()))
+    }
+
+    #[test]
+    fn part1_sample_input2() {
+        let input = vec![
+            "dc-end".parse().unwrap(),
+            "HN-start".parse().unwrap(),
+            "start-kj".parse().unwrap(),
+            "dc-start".parse().unwrap(),
+            "dc-HN".parse().unwrap(),
+            "LN-dc".parse().unwrap(),
+            "HN-end".parse().unwrap(),
+            "kj-sa".parse().unwrap(),
+            "kj-HN".parse().unwrap(),
+            "kj-dc".parse().unwrap(),
+        ];
+
+        let expected = 19;
+
+        assert_eq!(expected, part1(&Graph::construct(&input).unwrap()))
+    }
+
+    #[test]
+    fn part1_sample_input3() {
+        let input = vec![
+            "fs-end".parse().unwrap(),
+            "he-DX".parse().unwrap(),
+            "fs-he".parse().unwrap(),
+            "start-DX".parse().unwrap(),
+            "pj-DX".parse().unwrap(),
+            "end-zg".parse().unwrap(),
+            "zg-sl".parse().unwrap(),
+            "zg-pj".parse().unwrap(),
+            "pj-he".parse().unwrap(),
+            "RW-he".parse().unwrap(),
+            "fs-DX".parse().unwrap(),
+            "pj-RW".parse().unwrap(),
+            "zg-RW".parse().unwrap(),
+            "start-pj".parse().unwrap(),
+            "he-WI".parse().unwrap(),
+            "zg-he".parse().unwrap(),
+            "pj-fs".parse().unwrap(),
+            "start-RW".parse().unwrap(),
+        ];
+
+        let expected = 226;
+
+        assert_eq!(expected, part1(&Graph::construct(&input).unwrap()))
+    }
+
+    #[test]
+    fn part2_sample_input1() {
+        let input = vec![
+            "start-A".parse().unwrap(),
+            "start-b".parse().unwrap(),
+            "A-c".parse().unwrap(),
+            "A-b".parse().unwrap(),
+            "b-d".parse().unwrap(),
+            "A-end".parse().unwrap(),
+            "b-end".parse().unwrap(),
+        ];
+
+        let expected = 36;
+
+        assert_eq!(expected, part2(&Graph::construct(&input).unwrap()))
+    }
+
+    #[test]
+    fn part2_sample_input2() {
+        let input = vec![
+            "dc-end".parse().unwrap(),
+            "HN-start".parse().unwrap(),
+            "start-kj".parse().unwrap(),
+            "dc-start".parse().unwrap(),
+            "dc-HN".parse().unwrap(),
+            "LN-dc".parse().unwrap(),
+            "HN-end".parse().unwrap(),
+            "kj-sa".parse().unwrap(),
+            "kj-HN".parse().unwrap(),
+            "kj-dc".parse().unwrap(),
+        ];
+
+        let expected = 103;
+
+        assert_eq!(expected, part2(&Graph::construct(&input).unwrap()))
+    }
+
+    #[test]
+    fn part2_sample_input3() {
+        let input = vec![
+            "fs-end".parse().unwrap(),
+            "he-DX".parse().unwrap(),
+            "fs-he".parse().unwrap(),
+            "start-DX".parse().unwrap(),
+            "pj-DX".parse().unwrap(),
+            "end-zg".parse().unwrap(),
+            "zg-sl".parse().unwrap(),
+            "zg-pj".parse().unwrap(),
+            "pj-he".parse().unwrap(),
+            "RW-he".parse().unwrap(),
+            "fs-DX".parse().unwrap(),
+            "pj-RW".parse().unwrap(),
+            "zg-RW".parse().unwrap(),
+            "start-pj".parse().unwrap(),
+            "he-WI".parse().unwrap(),
+            "zg-he".parse().unwrap(),
+            "pj-fs".parse().unwrap(),
+            "start-RW".parse().unwrap(),
+        ];
+
+        let expected = 3509;
+
+        assert_eq!(expected, part2(&Graph::construct(&input).unwrap()))
+    }
+
+    #[test]
+    fn larger_visit_budget_sample_input1() {
+        let input = vec![
+            "start-A".parse().unwrap(),
+            "start-b".parse().unwrap(),
+            "A-c".parse().unwrap(),
+            "A-b".parse().unwrap(),
+            "b-d".parse().unwrap(),
+            "A-end".parse().unwrap(),
+            "b-end".parse().unwrap(),
+        ];
+
+        assert_eq!(
+            83,
+            count_paths_using(&input, 3, Algorithm::Sequential).unwrap()
+        );
+        assert_eq!(
+            159,
+            count_paths_using(&input, 4, Algorithm::Sequential).unwrap()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "parallel")]
+    fn parallel_counting_sample_input3() {
+        let input = vec![
+            "fs-end".parse().unwrap(),
+            "he-DX".parse().unwrap(),
+            "fs-he".parse().unwrap(),
+            "start-DX".parse().unwrap(),
+            "pj-DX".parse().unwrap(),
+            "end-zg".parse().unwrap(),
+            "zg-sl".parse().unwrap(),
+            "zg-pj".parse().unwrap(),
+            "pj-he".parse().unwrap(),
+            "RW-he".parse().unwrap(),
+            "fs-DX".parse().unwrap(),
+            "pj-RW".parse().unwrap(),
+            "zg-RW".parse().unwrap(),
+            "start-pj".parse().unwrap(),
+            "he-WI".parse().unwrap(),
+            "zg-he".parse().unwrap(),
+            "pj-fs".parse().unwrap(),
+            "start-RW".parse().unwrap(),
+        ];
+
+        for budget in 1..=3 {
+            assert_eq!(
+                count_paths_using(&input, budget, Algorithm::Sequential).unwrap(),
+                count_paths_using(&input, budget, Algorithm::Parallel).unwrap()
+            );
+        }
+        assert_eq!(
+            3509,
+            count_paths_using(&input, PART2_VISIT_BUDGET, Algorithm::Parallel).unwrap()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "parallel")]
+    fn parallel_counting_generated_caves() {
+        for seed in 0..5 {
+            let input = generators::day12::cave_system(seed, 8)
+                .lines()
+                .map(|edge| edge.parse().unwrap())
+                .collect::<Vec<_>>();
+
+            for budget in 1..=2 {
+                assert_eq!(
+                    count_paths_using(&input, budget, Algorithm::Sequential).unwrap(),
+                    count_paths_using(&input, budget, Algorithm::Parallel).unwrap()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn enumerating_paths_sample_input1() {
+        let input = vec![
+            "start-A".parse().unwrap(),
+            "start-b".parse().unwrap(),
+            "A-c".parse().unwrap(),
+            "A-b".parse().unwrap(),
+            "b-d".parse().unwrap(),
+            "A-end".parse().unwrap(),
+            "b-end".parse().unwrap(),
+        ];
+        let graph = Graph::construct(&input).unwrap();
+
+        let mut paths = enumerate_paths(&graph, PART1_VISIT_BUDGET)
+            .map(|path| {
+                path.into_iter()
+                    .map(|id| graph.node(id).name())
+                    .collect::<Vec<_>>()
+                    .join(",")
+            })
+            .collect::<Vec<_>>();
+        paths.sort();
+
+        let expected = vec![
+            "start,A,b,A,c,A,end",
+            "start,A,b,A,end",
+            "start,A,b,end",
+            "start,A,c,A,b,A,end",
+            "start,A,c,A,b,end",
+            "start,A,c,A,end",
+            "start,A,end",
+            "start,b,A,c,A,end",
+            "start,b,A,end",
+            "start,b,end",
+        ];
+        assert_eq!(expected, paths);
+
+        for budget in 1..=4 {
+            assert_eq!(
+                count_paths_using(&input, budget, Algorithm::Sequential).unwrap(),
+                enumerate_paths(&graph, budget).count()
+            );
+        }
+
+        assert_eq!(10, graph.all_paths(false).count());
+        let double_visits = graph
+            .all_paths(true)
+            .filter(|path| {
+                let small = path.iter().filter(|node| !node.is_big());
+                small.clone().count() > small.collect::<HashSet<_>>().len()
+            })
+            .count();
+        assert_eq!(36 - 10, double_visits);
+    }
+
+    #[test]
+    fn reading_invalid_graph() {
+        let path = std::env::temp_dir().join(format!("day12-invalid-graph-{}", std::process::id()));
+        std::fs::write(&path, "start-A\nA-B\nB-end").unwrap();
+        let err = read_graph(&path).unwrap_err();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(io::ErrorKind::InvalidData, err.kind());
+
+        let path = std::env::temp_dir().join(format!("day12-valid-graph-{}", std::process::id()));
+        std::fs::write(&path, "start-A\nA-end").unwrap();
+        let graph = read_graph(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(1, part1(&graph));
+    }
+
+    #[test]
+    fn graph_validation() {
+        let construct = |raw: &[&str]| {
+            let edges = raw
+                .iter()
+                .map(|edge| edge.parse().unwrap())
+                .collect::<Vec<Edge>>();
+            Graph::construct(&edges).map(|_| ())
+        };
+
+        assert!(construct(&["start-A", "A-end"]).is_ok());
+        assert_eq!(
+            Err(GraphError::InconsistentTerminalNode("START".to_owned())),
+            construct(&["START-A", "A-end"])
+        );
+        assert_eq!(
+            Err(GraphError::SelfLoop(Node::new("b"))),
+            construct(&["start-b", "b-b", "b-end"])
+        );
+        assert_eq!(
+            Err(GraphError::DuplicateEdge(
+                Node::new("b"),
+                Node::new("start")
+            )),
+            construct(&["start-b", "b-start", "b-end"])
+        );
+        assert_eq!(
+            Err(GraphError::AdjacentBigCaves(Node::new("A"), Node::new("B"))),
+            construct(&["start-A", "A-B", "B-end"])
+        );
+        assert_eq!(Err(GraphError::MissingStart), construct(&["a-end"]));
+        assert_eq!(Err(GraphError::MissingEnd), construct(&["start-a"]));
+
+        assert!("start-a-b".parse::<Edge>().is_err());
+        assert!("start-".parse::<Edge>().is_err());
+    }
+}
@@ -0,0 +1,37 @@
+// Copyright 2022 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use day13::Manual;
+
+/// Number of dots on the generated paper, which is folded 12 times.
+const DOTS: usize = 100_000;
+
+fn folding_algorithms(c: &mut Criterion) {
+    let manual: Manual = generators::day13::transparent_paper(0, DOTS)
+        .parse()
+        .expect("the generated manual is valid");
+
+    let mut group = c.benchmark_group("folding");
+    group.bench_function("rebuild", |b| {
+        b.iter(|| black_box(manual.clone()).fold_all().unwrap())
+    });
+    group.bench_function("transform", |b| {
+        b.iter(|| black_box(manual.clone()).fold_all_transformed().unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, folding_algorithms);
+criterion_main!(benches);
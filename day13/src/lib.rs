@@ -0,0 +1,868 @@
+// Copyright 2021 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::{BTreeSet, VecDeque};
+use std::fmt::{Display, Formatter};
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+use utils::input_read::read_parsed;
+use utils::ocr::recognise_letters;
+use utils::render::{render_points, write_image, FrameSink, Glyphs};
+use utils::{Solver, Visualize};
+
+/// Fold instruction, as found in the input, that isn't in the form of `fold along x=5`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MalformedFold(pub String);
+
+impl Display for MalformedFold {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "'{}' is not a valid fold instruction", self.0)
+    }
+}
+
+impl std::error::Error for MalformedFold {}
+
+/// Dot, as found in the input, that isn't in the form of `x,y`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MalformedPoint(pub String);
+
+impl Display for MalformedPoint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "'{}' is not a valid x,y point", self.0)
+    }
+}
+
+impl std::error::Error for MalformedPoint {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MalformedManual {
+    /// The dots have to be separated from the fold instructions by an empty line.
+    MissingFolds,
+    MalformedPoint {
+        line: usize,
+        source: MalformedPoint,
+    },
+    MalformedFold {
+        line: usize,
+        source: MalformedFold,
+    },
+}
+
+impl Display for MalformedManual {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MalformedManual::MissingFolds => {
+                write!(f, "the dots are not followed by the fold instructions")
+            }
+            MalformedManual::MalformedPoint { line, source } => write!(f, "line {line}: {source}"),
+            MalformedManual::MalformedFold { line, source } => write!(f, "line {line}: {source}"),
+        }
+    }
+}
+
+impl std::error::Error for MalformedManual {}
+
+#[derive(Debug, Hash, Eq, PartialEq, Clone, Copy, Ord, PartialOrd)]
+pub struct Point {
+    pub x: usize,
+    pub y: usize,
+}
+
+impl Display for Point {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+impl FromStr for Point {
+    type Err = MalformedPoint;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let malformed = || MalformedPoint(s.to_string());
+        let (x, y) = s.split_once(',').ok_or_else(malformed)?;
+        Ok(Point {
+            x: x.parse().map_err(|_| malformed())?,
+            y: y.parse().map_err(|_| malformed())?,
+        })
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum Axis {
+    X,
+    Y,
+}
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub struct Fold {
+    pub axis: Axis,
+    pub at: usize,
+}
+
+impl Display for Fold {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.axis {
+            Axis::X => write!(f, "fold along x={}", self.at),
+            Axis::Y => write!(f, "fold along y={}", self.at),
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum FoldError {
+    /// There is a dot placed directly on the fold line.
+    PointOnFoldLine { fold: Fold, point: Point },
+    /// The fold line lies outside the current paper.
+    OutsideExtents { fold: Fold, extent: usize },
+    /// The part of the paper that is being folded is longer than the part it's being folded onto,
+    /// meaning some of the dots would end up outside the paper.
+    UnevenFold { fold: Fold, extent: usize },
+}
+
+impl Display for FoldError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FoldError::PointOnFoldLine { fold, point } => {
+                write!(
+                    f,
+                    "'{fold}' is invalid as point {point} lies on the fold line"
+                )
+            }
+            FoldError::OutsideExtents { fold, extent } => write!(
+                f,
+                "'{fold}' is invalid as it lies outside the paper of length {extent}"
+            ),
+            FoldError::UnevenFold { fold, extent } => write!(
+                f,
+                "'{fold}' is invalid as it does not fold the paper of length {extent} in half"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for FoldError {}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Algorithm {
+    /// Rebuilds the entire set of points after every fold.
+    Rebuild,
+    /// Composes all the folds into a single coordinate transform applied once to every point.
+    #[default]
+    Transform,
+}
+
+impl FromStr for Algorithm {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "rebuild" => Ok(Algorithm::Rebuild),
+            "transform" => Ok(Algorithm::Transform),
+            other => Err(format!("{other} is not a valid algorithm")),
+        }
+    }
+}
+
+impl FromStr for Fold {
+    type Err = MalformedFold;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let malformed = || MalformedFold(s.to_string());
+        let stripped = s.strip_prefix("fold along ").ok_or_else(malformed)?;
+        let (axis, at) = stripped.split_once('=').ok_or_else(malformed)?;
+        let axis = match axis {
+            "x" => Axis::X,
+            "y" => Axis::Y,
+            _ => return Err(malformed()),
+        };
+        let at = at.parse().map_err(|_| malformed())?;
+
+        Ok(Fold { axis, at })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Manual {
+    points: BTreeSet<Point>,
+    folds: VecDeque<Fold>,
+    width: usize,
+    height: usize,
+}
+
+impl FromStr for Manual {
+    type Err = MalformedManual;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.replace("\r\n", "\n"); // Windows fix
+        let (points, folds) = s.split_once("\n\n").ok_or(MalformedManual::MissingFolds)?;
+
+        // line numbers are 1-based to match what any text editor would display
+        let points = points
+            .lines()
+            .enumerate()
+            .map(|(i, raw)| {
+                raw.parse()
+                    .map_err(|source| MalformedManual::MalformedPoint {
+                        line: i + 1,
+                        source,
+                    })
+            })
+            .collect::<Result<_, _>>()?;
+        let folds_offset = s[..s.len() - folds.len()].lines().count();
+        let folds = folds
+            .lines()
+            .enumerate()
+            .map(|(i, raw)| {
+                raw.parse()
+                    .map_err(|source| MalformedManual::MalformedFold {
+                        line: folds_offset + i + 1,
+                        source,
+                    })
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Manual::new(points, folds))
+    }
+}
+
+impl Manual {
+    pub fn new(points: BTreeSet<Point>, folds: VecDeque<Fold>) -> Self {
+        // the paper has to be at least big enough to fit all the dots
+        let width = points
+            .iter()
+            .map(|point| point.x + 1)
+            .max()
+            .unwrap_or_default();
+        let height = points
+            .iter()
+            .map(|point| point.y + 1)
+            .max()
+            .unwrap_or_default();
+
+        Manual {
+            points,
+            folds,
+            width,
+            height,
+        }
+    }
+
+    #[cfg(test)]
+    fn from_raw(raw: &[String]) -> Manual {
+        let points = raw[0].lines().map(|s| s.parse().unwrap()).collect();
+        let folds = raw[1].lines().map(|s| s.parse().unwrap()).collect();
+
+        Manual::new(points, folds)
+    }
+
+    pub fn points(&self) -> &BTreeSet<Point> {
+        &self.points
+    }
+
+    fn validate_fold(&self, fold: Fold) -> Result<(), FoldError> {
+        let (extent, coordinate): (_, fn(&Point) -> usize) = match fold.axis {
+            Axis::X => (self.width, |point| point.x),
+            Axis::Y => (self.height, |point| point.y),
+        };
+
+        if fold.at >= extent {
+            return Err(FoldError::OutsideExtents { fold, extent });
+        }
+        if extent - 1 - fold.at > fold.at {
+            return Err(FoldError::UnevenFold { fold, extent });
+        }
+        if let Some(point) = self.points.iter().find(|p| coordinate(p) == fold.at) {
+            return Err(FoldError::PointOnFoldLine {
+                fold,
+                point: *point,
+            });
+        }
+
+        Ok(())
+    }
+
+    fn fold_at_y_axis(&mut self, at: usize) {
+        let mut new_points: BTreeSet<Point> = self
+            .points
+            .iter()
+            .filter(|point| point.y < at)
+            .copied()
+            .collect();
+        for point in &self.points {
+            if point.y > at {
+                new_points.insert(Point {
+                    x: point.x,
+                    y: 2 * at - point.y,
+                });
+            }
+        }
+
+        self.points = new_points;
+        self.height = at;
+    }
+
+    fn fold_at_x_axis(&mut self, at: usize) {
+        let mut new_points: BTreeSet<Point> = self
+            .points
+            .iter()
+            .filter(|point| point.x < at)
+            .copied()
+            .collect();
+        for point in &self.points {
+            if point.x > at {
+                new_points.insert(Point {
+                    x: 2 * at - point.x,
+                    y: point.y,
+                });
+            }
+        }
+
+        self.points = new_points;
+        self.width = at;
+    }
+
+    /// Performs the next fold, if there's any remaining, returning the number of visible dots afterwards.
+    pub fn fold(&mut self) -> Result<Option<usize>, FoldError> {
+        if let Some(fold) = self.folds.front().copied() {
+            self.validate_fold(fold)?;
+            self.folds.pop_front();
+            if fold.axis == Axis::Y {
+                self.fold_at_y_axis(fold.at)
+            } else {
+                self.fold_at_x_axis(fold.at)
+            }
+            Ok(Some(self.points.len()))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Performs all the remaining folds returning the number of visible dots after each of them.
+    pub fn fold_all(&mut self) -> Result<Vec<usize>, FoldError> {
+        let mut counts = Vec::with_capacity(self.folds.len());
+        while let Some(count) = self.fold()? {
+            counts.push(count)
+        }
+        Ok(counts)
+    }
+
+    /// Checks whether all the remaining folds are valid, without performing any of them.
+    pub fn validate(&self) -> Result<(), FoldError> {
+        self.transformed().map(|_| ())
+    }
+
+    /// Performs all the remaining folds at once by treating them as a chain of reflections
+    /// applied to each original point, so that the final set of points is built in a single pass.
+    /// The manual is left untouched if any of the folds is invalid.
+    pub fn fold_all_transformed(&mut self) -> Result<(), FoldError> {
+        let (points, width, height) = self.transformed()?;
+        self.points = points;
+        self.width = width;
+        self.height = height;
+        self.folds.clear();
+        Ok(())
+    }
+
+    // points and extents of the paper after all the remaining folds
+    fn transformed(&self) -> Result<(BTreeSet<Point>, usize, usize), FoldError> {
+        // the paper extents do not depend on the points so they can be validated upfront
+        let (mut width, mut height) = (self.width, self.height);
+        for fold in &self.folds {
+            let extent = match fold.axis {
+                Axis::X => &mut width,
+                Axis::Y => &mut height,
+            };
+            if fold.at >= *extent {
+                return Err(FoldError::OutsideExtents {
+                    fold: *fold,
+                    extent: *extent,
+                });
+            }
+            if *extent - 1 - fold.at > fold.at {
+                return Err(FoldError::UnevenFold {
+                    fold: *fold,
+                    extent: *extent,
+                });
+            }
+            *extent = fold.at;
+        }
+
+        let transform = |mut point: Point| {
+            for fold in &self.folds {
+                let coordinate = match fold.axis {
+                    Axis::X => &mut point.x,
+                    Axis::Y => &mut point.y,
+                };
+                if *coordinate == fold.at {
+                    return Err(FoldError::PointOnFoldLine { fold: *fold, point });
+                }
+                if *coordinate > fold.at {
+                    *coordinate = 2 * fold.at - *coordinate
+                }
+            }
+            Ok(point)
+        };
+
+        let points = self
+            .points
+            .iter()
+            .map(|point| transform(*point))
+            .collect::<Result<_, _>>()?;
+        Ok((points, width, height))
+    }
+
+    pub fn final_manual(&self) -> String {
+        let points = self
+            .points
+            .iter()
+            .map(|point| (point.x as i64, point.y as i64))
+            .collect();
+        format!("\n{}", render_points(&points, Glyphs::BLOCKS))
+    }
+
+    fn lit_pixels(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.points.iter().map(|point| (point.x, point.y))
+    }
+
+    fn read_code(&self) -> Option<String> {
+        recognise_letters(self.lit_pixels())
+    }
+
+    /// Writes the final manual into the provided path and, if requested,
+    /// the result of every intermediate fold as `<name>-fold-<n>.<ext>` alongside it.
+    pub fn export_folds(mut self, path: &Path, intermediate: bool) -> io::Result<()> {
+        let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+        let extension = path.extension().unwrap_or_default().to_string_lossy();
+
+        let mut fold = 0;
+        while self
+            .fold()
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?
+            .is_some()
+        {
+            fold += 1;
+            if intermediate && !self.folds.is_empty() {
+                let fold_path = path.with_file_name(format!("{stem}-fold-{fold}.{extension}"));
+                write_image(fold_path, self.lit_pixels())?;
+            }
+        }
+        write_image(path, self.lit_pixels())
+    }
+}
+
+/// Reads the manual, making sure all of its folds are valid.
+pub fn read_manual<P: AsRef<Path>>(path: P) -> io::Result<Manual> {
+    let manual: Manual = read_parsed(path)?;
+    manual
+        .validate()
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+    Ok(manual)
+}
+
+/// # Panics
+/// Panics if the first fold is invalid, which [`read_manual`] rules out.
+pub fn part1(mut manual: Manual) -> usize {
+    manual
+        .fold()
+        .expect("the folds have been validated")
+        .unwrap_or(manual.points.len())
+}
+
+pub fn part2(manual: Manual) -> String {
+    part2_using(manual, Algorithm::Transform)
+}
+
+/// # Panics
+/// Panics if any of the folds is invalid, which [`read_manual`] rules out.
+pub fn part2_using(mut manual: Manual, algorithm: Algorithm) -> String {
+    match algorithm {
+        Algorithm::Rebuild => manual.fold_all().map(|_| ()),
+        Algorithm::Transform => manual.fold_all_transformed(),
+    }
+    .expect("the folds have been validated");
+
+    // if we failed to read the letters, let the human have a look at the result
+    manual.read_code().unwrap_or_else(|| manual.final_manual())
+}
+
+/// Folds the transparent paper, reading the code using the chosen algorithm.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Day13 {
+    pub algorithm: Algorithm,
+}
+
+impl Solver for Day13 {
+    const DAY: u8 = 13;
+
+    type Input = Manual;
+    type Output1 = usize;
+    type Output2 = String;
+
+    fn parse(&self, input_file: &Path) -> io::Result<Self::Input> {
+        read_manual(input_file)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Self::Output1 {
+        part1(input.clone())
+    }
+
+    fn part2(&self, input: &Self::Input) -> Self::Output2 {
+        part2_using(input.clone(), self.algorithm)
+    }
+}
+
+impl Visualize for Day13 {
+    // the original dots followed by the paper after every fold, all drawn on the unfolded sheet
+    fn visualize(&self, input: &Self::Input, sink: &mut dyn FrameSink) -> io::Result<()> {
+        let mut manual = input.clone();
+        let size = (manual.width, manual.height);
+        sink.push_frame(size, &manual.lit_pixels().collect::<Vec<_>>())?;
+        while manual
+            .fold()
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?
+            .is_some()
+        {
+            sink.push_frame(size, &manual.lit_pixels().collect::<Vec<_>>())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utils::render::FrameBuffer;
+
+    #[test]
+    fn part1_sample_input() {
+        let input = vec![
+            "6,10
+0,14
+9,10
+0,3
+10,4
+4,11
+6,0
+6,12
+4,1
+0,13
+10,12
+3,4
+3,0
+8,4
+1,10
+2,14
+8,10
+9,0"
+            .to_string(),
+            "fold along y=7
+fold along x=5"
+                .to_string(),
+        ];
+
+        let manual = Manual::from_raw(&input);
+        let expected = 17;
+
+        assert_eq!(expected, part1(manual))
+    }
+
+    #[test]
+    fn part2_sample_input() {
+        let input = vec![
+            "6,10
+0,14
+9,10
+0,3
+10,4
+4,11
+6,0
+6,12
+4,1
+0,13
+10,12
+3,4
+3,0
+8,4
+1,10
+2,14
+8,10
+9,0"
+            .to_string(),
+            "fold along y=7
+fold along x=5"
+                .to_string(),
+        ];
+
+        let manual = Manual::from_raw(&input);
+        let expected = r#"
+█████
+█⠀⠀⠀█
+█⠀⠀⠀█
+█⠀⠀⠀█
+█████"#;
+
+        assert_eq!(expected, part2(manual))
+    }
+
+    #[test]
+    fn part2_reading_code() {
+        let input = vec![
+            "0,0
+1,0
+2,0
+0,1
+0,2
+1,2
+2,2
+0,3
+0,4
+0,5
+5,0
+5,1
+5,2
+5,3
+5,4
+5,5
+6,5
+7,5
+8,5
+0,12
+3,12"
+                .to_string(),
+            "fold along y=6".to_string(),
+        ];
+
+        let manual = Manual::from_raw(&input);
+        assert_eq!("FL", part2(manual))
+    }
+
+    #[test]
+    fn per_fold_point_counts() {
+        let input = vec![
+            "6,10
+0,14
+9,10
+0,3
+10,4
+4,11
+6,0
+6,12
+4,1
+0,13
+10,12
+3,4
+3,0
+8,4
+1,10
+2,14
+8,10
+9,0"
+            .to_string(),
+            "fold along y=7
+fold along x=5"
+                .to_string(),
+        ];
+
+        let mut manual = Manual::from_raw(&input);
+        assert_eq!(vec![17, 16], manual.fold_all().unwrap());
+        assert_eq!(None, manual.fold().unwrap());
+    }
+
+    #[test]
+    fn transformed_folding_matches_rebuilding() {
+        let raw = [
+            "6,10
+0,14
+9,10
+0,3
+10,4
+4,11
+6,0
+6,12
+4,1
+0,13
+10,12
+3,4
+3,0
+8,4
+1,10
+2,14
+8,10
+9,0"
+            .to_string(),
+            "fold along y=7
+fold along x=5"
+                .to_string(),
+        ];
+
+        let mut rebuilt = Manual::from_raw(&raw);
+        let mut transformed = Manual::from_raw(&raw);
+        rebuilt.fold_all().unwrap();
+        transformed.fold_all_transformed().unwrap();
+
+        assert_eq!(rebuilt.points(), transformed.points());
+        assert!(!transformed.points().is_empty());
+    }
+
+    #[test]
+    fn fold_validation() {
+        let fold = |points: &str, fold: &str| {
+            let raw = [points.to_string(), fold.to_string()];
+            let mut transformed = Manual::from_raw(&raw);
+            let transformed_result = transformed.fold_all_transformed();
+            if transformed_result.is_err() {
+                // the failed fold must leave the manual untouched
+                let original = Manual::from_raw(&raw);
+                assert_eq!(original.points(), transformed.points());
+                assert_eq!(
+                    (original.width, original.height),
+                    (transformed.width, transformed.height)
+                );
+            }
+
+            let mut manual = Manual::from_raw(&raw);
+            let result = manual.fold_all();
+            assert_eq!(result.as_ref().err(), transformed_result.err().as_ref());
+            assert_eq!(
+                result.as_ref().err(),
+                Manual::from_raw(&raw).validate().err().as_ref()
+            );
+            result
+        };
+
+        assert_eq!(Ok(vec![2]), fold("0,0\n4,2", "fold along x=2"));
+        // it's fine to fold a shorter part onto a longer one
+        assert_eq!(Ok(vec![2]), fold("0,0\n4,2", "fold along x=3"));
+        assert_eq!(
+            Err(FoldError::PointOnFoldLine {
+                fold: Fold {
+                    axis: Axis::Y,
+                    at: 1
+                },
+                point: Point { x: 3, y: 1 }
+            }),
+            fold("0,0\n3,1\n4,2", "fold along y=1")
+        );
+        assert_eq!(
+            Err(FoldError::OutsideExtents {
+                fold: Fold {
+                    axis: Axis::X,
+                    at: 5
+                },
+                extent: 5
+            }),
+            fold("0,0\n4,2", "fold along x=5")
+        );
+        assert_eq!(
+            Err(FoldError::UnevenFold {
+                fold: Fold {
+                    axis: Axis::X,
+                    at: 1
+                },
+                extent: 5
+            }),
+            fold("0,0\n4,2", "fold along x=1")
+        );
+        assert_eq!(
+            Err(FoldError::OutsideExtents {
+                fold: Fold {
+                    axis: Axis::X,
+                    at: 3
+                },
+                extent: 2
+            }),
+            fold("0,0\n4,2", "fold along x=2\nfold along x=3")
+        );
+    }
+
+    #[test]
+    fn visualizing_folds() {
+        let manual: Manual = "6,10
+0,14
+9,10
+0,3
+10,4
+4,11
+6,0
+6,12
+4,1
+0,13
+10,12
+3,4
+3,0
+8,4
+1,10
+2,14
+8,10
+9,0
+
+fold along y=7
+fold along x=5"
+            .parse()
+            .unwrap();
+
+        let mut frames = FrameBuffer::default();
+        Day13::default().visualize(&manual, &mut frames).unwrap();
+        assert!(frames.frames.iter().all(|(size, _)| *size == (11, 15)));
+        assert_eq!(
+            vec![18, 17, 16],
+            frames
+                .frames
+                .iter()
+                .map(|(_, lit)| lit.len())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn reading_invalid_manual() {
+        let path =
+            std::env::temp_dir().join(format!("day13-invalid-manual-{}", std::process::id()));
+        std::fs::write(&path, "0,0\n4,2\n\nfold along x=1").unwrap();
+        let err = read_manual(&path).unwrap_err();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(io::ErrorKind::InvalidData, err.kind());
+
+        let path = std::env::temp_dir().join(format!("day13-valid-manual-{}", std::process::id()));
+        std::fs::write(&path, "0,0\n4,2\n\nfold along x=2").unwrap();
+        let manual = read_manual(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(2, part1(manual));
+    }
+
+    #[test]
+    fn malformed_manual() {
+        assert_eq!(
+            Err(MalformedManual::MissingFolds),
+            "6,10\n0,14".parse::<Manual>().map(|_| ())
+        );
+        assert_eq!(
+            Err(MalformedManual::MalformedPoint {
+                line: 2,
+                source: MalformedPoint("0;14".to_string())
+            }),
+            "6,10\n0;14\n\nfold along y=7".parse::<Manual>().map(|_| ())
+        );
+        assert_eq!(
+            Err(MalformedManual::MalformedFold {
+                line: 5,
+                source: MalformedFold("fold along z=5".to_string())
+            }),
+            "6,10\n0,14\n\nfold along y=7\nfold along z=5"
+                .parse::<Manual>()
+                .map(|_| ())
+        );
+    }
+}
@@ -0,0 +1,616 @@
+// Copyright 2021 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use itertools::Itertools;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt::{Display, Formatter};
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+use utils::input_read::read_parsed;
+use utils::Solver;
+
+/// Number of steps performed in the first part of the puzzle.
+pub const PART1_STEPS: usize = 10;
+
+/// Number of steps performed in the second part of the puzzle.
+pub const PART2_STEPS: usize = 40;
+
+type Pair = (char, char);
+
+/// Pair insertion rule, as found in the input, that isn't in the form of `AB -> C`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MalformedRule(pub String);
+
+impl Display for MalformedRule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "'{}' is not a valid pair insertion rule", self.0)
+    }
+}
+
+impl std::error::Error for MalformedRule {}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum MalformedManual {
+    EmptyTemplate,
+    MissingRules,
+    MalformedRule(MalformedRule),
+    /// Pairs that can arise during the polymerization, but do not have a corresponding insertion rule.
+    UnmatchedPairs(Vec<Pair>),
+}
+
+impl From<MalformedRule> for MalformedManual {
+    fn from(err: MalformedRule) -> Self {
+        MalformedManual::MalformedRule(err)
+    }
+}
+
+impl Display for MalformedManual {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MalformedManual::EmptyTemplate => write!(f, "the polymer template is empty"),
+            MalformedManual::MissingRules => write!(f, "the pair insertion rules are missing"),
+            MalformedManual::MalformedRule(err) => write!(f, "{err}"),
+            MalformedManual::UnmatchedPairs(pairs) => write!(
+                f,
+                "there are no insertion rules for the following pairs: {}",
+                pairs.iter().map(|(a, b)| format!("{a}{b}")).join(", ")
+            ),
+        }
+    }
+}
+
+impl std::error::Error for MalformedManual {}
+
+/// The element counts no longer fit in 128 bits.
+#[derive(Debug, PartialEq, Eq)]
+pub struct CountOverflow;
+
+#[derive(Debug, Clone)]
+struct Rule {
+    pair: Pair,
+    insertion: char,
+}
+
+impl FromStr for Rule {
+    type Err = MalformedRule;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let malformed = || MalformedRule(s.to_string());
+        let (pair, insertion) = s.split_once(" -> ").ok_or_else(malformed)?;
+        let pair = pair.chars().collect_tuple().ok_or_else(malformed)?;
+        let (insertion,) = insertion.chars().collect_tuple().ok_or_else(malformed)?;
+
+        Ok(Rule { pair, insertion })
+    }
+}
+
+impl Rule {
+    fn apply(&self) -> (Pair, Pair) {
+        ((self.pair.0, self.insertion), (self.insertion, self.pair.1))
+    }
+}
+
+/// Default upper bound on the number of steps for which the literal polymer is expanded
+/// when cross-checking the pair counting, as its length doubles with every step.
+pub const DEFAULT_LITERAL_STEPS_BOUND: usize = 15;
+
+/// Element histogram of the literal polymer does not match the one obtained via pair counting.
+#[derive(Debug, PartialEq, Eq)]
+pub struct HistogramMismatch {
+    pub steps: usize,
+    pub literal: HashMap<char, u128>,
+    pub counted: HashMap<char, u128>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Manual {
+    template: String,
+    front: char,
+    pairs: HashMap<Pair, u128>,
+    rules: Vec<Rule>,
+}
+
+impl FromStr for Manual {
+    type Err = MalformedManual;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let manual = Manual::parse_with_unmatched_pairs(s)?;
+
+        let unmatched = manual.unmatched_pairs();
+        if !unmatched.is_empty() {
+            return Err(MalformedManual::UnmatchedPairs(unmatched));
+        }
+        Ok(manual)
+    }
+}
+
+impl Manual {
+    /// Parses the manual without requiring every pair that can arise during the polymerization
+    /// to have a corresponding rule. Such pairs are simply left intact by the insertion steps.
+    pub fn parse_with_unmatched_pairs(s: &str) -> Result<Self, MalformedManual> {
+        let lines = s
+            .replace("\r\n", "\n") // Windows fix
+            .split("\n\n")
+            .map(|split| split.to_owned())
+            .collect::<Vec<_>>();
+
+        let template = lines[0].trim().to_owned();
+        let front = template
+            .chars()
+            .next()
+            .ok_or(MalformedManual::EmptyTemplate)?;
+
+        let mut pairs: HashMap<Pair, u128> = HashMap::new();
+        for pair in template.chars().tuple_windows() {
+            *pairs.entry(pair).or_default() += 1;
+        }
+
+        let mut rules = Vec::new();
+        for rule in lines.get(1).ok_or(MalformedManual::MissingRules)?.lines() {
+            rules.push(rule.parse()?)
+        }
+
+        Ok(Manual {
+            template,
+            front,
+            pairs,
+            rules,
+        })
+    }
+
+    /// Finds all the pairs that can arise during the polymerization,
+    /// either from the template or from the insertions, that do not have a corresponding rule.
+    fn unmatched_pairs(&self) -> Vec<Pair> {
+        let insertions = self.insertions();
+
+        let mut seen = self.pairs.keys().copied().collect::<HashSet<_>>();
+        let mut to_visit = seen.iter().copied().collect::<Vec<_>>();
+        let mut unmatched = BTreeSet::new();
+
+        while let Some(pair) = to_visit.pop() {
+            if let Some(insertion) = insertions.get(&pair) {
+                for new_pair in [(pair.0, *insertion), (*insertion, pair.1)] {
+                    if seen.insert(new_pair) {
+                        to_visit.push(new_pair)
+                    }
+                }
+            } else {
+                unmatched.insert(pair);
+            }
+        }
+
+        unmatched.into_iter().collect()
+    }
+
+    fn insertions(&self) -> HashMap<Pair, char> {
+        self.rules
+            .iter()
+            .map(|rule| (rule.pair, rule.insertion))
+            .collect()
+    }
+
+    /// Expands the literal polymer obtained by applying the specified number of steps
+    /// to the original template. Note that its length doubles with every step.
+    pub fn expand_polymer(&self, steps: usize) -> String {
+        let insertions = self.insertions();
+
+        let mut polymer = self.template.clone();
+        for _ in 0..steps {
+            polymer = expand_once(&polymer, &insertions);
+        }
+        polymer
+    }
+
+    fn step(&mut self) -> Result<(), CountOverflow> {
+        let mut new_pairs = self.pairs.clone();
+        for rule in &self.rules {
+            if let Some(count) = self.pairs.remove(&rule.pair) {
+                let inserted = rule.apply();
+
+                // this can't underflow as the pair has been present with at least `count` occurrences
+                *new_pairs.entry(rule.pair).or_default() -= count;
+                for pair in [inserted.0, inserted.1] {
+                    let entry = new_pairs.entry(pair).or_default();
+                    *entry = entry.checked_add(count).ok_or(CountOverflow)?;
+                }
+            }
+        }
+
+        self.pairs = new_pairs
+            .into_iter()
+            .filter(|(_, count)| *count != 0)
+            .collect();
+        Ok(())
+    }
+
+    pub fn apply_steps(&mut self, count: usize) -> Result<(), CountOverflow> {
+        for _ in 0..count {
+            self.step()?
+        }
+        Ok(())
+    }
+
+    pub fn element_count(&self) -> Result<HashMap<char, u128>, CountOverflow> {
+        let mut count: HashMap<_, u128> = HashMap::new();
+        for (pair, occurrences) in self.pairs.iter() {
+            let entry = count.entry(pair.1).or_default();
+            *entry = entry.checked_add(*occurrences).ok_or(CountOverflow)?;
+        }
+        let front = count.entry(self.front).or_default();
+        *front = front.checked_add(1).ok_or(CountOverflow)?;
+        Ok(count)
+    }
+
+    /// Total number of elements in the polymer.
+    pub fn polymer_length(&self) -> Result<u128, CountOverflow> {
+        self.element_count()?
+            .values()
+            .try_fold(0u128, |acc, count| acc.checked_add(*count))
+            .ok_or(CountOverflow)
+    }
+
+    fn max_frequency_difference(&self) -> Result<u128, CountOverflow> {
+        let count = self.element_count()?;
+
+        Ok(count.values().max().unwrap() - count.values().min().unwrap())
+    }
+}
+
+/// Applies the specified number of insertion steps and returns the difference between
+/// the quantities of the most and the least common elements in the resultant polymer.
+pub fn polymerize(mut manual: Manual, steps: usize) -> Result<u128, CountOverflow> {
+    manual.apply_steps(steps)?;
+    manual.max_frequency_difference()
+}
+
+/// Determines after how many steps the difference between the quantities of the most
+/// and the least common elements exceeds the provided threshold.
+/// Returns `None` if it doesn't happen within `max_steps`.
+pub fn steps_until_difference_exceeds(
+    mut manual: Manual,
+    threshold: u128,
+    max_steps: usize,
+) -> Result<Option<usize>, CountOverflow> {
+    for step in 0..=max_steps {
+        if manual.max_frequency_difference()? > threshold {
+            return Ok(Some(step));
+        }
+        manual.step()?
+    }
+    Ok(None)
+}
+
+fn expand_once(polymer: &str, insertions: &HashMap<Pair, char>) -> String {
+    let mut expanded = String::with_capacity(polymer.len() * 2);
+    expanded.extend(polymer.chars().next());
+    for pair in polymer.chars().tuple_windows() {
+        if let Some(insertion) = insertions.get(&pair) {
+            expanded.push(*insertion)
+        }
+        expanded.push(pair.1)
+    }
+    expanded
+}
+
+/// Compares the element histograms of the literal polymer and the pair counting approach
+/// after every step up to `max_steps`.
+pub fn cross_check(mut manual: Manual, max_steps: usize) -> Result<(), HistogramMismatch> {
+    let insertions = manual.insertions();
+    let mut polymer = manual.template.clone();
+    for steps in 0..=max_steps {
+        let mut literal: HashMap<_, u128> = HashMap::new();
+        for element in polymer.chars() {
+            *literal.entry(element).or_default() += 1;
+        }
+        let counted = manual
+            .element_count()
+            .expect("the counts can't overflow for literally expandable polymers");
+
+        if literal != counted {
+            return Err(HistogramMismatch {
+                steps,
+                literal,
+                counted,
+            });
+        }
+
+        if steps < max_steps {
+            manual
+                .step()
+                .expect("the counts can't overflow for literally expandable polymers");
+            polymer = expand_once(&polymer, &insertions);
+        }
+    }
+    Ok(())
+}
+
+pub fn part1(manual: Manual) -> u128 {
+    polymerize(manual, PART1_STEPS).expect("the element counts have overflowed")
+}
+
+pub fn part2(manual: Manual) -> u128 {
+    polymerize(manual, PART2_STEPS).expect("the element counts have overflowed")
+}
+
+/// Counts the elements of the polymer after the steps of both parts.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Day14;
+
+impl Solver for Day14 {
+    const DAY: u8 = 14;
+
+    type Input = Manual;
+    type Output1 = u128;
+    type Output2 = u128;
+
+    fn parse(&self, input_file: &Path) -> io::Result<Self::Input> {
+        read_parsed(input_file)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Self::Output1 {
+        part1(input.clone())
+    }
+
+    fn part2(&self, input: &Self::Input) -> Self::Output2 {
+        part2(input.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_sample_input() {
+        let input = "NNCB
+
+CH -> B
+HH -> N
+CB -> H
+NH -> C
+HB -> C
+HC -> B
+HN -> C
+NN -> C
+BH -> H
+NC -> B
+NB -> B
+BN -> B
+BB -> N
+BC -> B
+CC -> N
+CN -> C"
+            .to_string();
+
+        let manual = input.parse().unwrap();
+        let expected = 1588;
+
+        assert_eq!(expected, part1(manual));
+    }
+
+    #[test]
+    fn part2_sample_input() {
+        let input = "NNCB
+
+CH -> B
+HH -> N
+CB -> H
+NH -> C
+HB -> C
+HC -> B
+HN -> C
+NN -> C
+BH -> H
+NC -> B
+NB -> B
+BN -> B
+BB -> N
+BC -> B
+CC -> N
+CN -> C"
+            .to_string();
+
+        let manual = input.parse().unwrap();
+        let expected = 2188189693529;
+
+        assert_eq!(expected, part2(manual));
+    }
+
+    #[test]
+    fn threshold_queries() {
+        let input = "NNCB
+
+CH -> B
+HH -> N
+CB -> H
+NH -> C
+HB -> C
+HC -> B
+HN -> C
+NN -> C
+BH -> H
+NC -> B
+NB -> B
+BN -> B
+BB -> N
+BC -> B
+CC -> N
+CN -> C"
+            .to_string();
+
+        let manual: Manual = input.parse().unwrap();
+        assert_eq!(Ok(1), polymerize(manual.clone(), 0));
+        assert_eq!(Ok(1588), polymerize(manual.clone(), 10));
+
+        assert_eq!(
+            Ok(Some(10)),
+            steps_until_difference_exceeds(manual.clone(), 1587, 40)
+        );
+        assert_eq!(
+            Ok(Some(11)),
+            steps_until_difference_exceeds(manual.clone(), 1588, 40)
+        );
+        assert_eq!(
+            Ok(Some(0)),
+            steps_until_difference_exceeds(manual.clone(), 0, 40)
+        );
+        assert_eq!(Ok(None), steps_until_difference_exceeds(manual, 1588, 10));
+    }
+
+    #[test]
+    fn high_step_counts() {
+        let input = "NNCB
+
+CH -> B
+HH -> N
+CB -> H
+NH -> C
+HB -> C
+HC -> B
+HN -> C
+NN -> C
+BH -> H
+NC -> B
+NB -> B
+BN -> B
+BB -> N
+BC -> B
+CC -> N
+CN -> C"
+            .to_string();
+
+        let manual: Manual = input.parse().unwrap();
+
+        // every step inserts an element between each pair, so the polymer of length L
+        // grows into one of length 2L - 1, i.e. (L0 - 1) * 2^n + 1 after n steps
+        for steps in [10, 40, 80, 100, 126] {
+            let mut polymer = manual.clone();
+            polymer.apply_steps(steps).unwrap();
+            assert_eq!(
+                Ok(3 * 2u128.pow(steps as u32) + 1),
+                polymer.polymer_length()
+            );
+        }
+
+        let mut polymer = manual.clone();
+        polymer.apply_steps(127).unwrap();
+        assert_eq!(Err(CountOverflow), polymer.polymer_length());
+        assert_eq!(Err(CountOverflow), polymerize(manual, 200));
+    }
+
+    #[test]
+    fn literal_polymer_expansion() {
+        let input = "NNCB
+
+CH -> B
+HH -> N
+CB -> H
+NH -> C
+HB -> C
+HC -> B
+HN -> C
+NN -> C
+BH -> H
+NC -> B
+NB -> B
+BN -> B
+BB -> N
+BC -> B
+CC -> N
+CN -> C"
+            .to_string();
+
+        let manual: Manual = input.parse().unwrap();
+        assert_eq!("NNCB", manual.expand_polymer(0));
+        assert_eq!("NCNBCHB", manual.expand_polymer(1));
+        assert_eq!("NBCCNBBBCBHCB", manual.expand_polymer(2));
+        assert_eq!("NBBBCNCCNBBNBNBBCHBHHBCHB", manual.expand_polymer(3));
+        assert_eq!(
+            "NBBNBNBBCCNBCNCCNBBNBBNBBBNBBNBBCBHCBHHNHCBBCBHCB",
+            manual.expand_polymer(4)
+        );
+        assert_eq!(3073, manual.expand_polymer(10).len());
+
+        assert_eq!(Ok(()), cross_check(manual, DEFAULT_LITERAL_STEPS_BOUND));
+    }
+
+    #[test]
+    fn cross_checking_unmatched_pairs() {
+        // not every pair has a corresponding rule
+        let input = "ABCA
+
+AB -> C
+CA -> B"
+            .to_string();
+
+        let manual = Manual::parse_with_unmatched_pairs(&input).unwrap();
+        assert_eq!("ACBCBA", manual.expand_polymer(1));
+        assert_eq!(Ok(()), cross_check(manual, 8));
+    }
+
+    #[test]
+    fn rule_coverage_validation() {
+        let input = "ABCA
+
+AB -> C
+CA -> B"
+            .to_string();
+
+        assert_eq!(
+            MalformedManual::UnmatchedPairs(vec![('A', 'C'), ('B', 'A'), ('B', 'C'), ('C', 'B')]),
+            input.parse::<Manual>().unwrap_err()
+        );
+
+        // a truncated rules section
+        let input = "NNCB
+
+CH -> B
+HH -> N
+CB -> H
+NH -> C
+HB -> C
+HC -> B
+HN -> C
+NN -> C
+BH -> H
+NC -> B
+NB -> B
+BN -> B
+BB -> N
+BC -> B
+CC -> N"
+            .to_string();
+
+        let err = input.parse::<Manual>().unwrap_err();
+        assert_eq!(MalformedManual::UnmatchedPairs(vec![('C', 'N')]), err);
+        assert_eq!(
+            "there are no insertion rules for the following pairs: CN",
+            err.to_string()
+        );
+
+        assert_eq!(
+            MalformedManual::MissingRules,
+            "NNCB".parse::<Manual>().unwrap_err()
+        );
+        assert_eq!(
+            MalformedManual::EmptyTemplate,
+            "\n\nAB -> C".parse::<Manual>().unwrap_err()
+        );
+        assert_eq!(
+            MalformedManual::MalformedRule(MalformedRule("AB -> CD".to_string())),
+            "AB\n\nAB -> CD".parse::<Manual>().unwrap_err()
+        );
+    }
+}
@@ -12,207 +12,49 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use itertools::Itertools;
-use std::collections::HashMap;
-use std::str::FromStr;
-use utils::execution::execute_struct;
-use utils::input_read::read_parsed;
+use day14::{
+    cross_check, polymerize, steps_until_difference_exceeds, Day14, Manual,
+    DEFAULT_LITERAL_STEPS_BOUND,
+};
+use utils::cli::{flag_value, parsed_flag_value};
+use utils::execute_keeping_input;
 
-type Pair = (char, char);
+// upper bound on the number of steps when looking for the threshold
+const MAX_STEPS: usize = 120;
 
-#[derive(Debug)]
-struct MalformedRule;
-
-#[derive(Debug, Clone)]
-struct Rule {
-    pair: Pair,
-    insertion: char,
-}
-
-impl FromStr for Rule {
-    type Err = MalformedRule;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut split = s.split(" -> ");
-        let mut pair_raw = split.next().ok_or(MalformedRule)?.chars();
-        let pair = (
-            pair_raw.next().ok_or(MalformedRule)?,
-            pair_raw.next().ok_or(MalformedRule)?,
-        );
-
-        let insertion = split
-            .next()
-            .ok_or(MalformedRule)?
-            .to_owned()
-            .chars()
-            .next()
-            .ok_or(MalformedRule)?;
-
-        Ok(Rule { pair, insertion })
-    }
-}
-
-impl Rule {
-    fn apply(&self) -> (Pair, Pair) {
-        ((self.pair.0, self.insertion), (self.insertion, self.pair.1))
-    }
-}
-
-#[derive(Debug, Clone)]
-struct Manual {
-    front: char,
-    pairs: HashMap<Pair, usize>,
-    rules: Vec<Rule>,
-}
-
-impl FromStr for Manual {
-    type Err = MalformedRule;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let lines = s
-            .replace("\r\n", "\n") // Windows fix
-            .split("\n\n")
-            .map(|split| split.to_owned())
-            .collect::<Vec<_>>();
-
-        let mut pairs: HashMap<Pair, usize> = HashMap::new();
-
-        let mut front = 'Z';
-        for (i, pair) in lines[0].chars().tuple_windows().enumerate() {
-            *pairs.entry(pair).or_default() += 1;
-            if i == 0 {
-                front = pair.0;
-            }
-        }
+#[cfg(not(tarpaulin))]
+fn main() {
+    let manual: Manual = execute_keeping_input(&Day14, "input");
 
-        // let points = lines[0].lines().map(|s| s.parse().unwrap()).collect();
-        let mut rules = Vec::new();
-        for rule in lines[1].lines() {
-            rules.push(rule.parse()?)
+    // e.g. `cargo run --release -- --steps 20`
+    if let Some(steps) = parsed_flag_value("--steps") {
+        match polymerize(manual.clone(), steps) {
+            Ok(difference) => println!("\nAfter {steps} steps the difference is {difference}"),
+            Err(_) => println!("\nThe element counts overflow before reaching {steps} steps"),
         }
-
-        Ok(Manual {
-            front,
-            pairs,
-            rules,
-        })
     }
-}
-
-impl Manual {
-    fn step(&mut self) {
-        let mut new_pairs = self.pairs.clone();
-        for rule in &self.rules {
-            if let Some(count) = self.pairs.remove(&rule.pair) {
-                let inserted = rule.apply();
 
-                *new_pairs.entry(rule.pair).or_default() -= count;
-                *new_pairs.entry(inserted.0).or_default() += count;
-                *new_pairs.entry(inserted.1).or_default() += count;
+    // e.g. `cargo run --release -- --exceeds 1000000`
+    if let Some(threshold) = parsed_flag_value("--exceeds") {
+        match steps_until_difference_exceeds(manual.clone(), threshold, MAX_STEPS) {
+            Ok(Some(steps)) => println!("\nThe difference exceeds {threshold} after {steps} steps"),
+            Ok(None) => {
+                println!("\nThe difference does not exceed {threshold} within {MAX_STEPS} steps")
             }
+            Err(_) => println!("\nThe element counts overflow before exceeding {threshold}"),
         }
-
-        self.pairs = new_pairs
-            .into_iter()
-            .filter(|(_, count)| *count != 0)
-            .collect();
     }
 
-    fn apply_steps(&mut self, count: usize) {
-        for _ in 0..count {
-            self.step()
-        }
-    }
-
-    fn element_count(&self) -> HashMap<char, usize> {
-        let mut count = HashMap::new();
-        for (pair, occurrences) in self.pairs.iter() {
-            *count.entry(pair.1).or_default() += occurrences;
+    // e.g. `cargo run --release -- --cross-check 12`
+    if let Some(bound) = flag_value("--cross-check") {
+        let bound = bound.parse().unwrap_or(DEFAULT_LITERAL_STEPS_BOUND);
+        match cross_check(manual, bound) {
+            Ok(_) => {
+                println!("\nThe literal polymer matches the pair counts for up to {bound} steps")
+            }
+            Err(mismatch) => {
+                println!("\nThe literal polymer does not match the pair counts: {mismatch:?}")
+            }
         }
-        *count.entry(self.front).or_default() += 1;
-        count
-    }
-
-    fn max_frequency_difference(&self) -> usize {
-        let count = self.element_count();
-
-        count.iter().max_by_key(|(_, &count)| count).unwrap().1
-            - count.iter().min_by_key(|(_, &count)| count).unwrap().1
-    }
-}
-
-fn part1(mut manual: Manual) -> usize {
-    manual.apply_steps(10);
-    manual.max_frequency_difference()
-}
-
-fn part2(mut manual: Manual) -> usize {
-    manual.apply_steps(40);
-    manual.max_frequency_difference()
-}
-
-#[cfg(not(tarpaulin))]
-fn main() {
-    execute_struct("input", read_parsed, part1, part2)
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn part1_sample_input() {
-        let input = "NNCB
-
-CH -> B
-HH -> N
-CB -> H
-NH -> C
-HB -> C
-HC -> B
-HN -> C
-NN -> C
-BH -> H
-NC -> B
-NB -> B
-BN -> B
-BB -> N
-BC -> B
-CC -> N
-CN -> C"
-            .to_string();
-
-        let manual = input.parse().unwrap();
-        let expected = 1588;
-
-        assert_eq!(expected, part1(manual));
-    }
-
-    #[test]
-    fn part2_sample_input() {
-        let input = "NNCB
-
-CH -> B
-HH -> N
-CB -> H
-NH -> C
-HB -> C
-HC -> B
-HN -> C
-NN -> C
-BH -> H
-NC -> B
-NB -> B
-BN -> B
-BB -> N
-BC -> B
-CC -> N
-CN -> C"
-            .to_string();
-
-        let manual = input.parse().unwrap();
-        let expected = 2188189693529;
-
-        assert_eq!(expected, part2(manual));
     }
 }
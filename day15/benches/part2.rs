@@ -0,0 +1,49 @@
+// Copyright 2022 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use day15::{Algorithm, RiskLevelMap, FULL_CAVE_TILES};
+use utils::input_read::read_parsed;
+
+fn part2_algorithms(c: &mut Criterion) {
+    let risk_map: RiskLevelMap = read_parsed(concat!(env!("CARGO_MANIFEST_DIR"), "/input"))
+        .expect("failed to read input file");
+
+    // the timings alone do not show how much work the heuristic saves
+    for algorithm in Algorithm::ALL {
+        let expansions = risk_map.search(FULL_CAVE_TILES, algorithm).expansions;
+        println!("part2/{algorithm:?}: {expansions} expansions");
+    }
+
+    let mut group = c.benchmark_group("part2");
+    group.bench_function("heap", |b| {
+        b.iter(|| {
+            black_box(&risk_map).lowest_risk_tiled_path_cost(FULL_CAVE_TILES, Algorithm::Heap)
+        })
+    });
+    group.bench_function("bucket", |b| {
+        b.iter(|| {
+            black_box(&risk_map).lowest_risk_tiled_path_cost(FULL_CAVE_TILES, Algorithm::Bucket)
+        })
+    });
+    group.bench_function("astar", |b| {
+        b.iter(|| {
+            black_box(&risk_map).lowest_risk_tiled_path_cost(FULL_CAVE_TILES, Algorithm::AStar)
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, part2_algorithms);
+criterion_main!(benches);
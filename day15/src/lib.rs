@@ -0,0 +1,391 @@
+// Copyright 2021 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashSet;
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+use utils::grid::{orthogonal_neighbours, Grid, GridError, Position};
+use utils::input_read::read_parsed;
+use utils::render::FrameSink;
+use utils::search::{astar, dial, dijkstra};
+use utils::{Solver, Visualize};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Algorithm {
+    /// Dijkstra's algorithm backed by a binary heap operating on `(x, y)` positions.
+    Heap,
+    /// Dial's algorithm operating on flat indices, exploiting the bounded risk levels.
+    #[default]
+    Bucket,
+    /// A* using the manhattan distance to the bottom right corner as the heuristic.
+    AStar,
+}
+
+impl Algorithm {
+    pub const ALL: [Algorithm; 3] = [Algorithm::Heap, Algorithm::Bucket, Algorithm::AStar];
+}
+
+impl FromStr for Algorithm {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "heap" => Ok(Algorithm::Heap),
+            "bucket" => Ok(Algorithm::Bucket),
+            "astar" => Ok(Algorithm::AStar),
+            other => Err(format!("{other} is not a valid algorithm")),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RiskLevelMap {
+    grid: Grid<u8>,
+}
+
+pub type Pos = Position;
+
+#[derive(Debug)]
+pub struct SearchResult {
+    pub path: Vec<Pos>,
+    pub cost: usize,
+    /// Number of nodes whose successors had to be explored.
+    pub expansions: usize,
+}
+
+impl FromStr for RiskLevelMap {
+    type Err = GridError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(RiskLevelMap { grid: s.parse()? })
+    }
+}
+
+/// Number of times the map is tiled in each direction to form the full cave in the second part.
+pub const FULL_CAVE_TILES: usize = 5;
+
+const MAX_RISK: usize = 9;
+
+impl RiskLevelMap {
+    fn width(&self) -> usize {
+        self.grid.width()
+    }
+
+    fn height(&self) -> usize {
+        self.grid.height()
+    }
+
+    // risk level in the cave formed by tiling the map in both directions, where each tile
+    // to the right or downward has its risk levels increased by 1, wrapping from 9 back to 1
+    fn tiled_risk(&self, (x, y): Pos) -> usize {
+        let increase = x / self.width() + y / self.height();
+        let base = self.grid[(x % self.width(), y % self.height())] as usize;
+        (base + increase - 1) % 9 + 1
+    }
+
+    pub fn lowest_risk_path_cost(&self, algorithm: Algorithm) -> usize {
+        self.lowest_risk_tiled_path_cost(1, algorithm)
+    }
+
+    pub fn lowest_risk_tiled_path_cost(&self, tiles: usize, algorithm: Algorithm) -> usize {
+        self.search(tiles, algorithm).cost
+    }
+
+    pub fn lowest_risk_tiled_path(&self, tiles: usize, algorithm: Algorithm) -> Vec<Pos> {
+        self.search(tiles, algorithm).path
+    }
+
+    // rather than materializing the entire tiled cave, the risk levels are computed on the fly
+    pub fn search(&self, tiles: usize, algorithm: Algorithm) -> SearchResult {
+        let width = self.width() * tiles;
+        let height = self.height() * tiles;
+        let end = (width - 1, height - 1);
+
+        let mut expansions = 0;
+        let mut successors = |pos: &Pos| {
+            expansions += 1;
+            self.tiled_successors(*pos, width, height)
+        };
+
+        let (path, cost) = match algorithm {
+            Algorithm::Heap => dijkstra(&(0, 0), &mut successors, |&p| p == end),
+            // every cell has risk of at least 1, so the manhattan distance never overestimates
+            Algorithm::AStar => astar(
+                &(0, 0),
+                &mut successors,
+                |&(x, y)| (end.0 - x) + (end.1 - y),
+                |&p| p == end,
+            ),
+            Algorithm::Bucket => dial(width * height, 0, width * height - 1, MAX_RISK, |index| {
+                expansions += 1;
+                self.index_successors(index, width, height)
+            })
+            .map(|(path, cost)| {
+                let path = path.into_iter().map(|i| (i % width, i / width)).collect();
+                (path, cost)
+            }),
+        }
+        .expect("there is always a path to the bottom right corner");
+
+        SearchResult {
+            path,
+            cost,
+            expansions,
+        }
+    }
+
+    // overlays the path onto the (tiled) risk map, highlighting its risk levels
+    pub fn render_path(&self, tiles: usize, path: &[Pos]) -> String {
+        let path = path.iter().copied().collect::<HashSet<_>>();
+        let mut rendered = String::new();
+        for y in 0..self.height() * tiles {
+            for x in 0..self.width() * tiles {
+                let risk = self.tiled_risk((x, y));
+                if path.contains(&(x, y)) {
+                    rendered.push_str(&format!("\x1b[1;31m{risk}\x1b[0m"));
+                } else {
+                    rendered.push_str(&risk.to_string());
+                }
+            }
+            rendered.push('\n');
+        }
+        rendered
+    }
+
+    fn tiled_successors(
+        &self,
+        pos: Pos,
+        width: usize,
+        height: usize,
+    ) -> impl Iterator<Item = (Pos, usize)> + '_ {
+        orthogonal_neighbours(pos, width, height)
+            .map(|neighbour| (neighbour, self.tiled_risk(neighbour)))
+    }
+
+    // equivalent of `tiled_successors` operating on flat `y * width + x` indices
+    fn index_successors(
+        &self,
+        index: usize,
+        width: usize,
+        height: usize,
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.tiled_successors((index % width, index / width), width, height)
+            .map(move |((x, y), risk)| (y * width + x, risk))
+    }
+}
+
+pub fn part1(risk_map: RiskLevelMap, algorithm: Algorithm) -> usize {
+    risk_map.lowest_risk_path_cost(algorithm)
+}
+
+pub fn part2(risk_map: RiskLevelMap, algorithm: Algorithm) -> usize {
+    risk_map.lowest_risk_tiled_path_cost(FULL_CAVE_TILES, algorithm)
+}
+
+/// Finds the lowest total risk path through the chiton cave.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Day15 {
+    pub algorithm: Algorithm,
+}
+
+impl Solver for Day15 {
+    const DAY: u8 = 15;
+
+    type Input = RiskLevelMap;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(&self, input_file: &Path) -> io::Result<Self::Input> {
+        read_parsed(input_file)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Self::Output1 {
+        part1(input.clone(), self.algorithm)
+    }
+
+    fn part2(&self, input: &Self::Input) -> Self::Output2 {
+        part2(input.clone(), self.algorithm)
+    }
+}
+
+impl Visualize for Day15 {
+    // the lowest risk paths through the map and through the full cave
+    fn visualize(&self, input: &Self::Input, sink: &mut dyn FrameSink) -> io::Result<()> {
+        for tiles in [1, FULL_CAVE_TILES] {
+            let size = (input.width() * tiles, input.height() * tiles);
+            let path = input.lowest_risk_tiled_path(tiles, self.algorithm);
+            sink.push_frame(size, &path)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utils::render::FrameBuffer;
+
+    #[test]
+    fn part1_sample_input() {
+        let input: RiskLevelMap = "1163751742
+1381373672
+2136511328
+3694931569
+7463417111
+1319128137
+1359912421
+3125421639
+1293138521
+2311944581"
+            .parse()
+            .unwrap();
+
+        let expected = 40;
+        for algorithm in Algorithm::ALL {
+            assert_eq!(expected, part1(input.clone(), algorithm));
+        }
+    }
+
+    #[test]
+    fn part2_sample_input() {
+        let input: RiskLevelMap = "1163751742
+1381373672
+2136511328
+3694931569
+7463417111
+1319128137
+1359912421
+3125421639
+1293138521
+2311944581"
+            .parse()
+            .unwrap();
+
+        let expected = 315;
+        for algorithm in Algorithm::ALL {
+            assert_eq!(expected, part2(input.clone(), algorithm));
+        }
+    }
+
+    #[test]
+    fn lowest_risk_path() {
+        let input: RiskLevelMap = "1163751742
+1381373672
+2136511328
+3694931569
+7463417111
+1319128137
+1359912421
+3125421639
+1293138521
+2311944581"
+            .parse()
+            .unwrap();
+
+        for algorithm in Algorithm::ALL {
+            let path = input.lowest_risk_tiled_path(1, algorithm);
+            assert_eq!(Some(&(0, 0)), path.first());
+            assert_eq!(Some(&(9, 9)), path.last());
+            // consecutive positions are always adjacent
+            assert!(path
+                .windows(2)
+                .all(|w| w[0].0.abs_diff(w[1].0) + w[0].1.abs_diff(w[1].1) == 1));
+            // the starting position is never entered, so its risk is not counted
+            let risk: usize = path.iter().skip(1).map(|&pos| input.tiled_risk(pos)).sum();
+            assert_eq!(40, risk);
+        }
+    }
+
+    #[test]
+    fn astar_expands_fewer_nodes() {
+        // with uniform risk levels (and no tiling) the manhattan distance is exact,
+        // so A* heads straight for the exit
+        let input: RiskLevelMap = ["1111111111"; 10].join("\n").parse().unwrap();
+
+        let heap = input.search(1, Algorithm::Heap);
+        let astar = input.search(1, Algorithm::AStar);
+        assert_eq!(heap.cost, astar.cost);
+        assert!(astar.expansions < heap.expansions);
+    }
+
+    #[test]
+    fn malformed_map() {
+        assert!(matches!(
+            "116\n1x1".parse::<RiskLevelMap>(),
+            Err(GridError::InvalidDigit { .. })
+        ));
+        assert!(matches!(
+            "116\n13".parse::<RiskLevelMap>(),
+            Err(GridError::RaggedRow { .. })
+        ));
+    }
+
+    #[test]
+    fn tiled_risk_levels() {
+        let input: RiskLevelMap = "1163751742
+1381373672
+2136511328
+3694931569
+7463417111
+1319128137
+1359912421
+3125421639
+1293138521
+2311944581"
+            .parse()
+            .unwrap();
+
+        let first_row = (0..50)
+            .map(|x| input.tiled_risk((x, 0)).to_string())
+            .collect::<String>();
+        let last_row = (0..50)
+            .map(|x| input.tiled_risk((x, 49)).to_string())
+            .collect::<String>();
+
+        assert_eq!(
+            "11637517422274862853338597396444961841755517295286",
+            first_row
+        );
+        assert_eq!(
+            "67554889357866599146897761125791887223681299833479",
+            last_row
+        );
+    }
+
+    #[test]
+    fn visualizing_paths() {
+        let input: RiskLevelMap = "1163751742
+1381373672
+2136511328
+3694931569
+7463417111
+1319128137
+1359912421
+3125421639
+1293138521
+2311944581"
+            .parse()
+            .unwrap();
+
+        let mut frames = FrameBuffer::default();
+        Day15::default().visualize(&input, &mut frames).unwrap();
+        assert_eq!(2, frames.frames.len());
+        for ((size, path), end) in frames.frames.iter().zip([(9, 9), (49, 49)]) {
+            assert_eq!((end.0 + 1, end.1 + 1), *size);
+            assert_eq!(Some(&(0, 0)), path.first());
+            assert_eq!(Some(&end), path.last());
+        }
+    }
+}
@@ -12,176 +12,43 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use pathfinding::prelude::dijkstra;
-use std::ops::Index;
-use std::str::FromStr;
-use utils::execution::execute_struct;
-use utils::input_read::read_parsed;
+use day15::{Algorithm, Day15, RiskLevelMap, FULL_CAVE_TILES};
+use std::path::Path;
+use utils::cli::{flag_value, has_flag, parsed_flag_value};
+use utils::execute_keeping_input;
+use utils::render::write_image;
 
-#[derive(Debug, Clone)]
-struct RiskLevelMap {
-    rows: Vec<Vec<usize>>,
-}
-
-type Pos = (usize, usize);
-
-impl FromStr for RiskLevelMap {
-    type Err = ();
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let rows: Vec<Vec<_>> = s
-            .lines()
-            .map(|row| {
-                row.chars()
-                    .map(|char| char.to_digit(10).unwrap() as usize)
-                    .collect()
-            })
-            .collect();
-
-        Ok(Self { rows })
-    }
-}
-
-impl Index<Pos> for RiskLevelMap {
-    type Output = usize;
-
-    fn index(&self, index: Pos) -> &Self::Output {
-        let (x, y) = index;
-        &self.rows[y][x]
-    }
-}
-
-impl RiskLevelMap {
-    fn lowest_risk_path_cost(&self) -> usize {
-        let start = (0usize, 0usize);
-        let end = (self.rows[0].len() - 1, self.rows.len() - 1);
-        let (_, cost) = dijkstra(&start, |pos| self.node_successors(pos), |&p| p == end).unwrap();
-
-        cost
-    }
-
-    fn node_successors(&self, node: &Pos) -> Vec<(Pos, usize)> {
-        let mut successors = Vec::new();
-        if node.0 > 0 {
-            let left = (node.0 - 1, node.1);
-            successors.push((left, self[left]))
-        }
-
-        if node.0 < self.rows[0].len() - 1 {
-            let right = (node.0 + 1, node.1);
-            successors.push((right, self[right]))
-        }
-
-        if node.1 > 0 {
-            let top = (node.0, node.1 - 1);
-            successors.push((top, self[top]))
-        }
-
-        if node.1 < self.rows.len() - 1 {
-            let bottom = (node.0, node.1 + 1);
-            successors.push((bottom, self[bottom]))
-        }
-
-        successors
-    }
-
-    fn map_value(i: usize, val: usize) -> usize {
-        if i == 0 {
-            val
-        } else {
-            let res = val + i;
-            if res > 9 {
-                res - 9
-            } else {
-                res
-            }
+#[cfg(not(tarpaulin))]
+fn main() {
+    // e.g. `cargo run --release -- --algorithm heap` to compare against the binary heap
+    let algorithm = parsed_flag_value("--algorithm").unwrap_or_default();
+    let risk_map: RiskLevelMap = execute_keeping_input(&Day15 { algorithm }, "input");
+
+    // e.g. `cargo run --release -- --verbose` to compare the search strategies on the full cave
+    if has_flag("--verbose") {
+        for algorithm in Algorithm::ALL {
+            let start = std::time::Instant::now();
+            let result = risk_map.search(FULL_CAVE_TILES, algorithm);
+            println!(
+                "{algorithm:?}: cost {}, {} nodes expanded in {:?}",
+                result.cost,
+                result.expansions,
+                start.elapsed()
+            );
         }
     }
 
-    fn expand_row_five_folds(&mut self, row: usize) {
-        let old = std::mem::take(&mut self.rows[row]);
-        self.rows[row] = std::iter::repeat(old)
-            .take(5)
-            .enumerate()
-            .flat_map(|(i, vals)| vals.into_iter().map(move |v| Self::map_value(i, v)))
-            .collect::<Vec<_>>();
-    }
-
-    fn expand_columns_five_folds(&mut self) {
-        let rows = self.rows.clone();
-        for i in 1..=4 {
-            for row in rows.clone() {
-                let new_row = row
-                    .clone()
-                    .into_iter()
-                    .map(|v| Self::map_value(i, v))
-                    .collect();
-                self.rows.push(new_row);
-            }
-        }
+    // e.g. `cargo run --release -- --show-path` to overlay the path onto the original map
+    if has_flag("--show-path") {
+        let path = risk_map.lowest_risk_tiled_path(1, algorithm);
+        println!("\n{}", risk_map.render_path(1, &path));
     }
 
-    fn expand_five_folds(&mut self) {
-        for i in 0..self.rows.len() {
-            self.expand_row_five_folds(i)
+    // e.g. `cargo run --release -- --export path.svg` to draw the path through the full cave
+    if let Some(path) = flag_value("--export") {
+        let lowest_risk_path = risk_map.lowest_risk_tiled_path(FULL_CAVE_TILES, algorithm);
+        if let Err(err) = write_image(Path::new(&path), lowest_risk_path) {
+            eprintln!("failed to export the path - {err}")
         }
-        self.expand_columns_five_folds()
-    }
-}
-
-fn part1(risk_map: RiskLevelMap) -> usize {
-    risk_map.lowest_risk_path_cost()
-}
-
-fn part2(mut risk_map: RiskLevelMap) -> usize {
-    risk_map.expand_five_folds();
-    risk_map.lowest_risk_path_cost()
-}
-
-#[cfg(not(tarpaulin))]
-fn main() {
-    execute_struct("input", read_parsed, part1, part2)
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn part1_sample_input() {
-        let input = "1163751742
-1381373672
-2136511328
-3694931569
-7463417111
-1319128137
-1359912421
-3125421639
-1293138521
-2311944581"
-            .parse()
-            .unwrap();
-
-        let expected = 40;
-        assert_eq!(expected, part1(input))
-    }
-
-    #[test]
-    fn part2_sample_input() {
-        let input = "1163751742
-1381373672
-2136511328
-3694931569
-7463417111
-1319128137
-1359912421
-3125421639
-1293138521
-2311944581"
-            .parse()
-            .unwrap();
-
-        let expected = 315;
-        assert_eq!(expected, part2(input))
     }
 }
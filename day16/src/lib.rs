@@ -0,0 +1,1335 @@
+// Copyright 2021 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use bitvec::prelude::*;
+use bitvec::view::BitView;
+use std::collections::HashMap;
+use std::fmt::{self, Display, Formatter};
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+use utils::input_read::read_parsed;
+use utils::Solver;
+
+pub mod stream;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecodeErrorKind {
+    /// The transmission is not a valid hexadecimal string.
+    InvalidHex,
+    /// The transmission ended in the middle of a packet.
+    TruncatedPacket,
+    /// The literal value does not fit in 64 bits.
+    OversizedLiteral,
+    /// The sub-packets do not add up to the total length specified by their operator.
+    LengthMismatch,
+    /// The outermost packet is followed by something other than the zero padding.
+    TrailingGarbage,
+}
+
+/// Failure to decode the transmission, alongside the bit offset at which it was detected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodeError {
+    pub offset: usize,
+    pub kind: DecodeErrorKind,
+}
+
+impl DecodeError {
+    pub(crate) fn new(offset: usize, kind: DecodeErrorKind) -> Self {
+        DecodeError { offset, kind }
+    }
+}
+
+impl Display for DecodeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let description = match self.kind {
+            DecodeErrorKind::InvalidHex => "the transmission is not a valid hex string",
+            DecodeErrorKind::TruncatedPacket => "the transmission ended in the middle of a packet",
+            DecodeErrorKind::OversizedLiteral => "the literal value does not fit in 64 bits",
+            DecodeErrorKind::LengthMismatch => "the sub-packets do not match the operator length",
+            DecodeErrorKind::TrailingGarbage => "the packet is followed by non-zero bits",
+        };
+        write!(f, "{description} (at bit {})", self.offset)
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EvaluationError {
+    /// The intermediate result does not fit in the value type.
+    Overflow,
+    /// The operator has the wrong number of operands, such as comparison of three values.
+    InvalidOperandCount { type_id: Type, operands: usize },
+}
+
+impl Display for EvaluationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            EvaluationError::Overflow => write!(f, "the evaluation overflowed"),
+            EvaluationError::InvalidOperandCount { type_id, operands } => {
+                write!(f, "{type_id:?} packet can't have {operands} operands")
+            }
+        }
+    }
+}
+
+impl std::error::Error for EvaluationError {}
+
+/// Numeric type the packets can be evaluated over.
+pub trait Value: Ord + Sized {
+    fn from_literal(val: u64) -> Self;
+
+    fn from_bool(val: bool) -> Self;
+
+    fn checked_add(self, other: Self) -> Option<Self>;
+
+    fn checked_mul(self, other: Self) -> Option<Self>;
+}
+
+impl Value for u128 {
+    fn from_literal(val: u64) -> Self {
+        val.into()
+    }
+
+    fn from_bool(val: bool) -> Self {
+        val.into()
+    }
+
+    fn checked_add(self, other: Self) -> Option<Self> {
+        u128::checked_add(self, other)
+    }
+
+    fn checked_mul(self, other: Self) -> Option<Self> {
+        u128::checked_mul(self, other)
+    }
+}
+
+// arbitrary precision values never overflow
+#[cfg(feature = "bigint")]
+impl Value for num_bigint::BigUint {
+    fn from_literal(val: u64) -> Self {
+        val.into()
+    }
+
+    fn from_bool(val: bool) -> Self {
+        u8::from(val).into()
+    }
+
+    fn checked_add(self, other: Self) -> Option<Self> {
+        Some(self + other)
+    }
+
+    fn checked_mul(self, other: Self) -> Option<Self> {
+        Some(self * other)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum EncodeError {
+    /// The packet version does not fit in the 3 bits of the header.
+    VersionOutOfRange(u64),
+    /// The content of the packet does not match its type, e.g. literal value of a sum packet.
+    InconsistentContent(Type),
+    /// The sub-packets of the operator are too long to have their length encoded.
+    OversizedOperator,
+}
+
+impl Display for EncodeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            EncodeError::VersionOutOfRange(version) => {
+                write!(f, "version {version} does not fit in {VERSION_BITS} bits")
+            }
+            EncodeError::InconsistentContent(typ) => {
+                write!(f, "the packet content does not match its {typ:?} type")
+            }
+            EncodeError::OversizedOperator => {
+                write!(f, "the operator sub-packets are too long to be encoded")
+            }
+        }
+    }
+}
+
+impl std::error::Error for EncodeError {}
+
+/// The way the operator packet specifies the extent of its sub-packets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LengthType {
+    /// Total length in bits of the sub-packets, i.e. length type ID `0`.
+    TotalLength,
+    /// Number of the sub-packets, i.e. length type ID `1`. It is the more compact of the two.
+    #[default]
+    SubPacketCount,
+}
+
+const SUM_TYPE_ID: u64 = 0;
+const PRODUCT_TYPE_ID: u64 = 1;
+const MIN_TYPE_ID: u64 = 2;
+const MAX_TYPE_ID: u64 = 3;
+const LITERAL_VAL_TYPE_ID: u64 = 4;
+const GREATER_THAN_TYPE_ID: u64 = 5;
+const LESS_THAN_TYPE_ID: u64 = 6;
+const EQUAL_TYPE_ID: u64 = 7;
+
+pub(crate) const VERSION_BITS: usize = 3;
+pub(crate) const TYPE_ID_BITS: usize = 3;
+pub(crate) const LITERAL_GROUP_BITS: usize = 4;
+pub(crate) const TOTAL_LENGTH_BITS: usize = 15;
+pub(crate) const SUB_PACKET_COUNT_BITS: usize = 11;
+
+fn bits_to_u64(bits: &BitSlice<u8, Msb0>) -> Option<u64> {
+    if bits.len() > u64::BITS as usize {
+        return None;
+    }
+    let mut res = 0u64;
+    res.view_bits_mut::<Msb0>()[u64::BITS as usize - bits.len()..].clone_from_bitslice(bits);
+    Some(res)
+}
+
+// bounds-checked `bits[start..start + len]`, where `offset` is the position of `bits` within
+// the entire transmission
+fn take_bits(
+    bits: &BitSlice<u8, Msb0>,
+    start: usize,
+    len: usize,
+    offset: usize,
+) -> Result<&BitSlice<u8, Msb0>, DecodeError> {
+    bits.get(start..start + len)
+        .ok_or_else(|| DecodeError::new(offset + bits.len(), DecodeErrorKind::TruncatedPacket))
+}
+
+// reads a field of at most 64 bits
+fn read_field(
+    bits: &BitSlice<u8, Msb0>,
+    start: usize,
+    len: usize,
+    offset: usize,
+) -> Result<u64, DecodeError> {
+    debug_assert!(len <= u64::BITS as usize);
+    Ok(bits_to_u64(take_bits(bits, start, len, offset)?).unwrap_or_default())
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Copy, Hash)]
+pub enum Type {
+    Sum,
+    Product,
+    Min,
+    Max,
+    Literal,
+    GreaterThan,
+    LessThan,
+    Equal,
+}
+
+// all 3-bit values are valid type ids
+impl From<u64> for Type {
+    fn from(val: u64) -> Self {
+        match val & ((1 << TYPE_ID_BITS) - 1) {
+            SUM_TYPE_ID => Type::Sum,
+            PRODUCT_TYPE_ID => Type::Product,
+            MIN_TYPE_ID => Type::Min,
+            MAX_TYPE_ID => Type::Max,
+            LITERAL_VAL_TYPE_ID => Type::Literal,
+            GREATER_THAN_TYPE_ID => Type::GreaterThan,
+            LESS_THAN_TYPE_ID => Type::LessThan,
+            _ => Type::Equal,
+        }
+    }
+}
+
+impl Type {
+    pub(crate) fn is_literal(&self) -> bool {
+        matches!(self, Type::Literal)
+    }
+
+    fn id(&self) -> u64 {
+        match self {
+            Type::Sum => SUM_TYPE_ID,
+            Type::Product => PRODUCT_TYPE_ID,
+            Type::Min => MIN_TYPE_ID,
+            Type::Max => MAX_TYPE_ID,
+            Type::Literal => LITERAL_VAL_TYPE_ID,
+            Type::GreaterThan => GREATER_THAN_TYPE_ID,
+            Type::LessThan => LESS_THAN_TYPE_ID,
+            Type::Equal => EQUAL_TYPE_ID,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Header {
+    pub version: u64,
+    pub type_id: Type,
+}
+
+impl Header {
+    const LEN: usize = 6;
+
+    fn from_bits(bits: &BitSlice<u8, Msb0>, offset: usize) -> Result<Self, DecodeError> {
+        let version = read_field(bits, 0, VERSION_BITS, offset)?;
+        let type_id = Type::from(read_field(bits, VERSION_BITS, TYPE_ID_BITS, offset)?);
+
+        Ok(Header { version, type_id })
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Content {
+    Literal(u64),
+    Operator(Vec<Packet>),
+}
+
+impl Content {
+    fn parse_literal_value(
+        bits: &BitSlice<u8, Msb0>,
+        offset: usize,
+    ) -> Result<(Self, usize), DecodeError> {
+        let mut i = 0;
+        let mut literal_bits: BitVec<u8, Msb0> = BitVec::new();
+
+        loop {
+            let group = take_bits(bits, i, LITERAL_GROUP_BITS + 1, offset)?;
+            literal_bits.extend_from_bitslice(&group[1..]);
+            i += LITERAL_GROUP_BITS + 1;
+
+            if !group[0] {
+                break;
+            }
+        }
+
+        let value = bits_to_u64(&literal_bits)
+            .ok_or_else(|| DecodeError::new(offset, DecodeErrorKind::OversizedLiteral))?;
+        Ok((Content::Literal(value), i))
+    }
+
+    fn parse_operator_length_type_1(
+        bits: &BitSlice<u8, Msb0>,
+        offset: usize,
+    ) -> Result<(Self, usize), DecodeError> {
+        let mut sub_packets = Vec::new();
+        // The next 11 bits are a number that represents the number of sub-packets immediately contained by this packet.
+        let num_packets = read_field(bits, 0, SUB_PACKET_COUNT_BITS, offset)?;
+        let mut i = SUB_PACKET_COUNT_BITS;
+
+        for _ in 0..num_packets {
+            let (inner_packet, used_bits) = Packet::from_bits(&bits[i..], offset + i)?;
+            sub_packets.push(inner_packet);
+            i += used_bits;
+        }
+
+        Ok((Content::Operator(sub_packets), i))
+    }
+
+    fn parse_operator_length_type_0(
+        bits: &BitSlice<u8, Msb0>,
+        offset: usize,
+    ) -> Result<(Self, usize), DecodeError> {
+        let mut sub_packets = Vec::new();
+        // The next 15 bits are a number that represents the total length in bits of the sub-packets contained by this packet.
+        let subpackets_len = read_field(bits, 0, TOTAL_LENGTH_BITS, offset)? as usize;
+        let mut i = TOTAL_LENGTH_BITS;
+        // make sure none of the sub-packets can read past the specified length
+        let sub_bits = take_bits(bits, i, subpackets_len, offset)?;
+        let mut bits_left = subpackets_len;
+        while bits_left > 0 {
+            let used = subpackets_len - bits_left;
+            let (inner_packet, used_bits) = Packet::from_bits(&sub_bits[used..], offset + i)
+                .map_err(|err| match err.kind {
+                    // if there were more bits, they'd belong to another packet
+                    DecodeErrorKind::TruncatedPacket => {
+                        DecodeError::new(err.offset, DecodeErrorKind::LengthMismatch)
+                    }
+                    _ => err,
+                })?;
+            sub_packets.push(inner_packet);
+
+            i += used_bits;
+            bits_left -= used_bits;
+        }
+        Ok((Content::Operator(sub_packets), i))
+    }
+
+    fn from_bits(
+        bits: &BitSlice<u8, Msb0>,
+        typ: Type,
+        offset: usize,
+    ) -> Result<(Self, usize), DecodeError> {
+        if typ.is_literal() {
+            Self::parse_literal_value(bits, offset)
+        } else {
+            let length_type_id = take_bits(bits, 0, 1, offset)?[0];
+            let (content, used_bits) = if length_type_id {
+                Self::parse_operator_length_type_1(&bits[1..], offset + 1)?
+            } else {
+                Self::parse_operator_length_type_0(&bits[1..], offset + 1)?
+            };
+            Ok((content, used_bits + 1))
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Packet {
+    pub header: Header,
+    pub content: Content,
+}
+
+impl Packet {
+    pub fn version_sum(&self) -> usize {
+        match &self.content {
+            Content::Literal(_) => self.header.version as usize,
+            Content::Operator(operands) => {
+                self.header.version as usize
+                    + operands
+                        .iter()
+                        .map(|packet| packet.version_sum())
+                        .sum::<usize>()
+            }
+        }
+    }
+
+    /// Evaluates the expression represented by the packet, failing if any intermediate
+    /// result does not fit in 128 bits.
+    pub fn evaluate(&self) -> Result<u128, EvaluationError> {
+        self.evaluate_as()
+    }
+
+    /// Evaluates the expression represented by the packet over the specified value type.
+    pub fn evaluate_as<V: Value>(&self) -> Result<V, EvaluationError> {
+        let operands = match &self.content {
+            Content::Literal(val) => return Ok(V::from_literal(*val)),
+            Content::Operator(packets) => packets
+                .iter()
+                .map(Packet::evaluate_as)
+                .collect::<Result<Vec<V>, _>>()?,
+        };
+
+        let type_id = self.header.type_id;
+        let invalid_count = EvaluationError::InvalidOperandCount {
+            type_id,
+            operands: operands.len(),
+        };
+        if operands.is_empty() {
+            return Err(invalid_count);
+        }
+
+        match type_id {
+            Type::Sum => operands
+                .into_iter()
+                .try_fold(V::from_literal(0), V::checked_add)
+                .ok_or(EvaluationError::Overflow),
+            Type::Product => operands
+                .into_iter()
+                .try_fold(V::from_literal(1), V::checked_mul)
+                .ok_or(EvaluationError::Overflow),
+            Type::Min => operands.into_iter().min().ok_or(invalid_count),
+            Type::Max => operands.into_iter().max().ok_or(invalid_count),
+            Type::Literal => Err(invalid_count),
+            Type::GreaterThan | Type::LessThan | Type::Equal => {
+                let [first, second] = <[V; 2]>::try_from(operands).map_err(|_| invalid_count)?;
+                Ok(V::from_bool(match type_id {
+                    Type::GreaterThan => first > second,
+                    Type::LessThan => first < second,
+                    _ => first == second,
+                }))
+            }
+        }
+    }
+}
+
+impl Type {
+    fn operator_name(&self) -> &'static str {
+        match self {
+            Type::Sum => "sum",
+            Type::Product => "product",
+            Type::Min => "min",
+            Type::Max => "max",
+            Type::Literal => "literal",
+            Type::GreaterThan => ">",
+            Type::LessThan => "<",
+            Type::Equal => "==",
+        }
+    }
+
+    fn is_comparison(&self) -> bool {
+        matches!(self, Type::GreaterThan | Type::LessThan | Type::Equal)
+    }
+}
+
+impl Packet {
+    // comparisons are written infix, so they have to be parenthesised when nested in other comparisons
+    fn fmt_operand(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.header.type_id.is_comparison() {
+            write!(f, "({self})")
+        } else {
+            write!(f, "{self}")
+        }
+    }
+
+    // every packet on its own line, indented by its depth and annotated with its version
+    fn fmt_tree(&self, f: &mut Formatter<'_>, depth: usize) -> fmt::Result {
+        let indent = "  ".repeat(depth);
+        let version = self.header.version;
+        match &self.content {
+            Content::Literal(val) => write!(f, "{indent}{val} (v{version})"),
+            Content::Operator(operands) => {
+                write!(
+                    f,
+                    "{indent}{} (v{version})",
+                    self.header.type_id.operator_name()
+                )?;
+                for operand in operands {
+                    writeln!(f)?;
+                    operand.fmt_tree(f, depth + 1)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Renders the packet as a human-readable expression, such as `max(1, 2, 3) < sum(4, 5)`.
+/// The alternate form (`{:#}`) renders the packet tree instead, with each of the packets
+/// on its own line alongside its version, which is handy for debugging malformed transmissions.
+impl Display for Packet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            return self.fmt_tree(f, 0);
+        }
+
+        let operands = match &self.content {
+            Content::Literal(val) => return write!(f, "{val}"),
+            Content::Operator(operands) => operands,
+        };
+
+        let typ = self.header.type_id;
+        if typ.is_comparison() && operands.len() == 2 {
+            operands[0].fmt_operand(f)?;
+            write!(f, " {} ", typ.operator_name())?;
+            operands[1].fmt_operand(f)
+        } else {
+            write!(f, "{}(", typ.operator_name())?;
+            for (i, operand) in operands.iter().enumerate() {
+                if i > 0 {
+                    write!(f, ", ")?;
+                }
+                write!(f, "{operand}")?;
+            }
+            write!(f, ")")
+        }
+    }
+}
+
+impl FromStr for Packet {
+    type Err = DecodeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Packet::decode_transmission(&decode_hex(s)?)
+    }
+}
+
+fn decode_hex(s: &str) -> Result<BitVec<u8, Msb0>, DecodeError> {
+    let s = s.trim();
+    let decoded = hex::decode(s).map_err(|err| {
+        let offset = match err {
+            hex::FromHexError::InvalidHexCharacter { index, .. } => index * 4,
+            _ => s.len() * 4,
+        };
+        DecodeError::new(offset, DecodeErrorKind::InvalidHex)
+    })?;
+    Ok(BitVec::from_vec(decoded))
+}
+
+impl Packet {
+    /// Decodes the single outermost packet of the transmission, which may be followed only by
+    /// the zero padding.
+    pub fn decode_transmission(bits: &BitSlice<u8, Msb0>) -> Result<Self, DecodeError> {
+        Self::decode_with_length(bits).map(|(packet, _)| packet)
+    }
+
+    fn decode_with_length(bits: &BitSlice<u8, Msb0>) -> Result<(Self, usize), DecodeError> {
+        let (packet, used_bits) = Packet::from_bits(bits, 0)?;
+        match bits[used_bits..].first_one() {
+            Some(garbage) => Err(DecodeError::new(
+                used_bits + garbage,
+                DecodeErrorKind::TrailingGarbage,
+            )),
+            None => Ok((packet, used_bits)),
+        }
+    }
+
+    /// Number of packets of each type, including this one.
+    pub fn type_counts(&self) -> HashMap<Type, usize> {
+        let mut counts = HashMap::new();
+        let mut to_visit = vec![self];
+        while let Some(packet) = to_visit.pop() {
+            *counts.entry(packet.header.type_id).or_default() += 1;
+            if let Content::Operator(operands) = &packet.content {
+                to_visit.extend(operands)
+            }
+        }
+        counts
+    }
+
+    /// Depth of the most nested packet, with the lone literal having depth of 1.
+    pub fn depth(&self) -> usize {
+        match &self.content {
+            Content::Literal(_) => 1,
+            Content::Operator(operands) => {
+                1 + operands.iter().map(Packet::depth).max().unwrap_or_default()
+            }
+        }
+    }
+
+    fn from_bits(bits: &BitSlice<u8, Msb0>, offset: usize) -> Result<(Self, usize), DecodeError> {
+        let header = Header::from_bits(bits, offset)?;
+        let (content, bits_used) =
+            Content::from_bits(&bits[Header::LEN..], header.type_id, offset + Header::LEN)?;
+        let packet = Packet { header, content };
+        Ok((packet, bits_used + Header::LEN))
+    }
+
+    /// Encodes the packet into the BITS transmission, counting the operator sub-packets.
+    pub fn to_bits(&self) -> Result<BitVec<u8, Msb0>, EncodeError> {
+        self.to_bits_with(LengthType::default())
+    }
+
+    /// Encodes the packet into the BITS transmission, using the preferred length type
+    /// for all operators unless their sub-packets can't be described with it.
+    pub fn to_bits_with(&self, length_type: LengthType) -> Result<BitVec<u8, Msb0>, EncodeError> {
+        let mut bits = BitVec::new();
+        self.encode_into(&mut bits, length_type)?;
+        Ok(bits)
+    }
+
+    /// Encodes the packet into the hexadecimal transmission, padding it with zeroes to full byte.
+    pub fn to_hex(&self) -> Result<String, EncodeError> {
+        let mut bits = self.to_bits()?;
+        bits.resize(bits.len().next_multiple_of(u8::BITS as usize), false);
+        Ok(hex::encode_upper(bits.into_vec()))
+    }
+
+    fn encode_into(
+        &self,
+        bits: &mut BitVec<u8, Msb0>,
+        length_type: LengthType,
+    ) -> Result<(), EncodeError> {
+        if self.header.version >= 1 << VERSION_BITS {
+            return Err(EncodeError::VersionOutOfRange(self.header.version));
+        }
+        push_bits(bits, self.header.version, VERSION_BITS);
+        push_bits(bits, self.header.type_id.id(), TYPE_ID_BITS);
+
+        match &self.content {
+            Content::Literal(val) => {
+                if !self.header.type_id.is_literal() {
+                    return Err(EncodeError::InconsistentContent(self.header.type_id));
+                }
+                let significant_bits = (u64::BITS - val.leading_zeros()) as usize;
+                let groups = significant_bits.div_ceil(LITERAL_GROUP_BITS).max(1);
+                for group in (0..groups).rev() {
+                    // every group but the last one is prefixed with a 1 bit
+                    bits.push(group != 0);
+                    push_bits(
+                        bits,
+                        val >> (group * LITERAL_GROUP_BITS),
+                        LITERAL_GROUP_BITS,
+                    );
+                }
+            }
+            Content::Operator(sub_packets) => {
+                if self.header.type_id.is_literal() {
+                    return Err(EncodeError::InconsistentContent(self.header.type_id));
+                }
+                let countable = sub_packets.len() < 1 << SUB_PACKET_COUNT_BITS;
+                if length_type == LengthType::SubPacketCount && countable {
+                    bits.push(true);
+                    push_bits(bits, sub_packets.len() as u64, SUB_PACKET_COUNT_BITS);
+                    for sub_packet in sub_packets {
+                        sub_packet.encode_into(bits, length_type)?;
+                    }
+                } else {
+                    let mut sub_bits = BitVec::new();
+                    for sub_packet in sub_packets {
+                        sub_packet.encode_into(&mut sub_bits, length_type)?;
+                    }
+                    if sub_bits.len() < 1 << TOTAL_LENGTH_BITS {
+                        bits.push(false);
+                        push_bits(bits, sub_bits.len() as u64, TOTAL_LENGTH_BITS);
+                        bits.extend_from_bitslice(&sub_bits);
+                    } else if countable {
+                        bits.push(true);
+                        push_bits(bits, sub_packets.len() as u64, SUB_PACKET_COUNT_BITS);
+                        bits.extend_from_bitslice(&sub_bits);
+                    } else {
+                        return Err(EncodeError::OversizedOperator);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+// appends the `len` least significant bits of the value
+fn push_bits(bits: &mut BitVec<u8, Msb0>, value: u64, len: usize) {
+    bits.extend_from_bitslice(&value.view_bits::<Msb0>()[u64::BITS as usize - len..])
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransmissionStats {
+    pub packets_by_type: HashMap<Type, usize>,
+    pub max_depth: usize,
+    /// Number of bits used by the outermost packet, including all of its sub-packets.
+    pub packet_bits: usize,
+    pub padding_bits: usize,
+}
+
+impl TransmissionStats {
+    pub fn total_packets(&self) -> usize {
+        self.packets_by_type.values().sum()
+    }
+}
+
+impl Display for TransmissionStats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} packets nested up to {} levels deep",
+            self.total_packets(),
+            self.max_depth
+        )?;
+        for type_id in (0..1 << TYPE_ID_BITS).map(Type::from) {
+            let count = self
+                .packets_by_type
+                .get(&type_id)
+                .copied()
+                .unwrap_or_default();
+            writeln!(f, "{type_id:?}: {count}")?;
+        }
+        write!(
+            f,
+            "{} bits of packets followed by {} bits of padding",
+            self.packet_bits, self.padding_bits
+        )
+    }
+}
+
+/// Decodes the hexadecimal transmission alongside the statistics of its content.
+pub fn analyse_transmission(s: &str) -> Result<(Packet, TransmissionStats), DecodeError> {
+    let bits = decode_hex(s)?;
+    let (packet, packet_bits) = Packet::decode_with_length(&bits)?;
+    let stats = TransmissionStats {
+        packets_by_type: packet.type_counts(),
+        max_depth: packet.depth(),
+        packet_bits,
+        padding_bits: bits.len() - packet_bits,
+    };
+    Ok((packet, stats))
+}
+
+/// Decoded transmission alongside the statistics of its content.
+#[derive(Debug, Clone)]
+pub struct Transmission {
+    pub packet: Packet,
+    pub stats: TransmissionStats,
+}
+
+impl FromStr for Transmission {
+    type Err = DecodeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (packet, stats) = analyse_transmission(s)?;
+        Ok(Transmission { packet, stats })
+    }
+}
+
+/// Constructs valid packets, assigning them consecutive versions (wrapping around after 7)
+/// in the order they are built.
+#[derive(Debug, Default)]
+pub struct PacketBuilder {
+    next_version: u64,
+}
+
+impl PacketBuilder {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Starts assigning the versions from the specified one instead of 0.
+    pub fn starting_at(version: u64) -> Self {
+        PacketBuilder {
+            next_version: version % (1 << VERSION_BITS),
+        }
+    }
+
+    fn header(&mut self, type_id: Type) -> Header {
+        let version = self.next_version;
+        self.next_version = (self.next_version + 1) % (1 << VERSION_BITS);
+        Header { version, type_id }
+    }
+
+    fn variadic<I>(&mut self, type_id: Type, operands: I) -> Packet
+    where
+        I: IntoIterator<Item = Packet>,
+    {
+        let operands = operands.into_iter().collect::<Vec<_>>();
+        assert!(
+            !operands.is_empty(),
+            "{type_id:?} packet requires at least one operand"
+        );
+        Packet {
+            header: self.header(type_id),
+            content: Content::Operator(operands),
+        }
+    }
+
+    fn comparison(&mut self, type_id: Type, first: Packet, second: Packet) -> Packet {
+        Packet {
+            header: self.header(type_id),
+            content: Content::Operator(vec![first, second]),
+        }
+    }
+
+    pub fn literal(&mut self, val: u64) -> Packet {
+        Packet {
+            header: self.header(Type::Literal),
+            content: Content::Literal(val),
+        }
+    }
+
+    pub fn sum<I: IntoIterator<Item = Packet>>(&mut self, operands: I) -> Packet {
+        self.variadic(Type::Sum, operands)
+    }
+
+    pub fn product<I: IntoIterator<Item = Packet>>(&mut self, operands: I) -> Packet {
+        self.variadic(Type::Product, operands)
+    }
+
+    pub fn min<I: IntoIterator<Item = Packet>>(&mut self, operands: I) -> Packet {
+        self.variadic(Type::Min, operands)
+    }
+
+    pub fn max<I: IntoIterator<Item = Packet>>(&mut self, operands: I) -> Packet {
+        self.variadic(Type::Max, operands)
+    }
+
+    pub fn greater_than(&mut self, first: Packet, second: Packet) -> Packet {
+        self.comparison(Type::GreaterThan, first, second)
+    }
+
+    pub fn less_than(&mut self, first: Packet, second: Packet) -> Packet {
+        self.comparison(Type::LessThan, first, second)
+    }
+
+    pub fn equal_to(&mut self, first: Packet, second: Packet) -> Packet {
+        self.comparison(Type::Equal, first, second)
+    }
+}
+
+pub fn part1(packet: Packet) -> usize {
+    packet.version_sum()
+}
+
+pub fn part2(packet: Packet) -> u128 {
+    packet
+        .evaluate()
+        .expect("failed to evaluate the transmission")
+}
+
+/// Decodes the transmission and evaluates the packet it contains.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Day16;
+
+impl Solver for Day16 {
+    const DAY: u8 = 16;
+
+    type Input = Transmission;
+    type Output1 = usize;
+    type Output2 = u128;
+
+    fn parse(&self, input_file: &Path) -> io::Result<Self::Input> {
+        read_parsed(input_file)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Self::Output1 {
+        part1(input.packet.clone())
+    }
+
+    fn part2(&self, input: &Self::Input) -> Self::Output2 {
+        part2(input.packet.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn arb_packet() -> impl Strategy<Value = Packet> {
+        let literal = (0..8u64, any::<u64>()).prop_map(|(version, val)| Packet {
+            header: Header {
+                version,
+                type_id: Type::Literal,
+            },
+            content: Content::Literal(val),
+        });
+
+        literal.prop_recursive(4, 64, 5, |inner| {
+            let operators = prop_oneof![
+                Just(Type::Sum),
+                Just(Type::Product),
+                Just(Type::Min),
+                Just(Type::Max),
+                Just(Type::GreaterThan),
+                Just(Type::LessThan),
+                Just(Type::Equal),
+            ];
+            (0..8u64, operators, prop::collection::vec(inner, 1..5)).prop_map(
+                |(version, type_id, mut sub_packets)| {
+                    if type_id.is_comparison() {
+                        // comparisons always have exactly two operands
+                        let first = sub_packets[0].clone();
+                        sub_packets.resize(2, first);
+                    }
+                    Packet {
+                        header: Header { version, type_id },
+                        content: Content::Operator(sub_packets),
+                    }
+                },
+            )
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn encoding_round_trip(packet in arb_packet()) {
+            let hex = packet.to_hex().unwrap();
+            prop_assert_eq!(&packet, &hex.parse::<Packet>().unwrap());
+        }
+
+        #[test]
+        fn total_length_encoding_round_trip(packet in arb_packet()) {
+            let bits = packet.to_bits_with(LengthType::TotalLength).unwrap();
+            let (decoded, used_bits) = Packet::from_bits(&bits, 0).unwrap();
+            prop_assert_eq!(bits.len(), used_bits);
+            prop_assert_eq!(packet, decoded);
+        }
+    }
+
+    #[test]
+    fn literal_packet_encoding() {
+        let packet: Packet = "D2FE28".parse().unwrap();
+        assert_eq!("D2FE28", packet.to_hex().unwrap());
+
+        let mut invalid = packet.clone();
+        invalid.header.version = 8;
+        assert_eq!(Err(EncodeError::VersionOutOfRange(8)), invalid.to_hex());
+
+        invalid.header = Header {
+            version: 1,
+            type_id: Type::Sum,
+        };
+        assert_eq!(
+            Err(EncodeError::InconsistentContent(Type::Sum)),
+            invalid.to_hex()
+        );
+    }
+
+    #[test]
+    fn operator_packet_encoding() {
+        // the sample transmissions use both length types
+        let packet: Packet = "38006F45291200".parse().unwrap();
+        let mut bits = packet.to_bits_with(LengthType::TotalLength).unwrap();
+        assert_eq!(49, bits.len());
+        bits.resize(56, false);
+        assert_eq!("38006F45291200", hex::encode_upper(bits.into_vec()));
+
+        let packet: Packet = "EE00D40C823060".parse().unwrap();
+        assert_eq!("EE00D40C823060", packet.to_hex().unwrap());
+    }
+
+    #[test]
+    fn literal_packet_parsing() {
+        let packet = "D2FE28".parse().unwrap();
+        let expected = Packet {
+            header: Header {
+                version: 6,
+                type_id: Type::Literal,
+            },
+            content: Content::Literal(2021),
+        };
+
+        assert_eq!(expected, packet);
+    }
+
+    #[test]
+    fn operator_type0_packet_parsing() {
+        let packet = "38006F45291200".parse().unwrap();
+        let expected = Packet {
+            header: Header {
+                version: 1,
+                type_id: Type::LessThan,
+            },
+            content: Content::Operator(vec![
+                Packet {
+                    header: Header {
+                        version: 6,
+                        type_id: Type::Literal,
+                    },
+                    content: Content::Literal(10),
+                },
+                Packet {
+                    header: Header {
+                        version: 2,
+                        type_id: Type::Literal,
+                    },
+                    content: Content::Literal(20),
+                },
+            ]),
+        };
+
+        assert_eq!(expected, packet);
+    }
+
+    #[test]
+    fn operator_type1_packet_parsing() {
+        let packet = "EE00D40C823060".parse().unwrap();
+        let expected = Packet {
+            header: Header {
+                version: 7,
+                type_id: Type::Max,
+            },
+            content: Content::Operator(vec![
+                Packet {
+                    header: Header {
+                        version: 2,
+                        type_id: Type::Literal,
+                    },
+                    content: Content::Literal(1),
+                },
+                Packet {
+                    header: Header {
+                        version: 4,
+                        type_id: Type::Literal,
+                    },
+                    content: Content::Literal(2),
+                },
+                Packet {
+                    header: Header {
+                        version: 1,
+                        type_id: Type::Literal,
+                    },
+                    content: Content::Literal(3),
+                },
+            ]),
+        };
+
+        assert_eq!(expected, packet);
+    }
+
+    #[test]
+    fn expression_rendering() {
+        assert_eq!("2021", "D2FE28".parse::<Packet>().unwrap().to_string());
+        assert_eq!(
+            "10 < 20",
+            "38006F45291200".parse::<Packet>().unwrap().to_string()
+        );
+        assert_eq!(
+            "max(1, 2, 3)",
+            "EE00D40C823060".parse::<Packet>().unwrap().to_string()
+        );
+        assert_eq!(
+            "sum(1, 3) == product(2, 2)",
+            "9C0141080250320F1802104A08"
+                .parse::<Packet>()
+                .unwrap()
+                .to_string()
+        );
+
+        let nested = Packet {
+            header: Header {
+                version: 0,
+                type_id: Type::Equal,
+            },
+            content: Content::Operator(vec![
+                "38006F45291200".parse().unwrap(),
+                "D2FE28".parse().unwrap(),
+            ]),
+        };
+        assert_eq!("(10 < 20) == 2021", nested.to_string());
+    }
+
+    #[test]
+    fn tree_rendering() {
+        assert_eq!(
+            "2021 (v6)",
+            format!("{:#}", "D2FE28".parse::<Packet>().unwrap())
+        );
+
+        let packet: Packet = "9C0141080250320F1802104A08".parse().unwrap();
+        let expected = "\
+== (v4)
+  sum (v2)
+    1 (v2)
+    3 (v4)
+  product (v6)
+    2 (v0)
+    2 (v2)";
+        assert_eq!(expected, format!("{packet:#}"));
+    }
+
+    #[test]
+    fn malformed_transmissions() {
+        let err = |offset, kind| Err(DecodeError { offset, kind });
+
+        assert_eq!(
+            err(16, DecodeErrorKind::InvalidHex),
+            "D2FEX8".parse::<Packet>()
+        );
+        assert_eq!(
+            err(20, DecodeErrorKind::InvalidHex),
+            "D2FE2".parse::<Packet>()
+        );
+        // the literal is cut short in the middle of its second group
+        assert_eq!(
+            err(16, DecodeErrorKind::TruncatedPacket),
+            "D2FE".parse::<Packet>()
+        );
+        // the operator expects more sub-packets than there are
+        assert_eq!(
+            err(56, DecodeErrorKind::TruncatedPacket),
+            "EE00D40C823060"
+                .replace("EE00D4", "EE01D4")
+                .parse::<Packet>()
+        );
+        // the second sub-packet does not fit in the length specified by the operator
+        assert_eq!(
+            err(48, DecodeErrorKind::LengthMismatch),
+            "38006B45291200".parse::<Packet>()
+        );
+        // 17 groups of 4 bits
+        let oversized = Packet {
+            header: Header {
+                version: 0,
+                type_id: Type::Literal,
+            },
+            content: Content::Literal(0),
+        };
+        let mut bits = oversized.to_bits().unwrap();
+        bits.truncate(Header::LEN);
+        for _ in 0..16 {
+            bits.extend_from_bitslice(bits![u8, Msb0; 1, 1, 1, 1, 1]);
+        }
+        bits.extend_from_bitslice(bits![u8, Msb0; 0, 1, 1, 1, 1]);
+        assert_eq!(
+            err(Header::LEN, DecodeErrorKind::OversizedLiteral),
+            Packet::decode_transmission(&bits)
+        );
+        assert_eq!(
+            err(24, DecodeErrorKind::TrailingGarbage),
+            "D2FE28FF".parse::<Packet>()
+        );
+    }
+
+    fn literal(val: u64) -> Packet {
+        Packet {
+            header: Header {
+                version: 0,
+                type_id: Type::Literal,
+            },
+            content: Content::Literal(val),
+        }
+    }
+
+    fn operator(type_id: Type, operands: Vec<Packet>) -> Packet {
+        Packet {
+            header: Header {
+                version: 0,
+                type_id,
+            },
+            content: Content::Operator(operands),
+        }
+    }
+
+    #[test]
+    fn checked_evaluation() {
+        // exceeds u64, but still fits in u128
+        let product = operator(Type::Product, vec![literal(u64::MAX), literal(u64::MAX)]);
+        assert_eq!(Ok(u64::MAX as u128 * u64::MAX as u128), product.evaluate());
+
+        let overflowing = operator(Type::Product, vec![product.clone(), literal(2)]);
+        assert_eq!(Err(EvaluationError::Overflow), overflowing.evaluate());
+
+        let comparison = operator(Type::LessThan, vec![literal(1), literal(2), literal(3)]);
+        assert_eq!(
+            Err(EvaluationError::InvalidOperandCount {
+                type_id: Type::LessThan,
+                operands: 3
+            }),
+            comparison.evaluate()
+        );
+        assert!(operator(Type::Max, vec![]).evaluate().is_err());
+    }
+
+    #[cfg(feature = "bigint")]
+    #[test]
+    fn arbitrary_precision_evaluation() {
+        use num_bigint::BigUint;
+
+        let square = operator(Type::Product, vec![literal(u64::MAX), literal(u64::MAX)]);
+        let overflowing = operator(Type::Product, vec![square.clone(), square]);
+        let expected = BigUint::from(u64::MAX).pow(4);
+        assert_eq!(Ok(expected), overflowing.evaluate_as::<BigUint>());
+    }
+
+    #[test]
+    fn building_packets() {
+        let mut builder = PacketBuilder::new();
+        let operands = (1..=3).map(|val| builder.literal(val)).collect::<Vec<_>>();
+        let max = builder.max(operands);
+        let sum = [4, 5].map(|val| builder.literal(val));
+        let sum = builder.sum(sum);
+        let packet = builder.less_than(max, sum);
+
+        assert_eq!("max(1, 2, 3) < sum(4, 5)", packet.to_string());
+        assert_eq!(Ok(1), packet.evaluate());
+        // versions 0 through 7 followed by 0 again
+        assert_eq!(28, packet.version_sum());
+        assert_eq!(packet, packet.to_hex().unwrap().parse::<Packet>().unwrap());
+
+        let mut builder = PacketBuilder::starting_at(7);
+        assert_eq!(7, builder.literal(1).header.version);
+        assert_eq!(0, builder.literal(2).header.version);
+    }
+
+    #[test]
+    fn transmission_statistics() {
+        let (_, stats) = analyse_transmission("38006F45291200").unwrap();
+        assert_eq!(3, stats.total_packets());
+        assert_eq!(Some(&2), stats.packets_by_type.get(&Type::Literal));
+        assert_eq!(Some(&1), stats.packets_by_type.get(&Type::LessThan));
+        assert_eq!(2, stats.max_depth);
+        assert_eq!(49, stats.packet_bits);
+        assert_eq!(7, stats.padding_bits);
+
+        // operator packet (version 4) which contains an operator packet (version 1) which
+        // contains an operator packet (version 5) which contains a literal value (version 6)
+        let (_, stats) = analyse_transmission("8A004A801A8002F478").unwrap();
+        assert_eq!(4, stats.total_packets());
+        assert_eq!(4, stats.max_depth);
+        assert_eq!(69, stats.packet_bits);
+        assert_eq!(3, stats.padding_bits);
+        let transmission: Transmission = "8A004A801A8002F478".parse().unwrap();
+        assert_eq!(stats, transmission.stats);
+        assert_eq!(16, transmission.packet.version_sum());
+    }
+
+    #[test]
+    fn part1_sample_input_1() {
+        let packet = "8A004A801A8002F478".parse().unwrap();
+        let expected = 16;
+
+        assert_eq!(expected, part1(packet));
+    }
+
+    #[test]
+    fn part1_sample_input_2() {
+        let packet = "620080001611562C8802118E34".parse().unwrap();
+        let expected = 12;
+
+        assert_eq!(expected, part1(packet));
+    }
+
+    #[test]
+    fn part1_sample_input_3() {
+        let packet = "C0015000016115A2E0802F182340".parse().unwrap();
+        let expected = 23;
+
+        assert_eq!(expected, part1(packet));
+    }
+
+    #[test]
+    fn part1_sample_input_4() {
+        let packet = "A0016C880162017C3686B18A3D4780".parse().unwrap();
+        let expected = 31;
+
+        assert_eq!(expected, part1(packet));
+    }
+
+    #[test]
+    fn part2_sample_input_1() {
+        let packet = "C200B40A82".parse().unwrap();
+        let expected = 3;
+
+        assert_eq!(expected, part2(packet));
+    }
+
+    #[test]
+    fn part2_sample_input_2() {
+        let packet = "04005AC33890".parse().unwrap();
+        let expected = 54;
+
+        assert_eq!(expected, part2(packet));
+    }
+
+    #[test]
+    fn part2_sample_input_3() {
+        let packet = "880086C3E88112".parse().unwrap();
+        let expected = 7;
+
+        assert_eq!(expected, part2(packet));
+    }
+
+    #[test]
+    fn part2_sample_input_4() {
+        let packet = "CE00C43D881120".parse().unwrap();
+        let expected = 9;
+
+        assert_eq!(expected, part2(packet));
+    }
+
+    #[test]
+    fn part2_sample_input_5() {
+        let packet = "D8005AC2A8F0".parse().unwrap();
+        let expected = 1;
+
+        assert_eq!(expected, part2(packet));
+    }
+
+    #[test]
+    fn part2_sample_input_6() {
+        let packet = "F600BC2D8F".parse().unwrap();
+        let expected = 0;
+
+        assert_eq!(expected, part2(packet));
+    }
+
+    #[test]
+    fn part2_sample_input_7() {
+        let packet = "9C005AC2F8F0".parse().unwrap();
+        let expected = 0;
+
+        assert_eq!(expected, part2(packet));
+    }
+
+    #[test]
+    fn part2_sample_input_8() {
+        let packet = "9C0141080250320F1802104A08".parse().unwrap();
+        let expected = 1;
+
+        assert_eq!(expected, part2(packet));
+    }
+}
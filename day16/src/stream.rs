@@ -0,0 +1,338 @@
+// Copyright 2022 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    Content, DecodeError, DecodeErrorKind, Header, Packet, Type, LITERAL_GROUP_BITS,
+    SUB_PACKET_COUNT_BITS, TOTAL_LENGTH_BITS, TYPE_ID_BITS, VERSION_BITS,
+};
+use bitvec::prelude::*;
+use std::io::{self, BufRead, Bytes};
+use std::iter::Peekable;
+
+/// Reads the bits, most significant first, out of the underlying bytes only as they are needed.
+pub struct BitReader<I: Iterator<Item = io::Result<u8>>> {
+    bytes: Peekable<I>,
+    current: u8,
+    // number of bits of the current byte that have not been read yet
+    remaining: u32,
+    consumed: usize,
+}
+
+impl<I: Iterator<Item = io::Result<u8>>> BitReader<I> {
+    pub fn new(bytes: I) -> Self {
+        BitReader {
+            bytes: bytes.peekable(),
+            current: 0,
+            remaining: 0,
+            consumed: 0,
+        }
+    }
+
+    /// Total number of bits read so far.
+    pub fn consumed(&self) -> usize {
+        self.consumed
+    }
+
+    pub fn read_bit(&mut self) -> io::Result<bool> {
+        if self.remaining == 0 {
+            self.current = self.bytes.next().unwrap_or_else(|| {
+                Err(decode_error(
+                    self.consumed,
+                    DecodeErrorKind::TruncatedPacket,
+                ))
+            })?;
+            self.remaining = u8::BITS;
+        }
+        self.remaining -= 1;
+        self.consumed += 1;
+        Ok((self.current >> self.remaining) & 1 == 1)
+    }
+
+    /// Reads the specified number of bits, up to 64, as a big-endian number.
+    pub fn read_bits(&mut self, len: usize) -> io::Result<u64> {
+        debug_assert!(len <= u64::BITS as usize);
+        let mut value = 0;
+        for _ in 0..len {
+            value = (value << 1) | u64::from(self.read_bit()?);
+        }
+        Ok(value)
+    }
+
+    /// Discards the rest of the current byte.
+    pub fn align_to_byte(&mut self) {
+        self.consumed += self.remaining as usize;
+        self.remaining = 0;
+    }
+
+    fn is_exhausted(&mut self) -> bool {
+        self.remaining == 0 && self.bytes.peek().is_none()
+    }
+
+    pub fn read_packet(&mut self) -> io::Result<Packet> {
+        let version = self.read_bits(VERSION_BITS)?;
+        let type_id = Type::from(self.read_bits(TYPE_ID_BITS)?);
+        let header = Header { version, type_id };
+
+        let content = if type_id.is_literal() {
+            self.read_literal()?
+        } else if self.read_bit()? {
+            let count = self.read_bits(SUB_PACKET_COUNT_BITS)?;
+            let sub_packets = (0..count)
+                .map(|_| self.read_packet())
+                .collect::<io::Result<_>>()?;
+            Content::Operator(sub_packets)
+        } else {
+            let len = self.read_bits(TOTAL_LENGTH_BITS)? as usize;
+            let end = self.consumed + len;
+            let mut sub_packets = Vec::new();
+            while self.consumed < end {
+                sub_packets.push(self.read_packet()?);
+            }
+            if self.consumed != end {
+                return Err(decode_error(end, DecodeErrorKind::LengthMismatch));
+            }
+            Content::Operator(sub_packets)
+        };
+
+        Ok(Packet { header, content })
+    }
+
+    fn read_literal(&mut self) -> io::Result<Content> {
+        let start = self.consumed;
+        let mut value = 0u64;
+        loop {
+            let has_more = self.read_bit()?;
+            if value.leading_zeros() < LITERAL_GROUP_BITS as u32 {
+                return Err(decode_error(start, DecodeErrorKind::OversizedLiteral));
+            }
+            value = (value << LITERAL_GROUP_BITS) | self.read_bits(LITERAL_GROUP_BITS)?;
+            if !has_more {
+                return Ok(Content::Literal(value));
+            }
+        }
+    }
+}
+
+// wraps the decoding failure so that it could be told apart from the failures of the reader
+fn decode_error(offset: usize, kind: DecodeErrorKind) -> io::Error {
+    let io_kind = match kind {
+        DecodeErrorKind::TruncatedPacket => io::ErrorKind::UnexpectedEof,
+        _ => io::ErrorKind::InvalidData,
+    };
+    io::Error::new(io_kind, DecodeError::new(offset, kind))
+}
+
+/// Converts hexadecimal text into the bytes it represents, ignoring any whitespace.
+pub struct HexBytes<R: BufRead> {
+    chars: Bytes<R>,
+    digits: usize,
+}
+
+impl<R: BufRead> HexBytes<R> {
+    pub fn new(reader: R) -> Self {
+        HexBytes {
+            chars: reader.bytes(),
+            digits: 0,
+        }
+    }
+
+    fn next_digit(&mut self) -> Option<io::Result<u8>> {
+        loop {
+            let char = match self.chars.next()? {
+                Ok(char) => char,
+                Err(err) => return Some(Err(err)),
+            };
+            if char.is_ascii_whitespace() {
+                continue;
+            }
+            let offset = self.digits * 4;
+            self.digits += 1;
+            return Some(
+                (char as char)
+                    .to_digit(16)
+                    .map(|digit| digit as u8)
+                    .ok_or_else(|| decode_error(offset, DecodeErrorKind::InvalidHex)),
+            );
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for HexBytes<R> {
+    type Item = io::Result<u8>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let high = match self.next_digit()? {
+            Ok(digit) => digit,
+            Err(err) => return Some(Err(err)),
+        };
+        // a dangling hex digit is treated as if it was followed by the zero padding
+        let low = self.next_digit().unwrap_or(Ok(0));
+        Some(low.map(|low| (high << 4) | low))
+    }
+}
+
+/// Decodes consecutive packets out of the stream, each padded to the full byte,
+/// without ever holding more than a single packet in memory.
+pub struct PacketStream<I: Iterator<Item = io::Result<u8>>> {
+    reader: BitReader<I>,
+    failed: bool,
+}
+
+impl<I: Iterator<Item = io::Result<u8>>> PacketStream<I> {
+    pub fn new(bytes: I) -> Self {
+        PacketStream {
+            reader: BitReader::new(bytes),
+            failed: false,
+        }
+    }
+}
+
+impl<R: BufRead> PacketStream<Bytes<R>> {
+    /// Decodes the packets out of the raw binary transmission.
+    pub fn from_reader(reader: R) -> Self {
+        PacketStream::new(reader.bytes())
+    }
+}
+
+impl<R: BufRead> PacketStream<HexBytes<R>> {
+    /// Decodes the packets out of the hexadecimal transmission, such as the puzzle input.
+    pub fn from_hex_reader(reader: R) -> Self {
+        PacketStream::new(HexBytes::new(reader))
+    }
+}
+
+impl<I: Iterator<Item = io::Result<u8>>> Iterator for PacketStream<I> {
+    type Item = io::Result<Packet>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed || self.reader.is_exhausted() {
+            return None;
+        }
+
+        let packet = self.reader.read_packet();
+        self.reader.align_to_byte();
+        // after an error there's no telling where the next packet starts
+        self.failed = packet.is_err();
+        Some(packet)
+    }
+}
+
+/// Decodes consecutive packets out of the already decoded bits, each padded to the full byte,
+/// with the same semantics as the [`PacketStream`].
+pub struct PacketReader<'a> {
+    bits: &'a BitSlice<u8, Msb0>,
+    position: usize,
+    failed: bool,
+}
+
+impl<'a> PacketReader<'a> {
+    pub fn new(bits: &'a BitSlice<u8, Msb0>) -> Self {
+        PacketReader {
+            bits,
+            position: 0,
+            failed: false,
+        }
+    }
+
+    /// Number of bits, including the padding, taken up by the packets decoded so far.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+}
+
+impl Iterator for PacketReader<'_> {
+    type Item = Result<Packet, DecodeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed || self.position >= self.bits.len() {
+            return None;
+        }
+
+        let packet = Packet::from_bits(&self.bits[self.position..], self.position);
+        match &packet {
+            Ok((_, used_bits)) => {
+                let end = self.position + used_bits;
+                self.position = end.next_multiple_of(u8::BITS as usize);
+            }
+            // after an error there's no telling where the next packet starts
+            Err(_) => self.failed = true,
+        }
+        Some(packet.map(|(packet, _)| packet))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn streaming_consecutive_packets() {
+        let transmission = "D2FE28\n38006F45291200\nEE00D40C823060\n";
+        let packets = PacketStream::from_hex_reader(transmission.as_bytes())
+            .collect::<io::Result<Vec<_>>>()
+            .unwrap();
+
+        let expected = ["D2FE28", "38006F45291200", "EE00D40C823060"]
+            .iter()
+            .map(|hex| hex.parse::<Packet>().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(expected, packets);
+    }
+
+    #[test]
+    fn streaming_binary_transmission() {
+        let bytes = hex::decode("8A004A801A8002F478").unwrap();
+        let mut stream = PacketStream::from_reader(bytes.as_slice());
+        assert_eq!(16, stream.next().unwrap().unwrap().version_sum());
+        assert!(stream.next().is_none());
+    }
+
+    #[test]
+    fn reading_packets_out_of_bits() {
+        let bits = BitVec::<u8, Msb0>::from_vec(hex::decode("D2FE2838006F45291200").unwrap());
+        let mut reader = PacketReader::new(&bits);
+
+        assert_eq!(
+            "D2FE28".parse::<Packet>().unwrap(),
+            reader.next().unwrap().unwrap()
+        );
+        assert_eq!(24, reader.position());
+        assert_eq!(
+            "38006F45291200".parse::<Packet>().unwrap(),
+            reader.next().unwrap().unwrap()
+        );
+        assert_eq!(bits.len(), reader.position());
+        assert!(reader.next().is_none());
+
+        let truncated = BitVec::<u8, Msb0>::from_vec(hex::decode("D2FE2838006F4529").unwrap());
+        let packets = PacketReader::new(&truncated).collect::<Vec<_>>();
+        assert_eq!(2, packets.len());
+        assert!(packets[0].is_ok());
+        assert_eq!(
+            DecodeError::new(64, DecodeErrorKind::TruncatedPacket),
+            packets[1].clone().unwrap_err()
+        );
+    }
+
+    #[test]
+    fn streaming_truncated_transmission() {
+        let mut stream = PacketStream::from_hex_reader("38006F4529".as_bytes());
+        let err = stream.next().unwrap().unwrap_err();
+        assert_eq!(io::ErrorKind::UnexpectedEof, err.kind());
+        let decode_err = err.into_inner().unwrap().downcast::<DecodeError>().unwrap();
+        assert_eq!(DecodeErrorKind::TruncatedPacket, decode_err.kind);
+        assert_eq!(40, decode_err.offset);
+        assert!(stream.next().is_none());
+    }
+}
@@ -0,0 +1,451 @@
+// Copyright 2021 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cmp::max;
+use std::fmt::{self, Display, Formatter};
+use std::io;
+use std::ops::RangeInclusive;
+use std::path::Path;
+use std::str::FromStr;
+use utils::geometry::Vec2;
+use utils::input_read::read_parsed;
+use utils::parsing::parse_raw_range;
+use utils::render::FrameSink;
+use utils::{Solver, Visualize};
+
+/// Target area, as found in the input, that isn't in the form of `target area: x=20..30, y=-10..-5`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MalformedTarget(pub String);
+
+impl Display for MalformedTarget {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "'{}' is not a valid target area", self.0)
+    }
+}
+
+impl std::error::Error for MalformedTarget {}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Algorithm {
+    /// Derives the steps at which each velocity component is within the target.
+    #[default]
+    Analytic,
+    /// Simulates every plausible velocity.
+    BruteForce,
+}
+
+impl FromStr for Algorithm {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "analytic" => Ok(Algorithm::Analytic),
+            "brute-force" => Ok(Algorithm::BruteForce),
+            other => Err(format!("{other} is not a valid algorithm")),
+        }
+    }
+}
+
+/// Inclusive range of steps, where `None` as the last step means the range never ends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Steps {
+    first: isize,
+    last: Option<isize>,
+}
+
+impl Steps {
+    fn new(first: isize, last: Option<isize>) -> Option<Self> {
+        match last {
+            Some(last) if last < first => None,
+            _ => Some(Steps { first, last }),
+        }
+    }
+
+    fn overlaps(&self, other: &Steps) -> bool {
+        self.last.is_none_or(|last| other.first <= last)
+            && other.last.is_none_or(|last| self.first <= last)
+    }
+}
+
+// `floor((b + sqrt(d)) / 2)` and friends, computed exactly for non-negative `d`
+fn floor_half_sum(b: isize, d: isize) -> isize {
+    (b + d.isqrt()).div_euclid(2)
+}
+
+fn ceil_half_sum(b: isize, d: isize) -> isize {
+    let ceil_sqrt = d.isqrt() + isize::from(d.isqrt().pow(2) != d);
+    (b + ceil_sqrt + 1).div_euclid(2)
+}
+
+fn floor_half_difference(b: isize, d: isize) -> isize {
+    let ceil_sqrt = d.isqrt() + isize::from(d.isqrt().pow(2) != d);
+    (b - ceil_sqrt).div_euclid(2)
+}
+
+fn ceil_half_difference(b: isize, d: isize) -> isize {
+    (b - d.isqrt() + 1).div_euclid(2)
+}
+
+#[derive(Debug, Clone)]
+pub struct Target {
+    x_range: RangeInclusive<isize>,
+    y_range: RangeInclusive<isize>,
+}
+
+impl FromStr for Target {
+    type Err = MalformedTarget;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let malformed = || MalformedTarget(s.trim().to_string());
+        let stripped = s
+            .trim()
+            .strip_prefix("target area: ")
+            .ok_or_else(malformed)?;
+        let (x_range, y_range) = stripped.split_once(", ").ok_or_else(malformed)?;
+
+        let x_range = parse_raw_range(x_range).map_err(|_| malformed())?;
+        let y_range = parse_raw_range(y_range).map_err(|_| malformed())?;
+
+        Ok(Target { x_range, y_range })
+    }
+}
+
+impl Target {
+    fn maximise_altitude(&self) -> usize {
+        // only consider y acceleration, since probe's y position is independent of the x position
+        // and we know there must exist *some* x acceleration for which this will work, otherwise
+        // this task would have no solution
+
+        // also note that since we're launching upwards, we will have to reach y = 0 again
+        // and we're going to have Vy = -Vy_0 at that point
+        // now, to maximise the altitude, we must maximise our launch velocity and therefore
+        // also speed at which we cross y = 0
+        // So to maintain the highest possible speed, we must therefore reach the bottom of the target
+        // in a single step after reaching y = 0
+        // so we must cross y = 0 at min y_pos of target + 1 (so that we would not miss it)
+
+        // also:
+        // y = Vy_0 * t - 1/2 t^2 + 1/2 t
+        // y' = Vy_0 + 1/2 - t; y' = 0 <=> t = Vy0 + 1/2, so probe will reach its max attitude at t = Vy0 + 1/2
+        // therefore we have to consider t = Vy0 and t = Vy0 + 1
+
+        let vy_0 = (*self.y_range.start() + 1).unsigned_abs();
+        let y = |t: usize| vy_0 * t - t * t / 2 + t / 2;
+
+        let t1 = vy_0;
+        let t2 = vy_0 + 1;
+
+        let y1 = y(t1);
+        let y2 = y(t2);
+
+        max(y1, y2)
+    }
+
+    // the analytic approach assumes the (usual) target located to the right of and below the launcher
+    fn is_below_right(&self) -> bool {
+        *self.x_range.start() > 0 && *self.y_range.end() < 0
+    }
+
+    // after `t` steps, the probe is at x = vx * t - t * (t - 1) / 2 (for t <= vx, at which point it
+    // stops moving horizontally), i.e. it reaches the position `x` at t = (b -/+ sqrt(b^2 - 8x)) / 2,
+    // where b = 2vx + 1
+    fn horizontal_steps(&self, vx: isize) -> Option<Steps> {
+        let b = 2 * vx + 1;
+        let min_discriminant = b * b - 8 * self.x_range.start();
+        if min_discriminant < 0 {
+            // the probe stops before reaching the target
+            return None;
+        }
+        let first = ceil_half_difference(b, min_discriminant);
+
+        let resting_position = vx * (vx + 1) / 2;
+        let last = if resting_position <= *self.x_range.end() {
+            None
+        } else {
+            Some(floor_half_difference(b, b * b - 8 * self.x_range.end()))
+        };
+
+        Steps::new(first, last)
+    }
+
+    // similarly, the probe is at y = vy * t - t * (t - 1) / 2, but since the target is below
+    // the launcher, only the latter root, (b + sqrt(b^2 - 8y)) / 2, is of interest
+    fn vertical_steps(&self, vy: isize) -> Option<Steps> {
+        let b = 2 * vy + 1;
+        let first = ceil_half_sum(b, b * b - 8 * self.y_range.end());
+        let last = floor_half_sum(b, b * b - 8 * self.y_range.start());
+
+        Steps::new(first, Some(last))
+    }
+
+    /// All initial velocities for which the probe is within the target after some step.
+    pub fn valid_velocities(&self, algorithm: Algorithm) -> Vec<Velocity> {
+        match algorithm {
+            Algorithm::Analytic if self.is_below_right() => self.valid_velocities_analytically(),
+            _ => self.valid_velocities_brute_force(),
+        }
+    }
+
+    fn valid_velocities_analytically(&self) -> Vec<Velocity> {
+        // anything faster than that would overshoot the target in the very first step
+        let horizontal = (1..=*self.x_range.end())
+            .filter_map(|vx| self.horizontal_steps(vx).map(|steps| (vx, steps)))
+            .collect::<Vec<_>>();
+        // when launched upwards with vy, the probe comes back to y = 0 with -vy - 1 velocity
+        let lowest = *self.y_range.start();
+        let vertical = (lowest..-lowest)
+            .filter_map(|vy| self.vertical_steps(vy).map(|steps| (vy, steps)))
+            .collect::<Vec<_>>();
+
+        horizontal
+            .iter()
+            .flat_map(|(dx, x_steps)| {
+                vertical
+                    .iter()
+                    .filter(|(_, y_steps)| x_steps.overlaps(y_steps))
+                    .map(|&(dy, _)| Velocity { dx: *dx, dy })
+            })
+            .collect()
+    }
+
+    fn valid_velocities_brute_force(&self) -> Vec<Velocity> {
+        // unfortunately I'm running out of time now, so we're left to bruteforcing here : (
+        let mut valid_velocities = Vec::new();
+        for dx in 0..*self.x_range.end() * 2 {
+            for dy in *self.y_range.start()..self.y_range.start().abs() {
+                let velocity = Velocity { dx, dy };
+                if self.trajectory(velocity).hit {
+                    valid_velocities.push(velocity)
+                }
+            }
+        }
+
+        valid_velocities
+    }
+
+    /// Simulates the probe launched with the specified velocity until it either reaches
+    /// the target or it's guaranteed to miss it.
+    pub fn trajectory(&self, velocity: Velocity) -> Trajectory {
+        let mut v = velocity;
+        let mut probe = Vec2::origin();
+        let mut positions = vec![probe];
+        loop {
+            if self.x_range.contains(&probe.x) && self.y_range.contains(&probe.y) {
+                return Trajectory {
+                    positions,
+                    hit: true,
+                };
+            }
+            if probe.x > *self.x_range.end() || probe.y < *self.y_range.start() {
+                return Trajectory {
+                    positions,
+                    hit: false,
+                };
+            }
+
+            probe += v.displacement();
+            v.step();
+            positions.push(probe);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Velocity {
+    pub dx: isize,
+    pub dy: isize,
+}
+
+impl Velocity {
+    #[allow(clippy::comparison_chain)]
+    fn step(&mut self) {
+        self.dy -= 1;
+
+        if self.dx > 0 {
+            self.dx -= 1
+        } else if self.dx < 0 {
+            self.dx += 1
+        }
+    }
+
+    fn displacement(&self) -> Vec2 {
+        Vec2::new(self.dx, self.dy)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trajectory {
+    /// Consecutive positions of the probe, starting from the launcher at `(0, 0)`.
+    pub positions: Vec<Vec2>,
+    pub hit: bool,
+}
+
+impl Trajectory {
+    /// Number of steps performed by the probe.
+    pub fn steps(&self) -> usize {
+        self.positions.len() - 1
+    }
+}
+
+pub fn part1(target: Target) -> usize {
+    target.maximise_altitude()
+}
+
+pub fn part2(target: Target, algorithm: Algorithm) -> usize {
+    target.valid_velocities(algorithm).len()
+}
+
+/// Finds the initial velocities of the probe, using the chosen algorithm for the second part.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Day17 {
+    pub algorithm: Algorithm,
+}
+
+impl Solver for Day17 {
+    const DAY: u8 = 17;
+
+    type Input = Target;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(&self, input_file: &Path) -> io::Result<Self::Input> {
+        read_parsed(input_file)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Self::Output1 {
+        part1(input.clone())
+    }
+
+    fn part2(&self, input: &Self::Input) -> Self::Output2 {
+        part2(input.clone(), self.algorithm)
+    }
+}
+
+impl Visualize for Day17 {
+    // the target area alongside the trajectory of the highest launch reaching it,
+    // flipped so that the probe ascends towards the top of the frame
+    fn visualize(&self, input: &Self::Input, sink: &mut dyn FrameSink) -> io::Result<()> {
+        let highest = input
+            .valid_velocities(self.algorithm)
+            .into_iter()
+            .max_by_key(|velocity| velocity.dy);
+        let trajectory = highest
+            .map(|velocity| input.trajectory(velocity).positions)
+            .unwrap_or_else(|| vec![Vec2::origin()]);
+        let target = input
+            .x_range
+            .clone()
+            .flat_map(|x| input.y_range.clone().map(move |y| Vec2::new(x, y)));
+        let points = target.chain(trajectory).collect::<Vec<_>>();
+
+        // the launcher is always part of the trajectory, so there's at least a single point
+        let min_x = points.iter().map(|point| point.x).min().unwrap_or_default();
+        let max_x = points.iter().map(|point| point.x).max().unwrap_or_default();
+        let min_y = points.iter().map(|point| point.y).min().unwrap_or_default();
+        let max_y = points.iter().map(|point| point.y).max().unwrap_or_default();
+
+        let size = (
+            (max_x - min_x).unsigned_abs() + 1,
+            (max_y - min_y).unsigned_abs() + 1,
+        );
+        let lit = points
+            .iter()
+            .map(|point| {
+                (
+                    (point.x - min_x).unsigned_abs(),
+                    (max_y - point.y).unsigned_abs(),
+                )
+            })
+            .collect::<Vec<_>>();
+        sink.push_frame(size, &lit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utils::render::FrameBuffer;
+
+    #[test]
+    fn part1_sample_input() {
+        let target = "target area: x=20..30, y=-10..-5".parse().unwrap();
+
+        let expected = 45;
+        assert_eq!(expected, part1(target))
+    }
+
+    #[test]
+    fn part2_sample_input() {
+        let target: Target = "target area: x=20..30, y=-10..-5".parse().unwrap();
+
+        let expected = 112;
+        assert_eq!(expected, part2(target.clone(), Algorithm::Analytic));
+        assert_eq!(expected, part2(target, Algorithm::BruteForce))
+    }
+
+    #[test]
+    fn valid_velocities_and_trajectories() {
+        let target: Target = "target area: x=20..30, y=-10..-5".parse().unwrap();
+        let velocities = target.valid_velocities(Algorithm::Analytic);
+        assert!(velocities.contains(&Velocity { dx: 6, dy: 9 }));
+        assert!(velocities.contains(&Velocity { dx: 30, dy: -10 }));
+        assert!(!velocities.contains(&Velocity { dx: 17, dy: -4 }));
+
+        let trajectory = target.trajectory(Velocity { dx: 7, dy: 2 });
+        assert!(trajectory.hit);
+        assert_eq!(7, trajectory.steps());
+        assert_eq!(Some(&Vec2::new(28, -7)), trajectory.positions.last());
+
+        let trajectory = target.trajectory(Velocity { dx: 17, dy: -4 });
+        assert!(!trajectory.hit);
+        assert_eq!(
+            vec![Vec2::origin(), Vec2::new(17, -4), Vec2::new(33, -9)],
+            trajectory.positions
+        );
+    }
+
+    #[test]
+    fn visualizing_trajectory() {
+        let target: Target = "target area: x=20..30, y=-10..-5".parse().unwrap();
+
+        let mut frames = FrameBuffer::default();
+        Day17::default().visualize(&target, &mut frames).unwrap();
+        assert_eq!(1, frames.frames.len());
+        let (size, lit) = &frames.frames[0];
+        assert_eq!((31, 56), *size);
+        // the launcher and the corners of the target, with the peak of the trajectory at the top
+        for pixel in [(0, 45), (20, 50), (30, 55)] {
+            assert!(lit.contains(&pixel));
+        }
+        assert!(lit.iter().any(|&(_, y)| y == 0));
+    }
+
+    #[test]
+    fn analytic_matches_brute_force() {
+        for target in [
+            "target area: x=20..30, y=-10..-5",
+            "target area: x=169..206, y=-108..-68",
+            "target area: x=1..1, y=-1..-1",
+            "target area: x=6..6, y=-3..-1",
+            "target area: x=10..12, y=-20..-19",
+        ] {
+            let target: Target = target.parse().unwrap();
+            let mut brute_force = target.valid_velocities(Algorithm::BruteForce);
+            let mut analytic = target.valid_velocities(Algorithm::Analytic);
+            brute_force.sort_by_key(|v| (v.dx, v.dy));
+            analytic.sort_by_key(|v| (v.dx, v.dy));
+            assert_eq!(brute_force, analytic);
+        }
+    }
+}
@@ -0,0 +1,67 @@
+// Copyright 2022 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use day18::{part2_using, Algorithm, NumberTree};
+use utils::input_read::read_parsed_line_input;
+use utils::parallel::with_threads;
+
+const SYNTHETIC_NUMBERS: usize = 500;
+
+fn part2_algorithms(c: &mut Criterion) {
+    let numbers: Vec<NumberTree> =
+        read_parsed_line_input(concat!(env!("CARGO_MANIFEST_DIR"), "/input"))
+            .expect("failed to read input file");
+
+    let mut group = c.benchmark_group("part2");
+    group.sample_size(10);
+    group.bench_function("sequential", |b| {
+        b.iter(|| part2_using(black_box(&numbers), Algorithm::Sequential))
+    });
+    group.bench_function("parallel", |b| {
+        b.iter(|| part2_using(black_box(&numbers), Algorithm::Parallel))
+    });
+    for threads in [1, 2, 4] {
+        group.bench_function(format!("parallel-{threads}-threads"), |b| {
+            b.iter(|| {
+                with_threads(threads, || {
+                    part2_using(black_box(&numbers), Algorithm::Parallel)
+                })
+                .expect("failed to build the thread pool")
+            })
+        });
+    }
+    group.finish();
+}
+
+fn part2_synthetic(c: &mut Criterion) {
+    let numbers: Vec<NumberTree> =
+        generators::day18::snailfish_numbers(0x2021_1218, SYNTHETIC_NUMBERS)
+            .lines()
+            .map(|line| line.parse().expect("generated an invalid number"))
+            .collect();
+
+    let mut group = c.benchmark_group("part2-synthetic");
+    group.sample_size(10);
+    group.bench_function("sequential", |b| {
+        b.iter(|| part2_using(black_box(&numbers), Algorithm::Sequential))
+    });
+    group.bench_function("parallel", |b| {
+        b.iter(|| part2_using(black_box(&numbers), Algorithm::Parallel))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, part2_algorithms, part2_synthetic);
+criterion_main!(benches);
@@ -0,0 +1,36 @@
+// Copyright 2022 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![no_main]
+
+use day18::NumberTree;
+use libfuzzer_sys::fuzz_target;
+
+// e.g. `cargo +nightly fuzz run parse` from within the `day18` directory
+fuzz_target!(|data: &str| {
+    let num = match data.parse::<NumberTree>() {
+        Ok(num) => num,
+        Err(_) => return,
+    };
+
+    // leading zeroes are not preserved, but otherwise the number must survive the round trip
+    assert_eq!(Ok(&num), num.to_string().parse().as_ref());
+
+    // the arithmetic is only guaranteed not to overflow for the numbers from the puzzle domain
+    if num.is_reduced() {
+        let sum = num.clone() + &num;
+        assert!(sum.is_reduced());
+        sum.magnitude();
+    }
+});
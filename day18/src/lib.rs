@@ -0,0 +1,792 @@
+// Copyright 2021 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use itertools::Itertools;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use std::cmp::max;
+use std::fmt::{self, Display, Formatter};
+use std::io;
+use std::iter::Peekable;
+use std::ops::{Add, AddAssign};
+use std::path::Path;
+use std::str::{CharIndices, FromStr};
+use utils::input_read::read_parsed_line_input;
+use utils::Solver;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Algorithm {
+    #[default]
+    Sequential,
+    /// Distributes the sums of the ordered pairs of numbers across the rayon thread pool.
+    #[cfg(feature = "parallel")]
+    Parallel,
+}
+
+impl FromStr for Algorithm {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "sequential" => Ok(Algorithm::Sequential),
+            #[cfg(feature = "parallel")]
+            "parallel" => Ok(Algorithm::Parallel),
+            other => Err(format!("{other} is not a valid algorithm")),
+        }
+    }
+}
+
+/// Depth at which the pairs explode.
+const EXPLOSION_DEPTH: usize = 4;
+
+const SPLIT_THRESHOLD: u32 = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Expected {
+    OpeningBracket,
+    Comma,
+    ClosingBracket,
+    /// Either a regular value or an opening bracket of the nested pair.
+    Element,
+    End,
+}
+
+impl Display for Expected {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Expected::OpeningBracket => write!(f, "'['"),
+            Expected::Comma => write!(f, "','"),
+            Expected::ClosingBracket => write!(f, "']'"),
+            Expected::Element => write!(f, "a digit or '['"),
+            Expected::End => write!(f, "the end of the number"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseError {
+    Unexpected {
+        position: usize,
+        expected: Expected,
+        found: Option<char>,
+    },
+    ValueOverflow {
+        position: usize,
+    },
+}
+
+impl ParseError {
+    /// Byte offset within the input at which the parsing has failed.
+    pub fn position(&self) -> usize {
+        match *self {
+            ParseError::Unexpected { position, .. } => position,
+            ParseError::ValueOverflow { position } => position,
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Unexpected {
+                position,
+                expected,
+                found: Some(found),
+            } => write!(f, "expected {expected} at {position}, found '{found}'"),
+            ParseError::Unexpected {
+                position,
+                expected,
+                found: None,
+            } => write!(
+                f,
+                "expected {expected} at {position}, found the end of input"
+            ),
+            ParseError::ValueOverflow { position } => {
+                write!(f, "value starting at {position} does not fit in u32")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Snailfish number represented by its regular values, in order, alongside the number of pairs
+/// enclosing each of them, so that both explosions and splits are local operations.
+#[derive(Debug, Eq, PartialEq, Clone, Default)]
+pub struct NumberTree {
+    values: Vec<(u32, usize)>,
+}
+
+impl NumberTree {
+    pub fn max_depth(&self) -> usize {
+        self.values
+            .iter()
+            .map(|&(_, depth)| depth)
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// Checks whether the number has no pairs to explode nor values to split.
+    pub fn is_reduced(&self) -> bool {
+        self.values
+            .iter()
+            .all(|&(val, depth)| val < SPLIT_THRESHOLD && depth <= EXPLOSION_DEPTH)
+    }
+
+    pub fn magnitude(&self) -> u32 {
+        // regular values of the same depth adjacent on the stack form a pair
+        let mut stack: Vec<(u32, usize)> = Vec::with_capacity(self.values.len());
+        for &(val, depth) in &self.values {
+            let mut current = (val, depth);
+            while let Some(&(left, left_depth)) = stack.last() {
+                if left_depth != current.1 {
+                    break;
+                }
+                stack.pop();
+                current = (3 * left + 2 * current.0, current.1 - 1);
+            }
+            stack.push(current)
+        }
+        debug_assert_eq!(1, stack.len());
+        stack[0].0
+    }
+
+    // finds the leftmost pair consisting of two regular values nested deeper than the explosion
+    // depth, returning the index of its left value
+    fn exploding_pair(&self) -> Option<usize> {
+        // (depth, index of the value if the element is a regular value rather than a pair)
+        let mut stack: Vec<(usize, Option<usize>)> = Vec::with_capacity(self.values.len());
+        for (i, &(_, depth)) in self.values.iter().enumerate() {
+            let mut current = (depth, Some(i));
+            while let Some(&(left_depth, left)) = stack.last() {
+                if left_depth != current.0 {
+                    break;
+                }
+                if let (Some(left), Some(_)) = (left, current.1) {
+                    if depth > EXPLOSION_DEPTH {
+                        return Some(left);
+                    }
+                }
+                stack.pop();
+                current = (current.0 - 1, None);
+            }
+            stack.push(current)
+        }
+        None
+    }
+
+    fn explode(&mut self) -> bool {
+        let i = match self.exploding_pair() {
+            None => return false,
+            Some(i) => i,
+        };
+
+        let (left_val, depth) = self.values[i];
+        let (right_val, _) = self.values[i + 1];
+
+        if i > 0 {
+            self.values[i - 1].0 += left_val;
+        }
+        if let Some(right) = self.values.get_mut(i + 2) {
+            right.0 += right_val;
+        }
+
+        self.values.splice(i..i + 2, [(0, depth - 1)]);
+        true
+    }
+
+    fn split(&mut self) -> bool {
+        let i = match self
+            .values
+            .iter()
+            .position(|&(val, _)| val >= SPLIT_THRESHOLD)
+        {
+            None => return false,
+            Some(i) => i,
+        };
+
+        let (val, depth) = self.values[i];
+        let x = val / 2;
+        let y = val - x;
+        self.values.splice(i..=i, [(x, depth + 1), (y, depth + 1)]);
+        true
+    }
+
+    pub fn reduce(&mut self) {
+        loop {
+            if self.explode() {
+                continue;
+            } else if !self.split() {
+                break;
+            }
+        }
+    }
+}
+
+impl NumberTree {
+    // writes the element starting at the `next` value, nested `depth` pairs deep
+    fn fmt_element(&self, f: &mut Formatter<'_>, next: &mut usize, depth: usize) -> fmt::Result {
+        let (val, val_depth) = self.values[*next];
+        if val_depth == depth {
+            *next += 1;
+            write!(f, "{val}")
+        } else {
+            write!(f, "[")?;
+            self.fmt_element(f, next, depth + 1)?;
+            write!(f, ",")?;
+            self.fmt_element(f, next, depth + 1)?;
+            write!(f, "]")
+        }
+    }
+}
+
+impl Display for NumberTree {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.values.is_empty() {
+            return Ok(());
+        }
+        self.fmt_element(f, &mut 0, 0)
+    }
+}
+
+impl<'a> AddAssign<&'a NumberTree> for NumberTree {
+    fn add_assign(&mut self, rhs: &'a NumberTree) {
+        // the empty number is the identity, making it a convenient starting point of a sum
+        if self.values.is_empty() {
+            self.values.extend_from_slice(&rhs.values);
+            return;
+        }
+
+        // both operands become one level deeper by being put into the new pair
+        self.values.reserve(rhs.values.len());
+        for (_, depth) in &mut self.values {
+            *depth += 1;
+        }
+        self.values
+            .extend(rhs.values.iter().map(|&(val, depth)| (val, depth + 1)));
+        self.reduce();
+    }
+}
+
+impl<'a> Add<&'a NumberTree> for NumberTree {
+    type Output = NumberTree;
+
+    fn add(mut self, rhs: &'a NumberTree) -> Self::Output {
+        self += rhs;
+        self
+    }
+}
+
+struct Parser<'a> {
+    chars: Peekable<CharIndices<'a>>,
+    len: usize,
+    values: Vec<(u32, usize)>,
+}
+
+impl<'a> Parser<'a> {
+    fn new(s: &'a str) -> Self {
+        Parser {
+            chars: s.char_indices().peekable(),
+            len: s.len(),
+            values: Vec::new(),
+        }
+    }
+
+    fn unexpected(&mut self, expected: Expected) -> ParseError {
+        let (position, found) = match self.chars.peek() {
+            Some(&(position, found)) => (position, Some(found)),
+            None => (self.len, None),
+        };
+        ParseError::Unexpected {
+            position,
+            expected,
+            found,
+        }
+    }
+
+    fn expect(&mut self, char: char, expected: Expected) -> Result<(), ParseError> {
+        if self.chars.next_if(|&(_, c)| c == char).is_some() {
+            Ok(())
+        } else {
+            Err(self.unexpected(expected))
+        }
+    }
+
+    // parses `[element,element]` whose elements are nested `depth` pairs deep
+    fn pair(&mut self, depth: usize) -> Result<(), ParseError> {
+        self.expect('[', Expected::OpeningBracket)?;
+        self.element(depth)?;
+        self.expect(',', Expected::Comma)?;
+        self.element(depth)?;
+        self.expect(']', Expected::ClosingBracket)
+    }
+
+    fn element(&mut self, depth: usize) -> Result<(), ParseError> {
+        match self.chars.peek() {
+            Some((_, '[')) => self.pair(depth + 1),
+            Some(&(position, c)) if c.is_ascii_digit() => {
+                let mut val = 0u32;
+                while let Some((_, digit)) = self.chars.next_if(|(_, c)| c.is_ascii_digit()) {
+                    val = val
+                        .checked_mul(10)
+                        .and_then(|val| val.checked_add(digit.to_digit(10)?))
+                        .ok_or(ParseError::ValueOverflow { position })?;
+                }
+                self.values.push((val, depth));
+                Ok(())
+            }
+            _ => Err(self.unexpected(Expected::Element)),
+        }
+    }
+}
+
+impl FromStr for NumberTree {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser::new(s);
+        parser.pair(1)?;
+        if parser.chars.peek().is_some() {
+            return Err(parser.unexpected(Expected::End));
+        }
+
+        Ok(NumberTree {
+            values: parser.values,
+        })
+    }
+}
+
+pub fn part1(numbers: &[NumberTree]) -> u32 {
+    let mut acc = NumberTree::default();
+    for num in numbers {
+        acc += num;
+    }
+    acc.magnitude()
+}
+
+// no point in using short numbers, they won't produce high magnitudes
+fn candidates(numbers: &[NumberTree]) -> Vec<&NumberTree> {
+    numbers
+        .iter()
+        .filter(|num| num.max_depth() >= EXPLOSION_DEPTH)
+        .collect()
+}
+
+fn largest_pair_magnitude(numbers: &[NumberTree]) -> u32 {
+    candidates(numbers)
+        .into_iter()
+        .permutations(2)
+        .map(|nums| {
+            max(
+                (nums[0].clone() + nums[1]).magnitude(),
+                (nums[1].clone() + nums[0]).magnitude(),
+            )
+        })
+        .max()
+        .unwrap()
+}
+
+#[cfg(feature = "parallel")]
+fn largest_pair_magnitude_parallel(numbers: &[NumberTree]) -> u32 {
+    let candidates = candidates(numbers);
+    // every ordered pair is summed exactly once, with the left operand determining the task
+    candidates
+        .par_iter()
+        .enumerate()
+        .map(|(i, &lhs)| {
+            candidates
+                .iter()
+                .enumerate()
+                .filter(|&(j, _)| i != j)
+                .map(|(_, &rhs)| (lhs.clone() + rhs).magnitude())
+                .max()
+                .unwrap_or_default()
+        })
+        .max()
+        .unwrap()
+}
+
+pub fn part2(numbers: &[NumberTree]) -> u32 {
+    part2_using(numbers, Algorithm::default())
+}
+
+pub fn part2_using(numbers: &[NumberTree], algorithm: Algorithm) -> u32 {
+    match algorithm {
+        Algorithm::Sequential => largest_pair_magnitude(numbers),
+        #[cfg(feature = "parallel")]
+        Algorithm::Parallel => largest_pair_magnitude_parallel(numbers),
+    }
+}
+
+/// Adds up the snailfish numbers, using the chosen algorithm for the second part.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Day18 {
+    pub algorithm: Algorithm,
+}
+
+impl Solver for Day18 {
+    const DAY: u8 = 18;
+
+    type Input = Vec<NumberTree>;
+    type Output1 = u32;
+    type Output2 = u32;
+
+    fn parse(&self, input_file: &Path) -> io::Result<Self::Input> {
+        read_parsed_line_input(input_file)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Self::Output1 {
+        part1(input)
+    }
+
+    fn part2(&self, input: &Self::Input) -> Self::Output2 {
+        part2_using(input, self.algorithm)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn number_parsing() {
+        let num: NumberTree = "[[[[0,7],4],[[7,8],[6,0]]],[8,1]]".parse().unwrap();
+        let expected = NumberTree {
+            values: vec![
+                (0, 4),
+                (7, 4),
+                (4, 3),
+                (7, 4),
+                (8, 4),
+                (6, 4),
+                (0, 4),
+                (8, 2),
+                (1, 2),
+            ],
+        };
+        assert_eq!(expected, num);
+    }
+
+    #[test]
+    fn display_round_trip() {
+        for raw in [
+            "[1,2]",
+            "[[1,2],3]",
+            "[[[[0,7],4],[[7,8],[6,0]]],[8,1]]",
+            "[[3,[2,[8,0]]],[9,[5,[4,[3,2]]]]]",
+            "[[[[8,7],[7,7]],[[8,6],[7,7]]],[[[0,7],[6,6]],[8,7]]]",
+        ] {
+            let num: NumberTree = raw.parse().unwrap();
+            assert_eq!(raw, num.to_string());
+            assert_eq!(num, num.to_string().parse().unwrap());
+        }
+
+        let t1: NumberTree = "[[[[4,3],4],4],[7,[[8,4],9]]]".parse().unwrap();
+        let t2: NumberTree = "[1,1]".parse().unwrap();
+        assert_eq!("[[[[0,7],4],[[7,8],[6,0]]],[8,1]]", (t1 + &t2).to_string());
+    }
+
+    #[test]
+    fn deep_and_multi_digit_reduction() {
+        let mut num: NumberTree = "[[[[[[1,2],3],4],5],6],7]".parse().unwrap();
+        assert_eq!(6, num.max_depth());
+        num.reduce();
+        assert_eq!("[[[[0,9],5],6],7]", num.to_string());
+
+        let mut num: NumberTree = "[[10,2],33]".parse().unwrap();
+        assert_eq!(vec![(10, 2), (2, 2), (33, 1)], num.values);
+        num.reduce();
+        assert_eq!("[[[5,5],2],[[8,8],[8,9]]]", num.to_string());
+
+        // only pairs of regular values explode, even if other pairs are nested deep enough
+        let mut num: NumberTree = "[[[[[1,[2,3]],4],5],6],7]".parse().unwrap();
+        assert!(num.explode());
+        assert_eq!("[[[[[3,0],7],5],6],7]", num.to_string());
+    }
+
+    #[test]
+    fn malformed_numbers() {
+        let unexpected = |position, expected, found| ParseError::Unexpected {
+            position,
+            expected,
+            found,
+        };
+
+        assert_eq!(
+            Err(unexpected(0, Expected::OpeningBracket, None)),
+            "".parse::<NumberTree>()
+        );
+        assert_eq!(
+            Err(unexpected(0, Expected::OpeningBracket, Some('1'))),
+            "1".parse::<NumberTree>()
+        );
+        assert_eq!(
+            Err(unexpected(2, Expected::Comma, Some(']'))),
+            "[1]".parse::<NumberTree>()
+        );
+        assert_eq!(
+            Err(unexpected(3, Expected::Element, Some(']'))),
+            "[1,]".parse::<NumberTree>()
+        );
+        assert_eq!(
+            Err(unexpected(8, Expected::ClosingBracket, None)),
+            "[[1,2],3".parse::<NumberTree>()
+        );
+        assert_eq!(
+            Err(unexpected(5, Expected::End, Some(']'))),
+            "[1,2]]".parse::<NumberTree>()
+        );
+        assert_eq!(
+            Err(unexpected(3, Expected::Element, Some('x'))),
+            "[1,x]".parse::<NumberTree>()
+        );
+        assert_eq!(
+            Err(ParseError::ValueOverflow { position: 1 }),
+            "[99999999999,1]".parse::<NumberTree>()
+        );
+    }
+
+    #[test]
+    fn in_place_addition() {
+        let mut sum = NumberTree::default();
+        sum += &"[[[[4,3],4],4],[7,[[8,4],9]]]".parse().unwrap();
+        assert_eq!("[[[[4,3],4],4],[7,[[8,4],9]]]", sum.to_string());
+
+        sum += &"[1,1]".parse().unwrap();
+        assert_eq!("[[[[0,7],4],[[7,8],[6,0]]],[8,1]]", sum.to_string());
+    }
+
+    #[test]
+    fn splitting() {
+        let mut before = NumberTree {
+            values: vec![
+                (0, 4),
+                (7, 4),
+                (4, 3),
+                (15, 3),
+                (0, 4),
+                (13, 4),
+                (1, 2),
+                (1, 2),
+            ],
+        };
+        // [[[[0,7],4],[15,[0,13]]],[1,1]] becomes [[[[0,7],4],[[7,8],[0,13]]],[1,1]]
+        assert!(before.split());
+        assert_eq!(
+            vec![
+                (0, 4),
+                (7, 4),
+                (4, 3),
+                (7, 4),
+                (8, 4),
+                (0, 4),
+                (13, 4),
+                (1, 2),
+                (1, 2)
+            ],
+            before.values
+        );
+    }
+
+    #[test]
+    fn explosion() {
+        let mut before: NumberTree = "[[[[[9,8],1],2],3],4]".parse().unwrap();
+        assert!(before.explode());
+        let after: NumberTree = "[[[[0,9],2],3],4]".parse().unwrap();
+        assert_eq!(after, before);
+
+        let mut before: NumberTree = "[7,[6,[5,[4,[3,2]]]]]".parse().unwrap();
+        assert!(before.explode());
+        let after: NumberTree = "[7,[6,[5,[7,0]]]]".parse().unwrap();
+        assert_eq!(after, before);
+
+        let mut before: NumberTree = "[[6,[5,[4,[3,2]]]],1]".parse().unwrap();
+        assert!(before.explode());
+        let after: NumberTree = "[[6,[5,[7,0]]],3]".parse().unwrap();
+        assert_eq!(after, before);
+
+        let mut before: NumberTree = "[[3,[2,[1,[7,3]]]],[6,[5,[4,[3,2]]]]]".parse().unwrap();
+        assert!(before.explode());
+        let after: NumberTree = "[[3,[2,[8,0]]],[9,[5,[4,[3,2]]]]]".parse().unwrap();
+        assert_eq!(after, before);
+
+        let mut before: NumberTree = "[[3,[2,[8,0]]],[9,[5,[4,[3,2]]]]]".parse().unwrap();
+        assert!(before.explode());
+        let after: NumberTree = "[[3,[2,[8,0]]],[9,[5,[7,0]]]]".parse().unwrap();
+        assert_eq!(after, before);
+    }
+
+    #[test]
+    fn magnitude() {
+        let tree: NumberTree = "[[1,2],[[3,4],5]]".parse().unwrap();
+        let expected = 143;
+        assert_eq!(tree.magnitude(), expected);
+
+        let tree: NumberTree = "[[[[0,7],4],[[7,8],[6,0]]],[8,1]]".parse().unwrap();
+        let expected = 1384;
+        assert_eq!(tree.magnitude(), expected);
+
+        let tree: NumberTree = "[[[[1,1],[2,2]],[3,3]],[4,4]]".parse().unwrap();
+        let expected = 445;
+        assert_eq!(tree.magnitude(), expected);
+
+        let tree: NumberTree = "[[[[3,0],[5,3]],[4,4]],[5,5]]".parse().unwrap();
+        let expected = 791;
+        assert_eq!(tree.magnitude(), expected);
+
+        let tree: NumberTree = "[[[[5,0],[7,4]],[5,5]],[6,6]]".parse().unwrap();
+        let expected = 1137;
+        assert_eq!(tree.magnitude(), expected);
+
+        let tree: NumberTree = "[[[[8,7],[7,7]],[[8,6],[7,7]]],[[[0,7],[6,6]],[8,7]]]"
+            .parse()
+            .unwrap();
+        let expected = 3488;
+        assert_eq!(tree.magnitude(), expected);
+    }
+
+    #[test]
+    fn sample_addition() {
+        let t1: NumberTree = "[[[[4,3],4],4],[7,[[8,4],9]]]".parse().unwrap();
+        let t2: NumberTree = "[1,1]".parse().unwrap();
+
+        let expected: NumberTree = "[[[[0,7],4],[[7,8],[6,0]]],[8,1]]".parse().unwrap();
+        assert_eq!(expected, t1 + &t2)
+    }
+
+    #[test]
+    fn sample_sum() {
+        let nums: Vec<NumberTree> = vec![
+            "[[[0,[4,5]],[0,0]],[[[4,5],[2,6]],[9,5]]]".parse().unwrap(),
+            "[7,[[[3,7],[4,3]],[[6,3],[8,8]]]]".parse().unwrap(),
+            "[[2,[[0,8],[3,4]]],[[[6,7],1],[7,[1,6]]]]".parse().unwrap(),
+            "[[[[2,4],7],[6,[0,5]]],[[[6,8],[2,8]],[[2,1],[4,5]]]]"
+                .parse()
+                .unwrap(),
+            "[7,[5,[[3,8],[1,4]]]]".parse().unwrap(),
+            "[[2,[2,2]],[8,[8,1]]]".parse().unwrap(),
+            "[2,9]".parse().unwrap(),
+            "[1,[[[9,3],9],[[9,0],[0,7]]]]".parse().unwrap(),
+            "[[[5,[7,4]],7],1]".parse().unwrap(),
+            "[[[[4,2],2],6],[8,7]]".parse().unwrap(),
+        ];
+
+        let s1: NumberTree = "[[[[4,0],[5,4]],[[7,7],[6,0]]],[[8,[7,7]],[[7,9],[5,0]]]]"
+            .parse()
+            .unwrap();
+
+        let s2: NumberTree = "[[[[6,7],[6,7]],[[7,7],[0,7]]],[[[8,7],[7,7]],[[8,8],[8,0]]]]"
+            .parse()
+            .unwrap();
+
+        let s3: NumberTree = "[[[[7,0],[7,7]],[[7,7],[7,8]]],[[[7,7],[8,8]],[[7,7],[8,7]]]]"
+            .parse()
+            .unwrap();
+
+        let s4: NumberTree = "[[[[7,7],[7,8]],[[9,5],[8,7]]],[[[6,8],[0,8]],[[9,9],[9,0]]]]"
+            .parse()
+            .unwrap();
+
+        let s5: NumberTree = "[[[[6,6],[6,6]],[[6,0],[6,7]]],[[[7,7],[8,9]],[8,[8,1]]]]"
+            .parse()
+            .unwrap();
+
+        let s6: NumberTree = "[[[[6,6],[7,7]],[[0,7],[7,7]]],[[[5,5],[5,6]],9]]"
+            .parse()
+            .unwrap();
+
+        let s7: NumberTree = "[[[[7,8],[6,7]],[[6,8],[0,8]]],[[[7,7],[5,0]],[[5,5],[5,6]]]]"
+            .parse()
+            .unwrap();
+
+        let s8: NumberTree = "[[[[7,7],[7,7]],[[8,7],[8,7]]],[[[7,0],[7,7]],9]]"
+            .parse()
+            .unwrap();
+
+        let s9: NumberTree = "[[[[8,7],[7,7]],[[8,6],[7,7]]],[[[0,7],[6,6]],[8,7]]]"
+            .parse()
+            .unwrap();
+
+        let mut running_total = nums[0].clone() + &nums[1];
+        assert_eq!(running_total, s1);
+
+        running_total += &nums[2];
+        assert_eq!(running_total, s2);
+
+        running_total += &nums[3];
+        assert_eq!(running_total, s3);
+
+        running_total += &nums[4];
+        assert_eq!(running_total, s4);
+
+        running_total += &nums[5];
+        assert_eq!(running_total, s5);
+
+        running_total += &nums[6];
+        assert_eq!(running_total, s6);
+
+        running_total += &nums[7];
+        assert_eq!(running_total, s7);
+
+        running_total += &nums[8];
+        assert_eq!(running_total, s8);
+
+        running_total += &nums[9];
+        assert_eq!(running_total, s9);
+    }
+
+    #[test]
+    fn part1_sample_input() {
+        let input = vec![
+            "[[[0,[5,8]],[[1,7],[9,6]]],[[4,[1,2]],[[1,4],2]]]"
+                .parse()
+                .unwrap(),
+            "[[[5,[2,8]],4],[5,[[9,9],0]]]".parse().unwrap(),
+            "[6,[[[6,2],[5,6]],[[7,6],[4,7]]]]".parse().unwrap(),
+            "[[[6,[0,7]],[0,9]],[4,[9,[9,0]]]]".parse().unwrap(),
+            "[[[7,[6,4]],[3,[1,3]]],[[[5,5],1],9]]".parse().unwrap(),
+            "[[6,[[7,3],[3,2]]],[[[3,8],[5,7]],4]]".parse().unwrap(),
+            "[[[[5,4],[7,7]],8],[[8,3],8]]".parse().unwrap(),
+            "[[9,3],[[9,9],[6,[4,9]]]]".parse().unwrap(),
+            "[[2,[[7,7],7]],[[5,8],[[9,3],[0,2]]]]".parse().unwrap(),
+            "[[[[5,2],5],[8,[3,7]]],[[5,[7,5]],[4,4]]]".parse().unwrap(),
+        ];
+
+        let expected = 4140;
+        assert_eq!(expected, part1(&input))
+    }
+
+    #[test]
+    fn part2_sample_input() {
+        let input = vec![
+            "[[[0,[5,8]],[[1,7],[9,6]]],[[4,[1,2]],[[1,4],2]]]"
+                .parse()
+                .unwrap(),
+            "[[[5,[2,8]],4],[5,[[9,9],0]]]".parse().unwrap(),
+            "[6,[[[6,2],[5,6]],[[7,6],[4,7]]]]".parse().unwrap(),
+            "[[[6,[0,7]],[0,9]],[4,[9,[9,0]]]]".parse().unwrap(),
+            "[[[7,[6,4]],[3,[1,3]]],[[[5,5],1],9]]".parse().unwrap(),
+            "[[6,[[7,3],[3,2]]],[[[3,8],[5,7]],4]]".parse().unwrap(),
+            "[[[[5,4],[7,7]],8],[[8,3],8]]".parse().unwrap(),
+            "[[9,3],[[9,9],[6,[4,9]]]]".parse().unwrap(),
+            "[[2,[[7,7],7]],[[5,8],[[9,3],[0,2]]]]".parse().unwrap(),
+            "[[[[5,2],5],[8,[3,7]]],[[5,[7,5]],[4,4]]]".parse().unwrap(),
+        ];
+
+        let expected = 3993;
+        assert_eq!(expected, part2(&input));
+        #[cfg(feature = "parallel")]
+        assert_eq!(expected, part2_using(&input, Algorithm::Parallel))
+    }
+}
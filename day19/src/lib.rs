@@ -0,0 +1,636 @@
+// Copyright 2022 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::{anyhow, bail};
+use itertools::Itertools;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap};
+use std::fmt::{self, Display, Formatter};
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+use utils::geometry::{Rotation3, Vec3};
+use utils::input_read::read_parsed_groups;
+use utils::{SharedSolver, Solver};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Algorithm {
+    #[cfg_attr(not(feature = "parallel"), default)]
+    Sequential,
+    /// Distributes the alignment attempts of the unaligned scanners across the rayon thread pool.
+    /// It is the default whenever the `parallel` feature is enabled.
+    #[cfg(feature = "parallel")]
+    #[default]
+    Parallel,
+}
+
+impl FromStr for Algorithm {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "sequential" => Ok(Algorithm::Sequential),
+            #[cfg(feature = "parallel")]
+            "parallel" => Ok(Algorithm::Parallel),
+            other => Err(format!("{other} is not a valid algorithm")),
+        }
+    }
+}
+
+/// Number of beacons two scanners have to detect in common to be considered overlapping,
+/// as specified by the puzzle.
+pub const DEFAULT_OVERLAP_THRESHOLD: usize = 12;
+
+/// Number of pairs that can be formed out of the overlapping beacons,
+/// i.e. number of beacon distances two overlapping scanners must have in common.
+const fn shared_distances_threshold(overlap_threshold: usize) -> usize {
+    overlap_threshold * overlap_threshold.saturating_sub(1) / 2
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AlignmentError {
+    NoScanners,
+    /// None of the listed scanners overlap with any of the scanners that got placed.
+    Unplaced(Vec<usize>),
+}
+
+impl Display for AlignmentError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            AlignmentError::NoScanners => write!(f, "there are no scanners to align"),
+            AlignmentError::Unplaced(ids) => write!(f, "failed to place scanners {ids:?}"),
+        }
+    }
+}
+
+impl std::error::Error for AlignmentError {}
+
+/// Position of a scanner or a beacon, relative to the scanner it's expressed against.
+pub type Position = Vec3;
+
+/// Scanners and beacons placed relative to the scanner 0, alongside the orientation
+/// each of the scanners turned out to be facing.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ReconstructedMap {
+    pub scanner_positions: HashMap<usize, Position>,
+    pub beacons: BTreeSet<Position>,
+    pub orientations: HashMap<usize, Rotation3>,
+}
+
+impl ReconstructedMap {
+    pub fn largest_scanner_distance(&self) -> usize {
+        self.scanner_positions
+            .values()
+            .tuple_combinations::<(_, _)>()
+            .map(|(a, b)| a.manhattan_distance(b))
+            .max()
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Scanner {
+    id: usize,
+    relative_position: Position,
+    orientation: Rotation3,
+    beacons: BTreeSet<Position>,
+    // pairs of beacons keyed by the squared distance between them, which does not depend on
+    // either the orientation or the position of the scanner
+    distances: HashMap<isize, Vec<(Position, Position)>>,
+}
+
+impl FromStr for Scanner {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            bail!("empty input")
+        }
+        let mut lines = s.lines();
+        let id_line = lines.next().ok_or_else(|| anyhow!("no id value present"))?;
+        let prefix_stripped = id_line
+            .strip_prefix("--- scanner ")
+            .ok_or_else(|| anyhow!("invalid scanner id"))?;
+        let id = prefix_stripped
+            .strip_suffix(" ---")
+            .ok_or_else(|| anyhow!("invalid scanner id"))?
+            .parse()?;
+
+        let beacons = lines
+            .map(FromStr::from_str)
+            .collect::<Result<BTreeSet<_>, _>>()?;
+
+        Ok(Scanner::new(id, Position::origin(), beacons))
+    }
+}
+
+impl Scanner {
+    fn new(id: usize, relative_position: Position, beacons: BTreeSet<Position>) -> Self {
+        let mut distances: HashMap<_, Vec<_>> = HashMap::new();
+        for (&a, &b) in beacons.iter().tuple_combinations() {
+            distances
+                .entry(a.squared_distance(&b))
+                .or_default()
+                .push((a, b));
+        }
+
+        Scanner {
+            id,
+            relative_position,
+            orientation: Rotation3::IDENTITY,
+            beacons,
+            distances,
+        }
+    }
+
+    fn shared_distances(&self, other: &Self) -> usize {
+        self.distances
+            .iter()
+            .filter_map(|(distance, pairs)| {
+                other
+                    .distances
+                    .get(distance)
+                    .map(|other_pairs| pairs.len().min(other_pairs.len()))
+            })
+            .sum()
+    }
+
+    // we treat 'self' as the source of truth
+    fn try_align_scanner(&self, other: &Self, overlap_threshold: usize) -> Option<Scanner> {
+        // scanners without enough common beacon distances can't possibly overlap
+        if self.shared_distances(other) < shared_distances_threshold(overlap_threshold) {
+            return None;
+        }
+
+        let rotated_beacons = other
+            .beacons
+            .iter()
+            .map(Position::all_rotations)
+            .collect::<Vec<_>>();
+
+        // if the pairs are the same beacons, one of the other pair's beacons has to become `base`
+        // after getting rotated and translated
+        for (distance, pairs) in &self.distances {
+            let other_pairs = match other.distances.get(distance) {
+                Some(other_pairs) => other_pairs,
+                None => continue,
+            };
+            for &(base, _) in pairs {
+                for &(a, b) in other_pairs {
+                    let (a_rotations, b_rotations) = (a.all_rotations(), b.all_rotations());
+                    for rotation in 0..Rotation3::ALL.len() {
+                        for beacon in [a_rotations[rotation], b_rotations[rotation]] {
+                            let translation = base - beacon;
+                            let overlap = rotated_beacons
+                                .iter()
+                                .filter(|rotations| {
+                                    self.beacons.contains(&(rotations[rotation] + translation))
+                                })
+                                .count();
+
+                            if overlap >= overlap_threshold {
+                                // we found it!
+                                let beacons = rotated_beacons
+                                    .iter()
+                                    .map(|rotations| rotations[rotation] + translation)
+                                    .collect();
+                                return Some(Scanner {
+                                    orientation: Rotation3::ALL[rotation],
+                                    ..Scanner::new(
+                                        other.id,
+                                        other.relative_position + translation,
+                                        beacons,
+                                    )
+                                });
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        None
+    }
+}
+
+// attempts to align each of the unaligned scanners against any of the known ones
+fn try_align_relative_to(
+    known: &[Scanner],
+    unaligned: &HashMap<usize, Scanner>,
+    overlap_threshold: usize,
+    algorithm: Algorithm,
+) -> Vec<Scanner> {
+    let align = |scanner: &Scanner| {
+        known
+            .iter()
+            .find_map(|base| base.try_align_scanner(scanner, overlap_threshold))
+    };
+
+    match algorithm {
+        Algorithm::Sequential => unaligned.values().filter_map(align).collect(),
+        #[cfg(feature = "parallel")]
+        Algorithm::Parallel => unaligned.par_iter().filter_map(|(_, s)| align(s)).collect(),
+    }
+}
+
+fn reconstruct_absolute_positions(
+    scanners: &[Scanner],
+    overlap_threshold: usize,
+    algorithm: Algorithm,
+) -> Result<Vec<Scanner>, AlignmentError> {
+    let origin = scanners.first().ok_or(AlignmentError::NoScanners)?;
+    let mut unaligned = scanners
+        .iter()
+        .skip(1)
+        .map(|s| (s.id, s.clone()))
+        .collect::<HashMap<_, _>>();
+
+    // we treat scanner 0 as the origin and attempt to align everything relative to it
+    let mut aligned = vec![];
+
+    // check leftover scanners only against any newly aligned entries
+    let mut aligned_last_iter = vec![origin.clone()];
+
+    while !unaligned.is_empty() {
+        // nothing new to align against, so the rest of the scanners can't ever be placed
+        if aligned_last_iter.is_empty() {
+            let mut ids = unaligned.into_keys().collect::<Vec<_>>();
+            ids.sort_unstable();
+            return Err(AlignmentError::Unplaced(ids));
+        }
+
+        let aligned_this_iter =
+            try_align_relative_to(&aligned_last_iter, &unaligned, overlap_threshold, algorithm);
+        for new_known in &aligned_this_iter {
+            unaligned.remove(&new_known.id);
+        }
+
+        aligned.append(&mut aligned_last_iter);
+        aligned_last_iter = aligned_this_iter;
+    }
+    aligned.append(&mut aligned_last_iter);
+
+    Ok(aligned)
+}
+
+pub fn reconstruct_map(
+    scanners: &[Scanner],
+    overlap_threshold: usize,
+    algorithm: Algorithm,
+) -> Result<ReconstructedMap, AlignmentError> {
+    let aligned = reconstruct_absolute_positions(scanners, overlap_threshold, algorithm)?;
+    Ok(ReconstructedMap {
+        scanner_positions: aligned
+            .iter()
+            .map(|s| (s.id, s.relative_position))
+            .collect(),
+        beacons: aligned
+            .iter()
+            .flat_map(|s| s.beacons.iter().copied())
+            .collect(),
+        orientations: aligned.iter().map(|s| (s.id, s.orientation)).collect(),
+    })
+}
+
+pub fn part1(map: &ReconstructedMap) -> usize {
+    map.beacons.len()
+}
+
+pub fn part2(map: &ReconstructedMap) -> usize {
+    map.largest_scanner_distance()
+}
+
+/// Reconstructs the map of the scanners and beacons using the chosen algorithm.
+/// The parsing includes the alignment of all the scanners, as both parts are answered
+/// using the same reconstruction.
+#[derive(Debug, Clone, Copy)]
+pub struct Day19 {
+    pub algorithm: Algorithm,
+    pub overlap_threshold: usize,
+}
+
+impl Default for Day19 {
+    fn default() -> Self {
+        Day19 {
+            algorithm: Algorithm::default(),
+            overlap_threshold: DEFAULT_OVERLAP_THRESHOLD,
+        }
+    }
+}
+
+impl Solver for Day19 {
+    const DAY: u8 = 19;
+
+    type Input = ReconstructedMap;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(&self, input_file: &Path) -> io::Result<Self::Input> {
+        self.prepare(self.read(input_file)?)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Self::Output1 {
+        part1(input)
+    }
+
+    fn part2(&self, input: &Self::Input) -> Self::Output2 {
+        part2(input)
+    }
+}
+
+impl SharedSolver for Day19 {
+    type Raw = Vec<Scanner>;
+
+    fn read(&self, input_file: &Path) -> io::Result<Self::Raw> {
+        read_parsed_groups(input_file)
+    }
+
+    fn prepare(&self, raw: Self::Raw) -> io::Result<Self::Input> {
+        reconstruct_map(&raw, self.overlap_threshold, self.algorithm)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example_scanners() -> Vec<Scanner> {
+        let scanner0 = Scanner::new(
+            0,
+            Position::origin(),
+            vec![
+                (404, -588, -901).into(),
+                (528, -643, 409).into(),
+                (-838, 591, 734).into(),
+                (390, -675, -793).into(),
+                (-537, -823, -458).into(),
+                (-485, -357, 347).into(),
+                (-345, -311, 381).into(),
+                (-661, -816, -575).into(),
+                (-876, 649, 763).into(),
+                (-618, -824, -621).into(),
+                (553, 345, -567).into(),
+                (474, 580, 667).into(),
+                (-447, -329, 318).into(),
+                (-584, 868, -557).into(),
+                (544, -627, -890).into(),
+                (564, 392, -477).into(),
+                (455, 729, 728).into(),
+                (-892, 524, 684).into(),
+                (-689, 845, -530).into(),
+                (423, -701, 434).into(),
+                (7, -33, -71).into(),
+                (630, 319, -379).into(),
+                (443, 580, 662).into(),
+                (-789, 900, -551).into(),
+                (459, -707, 401).into(),
+            ]
+            .into_iter()
+            .collect(),
+        );
+
+        let scanner1 = Scanner::new(
+            1,
+            Position::origin(),
+            vec![
+                (686, 422, 578).into(),
+                (605, 423, 415).into(),
+                (515, 917, -361).into(),
+                (-336, 658, 858).into(),
+                (95, 138, 22).into(),
+                (-476, 619, 847).into(),
+                (-340, -569, -846).into(),
+                (567, -361, 727).into(),
+                (-460, 603, -452).into(),
+                (669, -402, 600).into(),
+                (729, 430, 532).into(),
+                (-500, -761, 534).into(),
+                (-322, 571, 750).into(),
+                (-466, -666, -811).into(),
+                (-429, -592, 574).into(),
+                (-355, 545, -477).into(),
+                (703, -491, -529).into(),
+                (-328, -685, 520).into(),
+                (413, 935, -424).into(),
+                (-391, 539, -444).into(),
+                (586, -435, 557).into(),
+                (-364, -763, -893).into(),
+                (807, -499, -711).into(),
+                (755, -354, -619).into(),
+                (553, 889, -390).into(),
+            ]
+            .into_iter()
+            .collect(),
+        );
+
+        let scanner2 = Scanner::new(
+            2,
+            Position::origin(),
+            vec![
+                (649, 640, 665).into(),
+                (682, -795, 504).into(),
+                (-784, 533, -524).into(),
+                (-644, 584, -595).into(),
+                (-588, -843, 648).into(),
+                (-30, 6, 44).into(),
+                (-674, 560, 763).into(),
+                (500, 723, -460).into(),
+                (609, 671, -379).into(),
+                (-555, -800, 653).into(),
+                (-675, -892, -343).into(),
+                (697, -426, -610).into(),
+                (578, 704, 681).into(),
+                (493, 664, -388).into(),
+                (-671, -858, 530).into(),
+                (-667, 343, 800).into(),
+                (571, -461, -707).into(),
+                (-138, -166, 112).into(),
+                (-889, 563, -600).into(),
+                (646, -828, 498).into(),
+                (640, 759, 510).into(),
+                (-630, 509, 768).into(),
+                (-681, -892, -333).into(),
+                (673, -379, -804).into(),
+                (-742, -814, -386).into(),
+                (577, -820, 562).into(),
+            ]
+            .into_iter()
+            .collect(),
+        );
+
+        let scanner3 = Scanner::new(
+            3,
+            Position::origin(),
+            vec![
+                (-589, 542, 597).into(),
+                (605, -692, 669).into(),
+                (-500, 565, -823).into(),
+                (-660, 373, 557).into(),
+                (-458, -679, -417).into(),
+                (-488, 449, 543).into(),
+                (-626, 468, -788).into(),
+                (338, -750, -386).into(),
+                (528, -832, -391).into(),
+                (562, -778, 733).into(),
+                (-938, -730, 414).into(),
+                (543, 643, -506).into(),
+                (-524, 371, -870).into(),
+                (407, 773, 750).into(),
+                (-104, 29, 83).into(),
+                (378, -903, -323).into(),
+                (-778, -728, 485).into(),
+                (426, 699, 580).into(),
+                (-438, -605, -362).into(),
+                (-469, -447, -387).into(),
+                (509, 732, 623).into(),
+                (647, 635, -688).into(),
+                (-868, -804, 481).into(),
+                (614, -800, 639).into(),
+                (595, 780, -596).into(),
+            ]
+            .into_iter()
+            .collect(),
+        );
+
+        let scanner4 = Scanner::new(
+            4,
+            Position::origin(),
+            vec![
+                (727, 592, 562).into(),
+                (-293, -554, 779).into(),
+                (441, 611, -461).into(),
+                (-714, 465, -776).into(),
+                (-743, 427, -804).into(),
+                (-660, -479, -426).into(),
+                (832, -632, 460).into(),
+                (927, -485, -438).into(),
+                (408, 393, -506).into(),
+                (466, 436, -512).into(),
+                (110, 16, 151).into(),
+                (-258, -428, 682).into(),
+                (-393, 719, 612).into(),
+                (-211, -452, 876).into(),
+                (808, -476, -593).into(),
+                (-575, 615, 604).into(),
+                (-485, 667, 467).into(),
+                (-680, 325, -822).into(),
+                (-627, -443, -432).into(),
+                (872, -547, -609).into(),
+                (833, 512, 582).into(),
+                (807, 604, 487).into(),
+                (839, -516, 451).into(),
+                (891, -625, 532).into(),
+                (-652, -548, -490).into(),
+                (30, -46, -14).into(),
+            ]
+            .into_iter()
+            .collect(),
+        );
+
+        vec![scanner0, scanner1, scanner2, scanner3, scanner4]
+    }
+
+    #[test]
+    fn part1_sample_input() {
+        let map = reconstruct_map(
+            &example_scanners(),
+            DEFAULT_OVERLAP_THRESHOLD,
+            Algorithm::Sequential,
+        )
+        .unwrap();
+        assert_eq!(79, part1(&map))
+    }
+
+    #[test]
+    fn part2_sample_input() {
+        let map = reconstruct_map(
+            &example_scanners(),
+            DEFAULT_OVERLAP_THRESHOLD,
+            Algorithm::Sequential,
+        )
+        .unwrap();
+        assert_eq!(3621, part2(&map))
+    }
+
+    #[test]
+    fn reconstructed_sample_map() {
+        let map = reconstruct_map(
+            &example_scanners(),
+            DEFAULT_OVERLAP_THRESHOLD,
+            Algorithm::Sequential,
+        )
+        .unwrap();
+        assert_eq!(79, map.beacons.len());
+        assert_eq!(Position::origin(), map.scanner_positions[&0]);
+        assert_eq!(Position::from((68, -1246, -43)), map.scanner_positions[&1]);
+        assert_eq!(
+            Position::from((1105, -1205, 1229)),
+            map.scanner_positions[&2]
+        );
+        assert_eq!(Position::from((-92, -2380, -20)), map.scanner_positions[&3]);
+        assert_eq!(
+            Position::from((-20, -1133, 1061)),
+            map.scanner_positions[&4]
+        );
+        assert_eq!(Rotation3::IDENTITY, map.orientations[&0]);
+
+        // the orientation brings the scanner's own readings into the scanner 0 frame
+        let position = map.scanner_positions[&1];
+        let orientation = map.orientations[&1];
+        let reading = Position::from((686, 422, 578));
+        assert_eq!(
+            Position::from((-618, -824, -621)),
+            orientation.apply(reading) + position
+        );
+        let matrix: [[isize; 3]; 3] = orientation.into();
+        assert_eq!([[-1, 0, 0], [0, 1, 0], [0, 0, -1]], matrix);
+    }
+
+    #[test]
+    #[cfg(feature = "parallel")]
+    fn parallel_alignment() {
+        let scanners = example_scanners();
+        assert_eq!(
+            reconstruct_map(&scanners, DEFAULT_OVERLAP_THRESHOLD, Algorithm::Sequential),
+            reconstruct_map(&scanners, DEFAULT_OVERLAP_THRESHOLD, Algorithm::Parallel)
+        );
+    }
+
+    #[test]
+    fn unreachable_overlap_threshold() {
+        let scanners = example_scanners();
+        // scanners 0 and 1 have exactly 12 beacons in common and nothing else overlaps scanner 0
+        assert_eq!(
+            Err(AlignmentError::Unplaced(vec![1, 2, 3, 4])),
+            reconstruct_map(&scanners, 13, Algorithm::Sequential)
+        );
+        // without scanner 1 there's nothing overlapping scanner 0
+        let without_bridge = [&scanners[..1], &scanners[2..]].concat();
+        assert_eq!(
+            Err(AlignmentError::Unplaced(vec![2, 3, 4])),
+            reconstruct_map(
+                &without_bridge,
+                DEFAULT_OVERLAP_THRESHOLD,
+                Algorithm::Sequential
+            )
+        );
+        assert_eq!(
+            Err(AlignmentError::NoScanners),
+            reconstruct_map(&[], DEFAULT_OVERLAP_THRESHOLD, Algorithm::Sequential)
+        );
+    }
+}
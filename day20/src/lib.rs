@@ -0,0 +1,657 @@
+// Copyright 2021 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryInto;
+use std::fmt::{self, Display, Formatter};
+use std::io;
+use std::mem;
+use std::path::Path;
+use std::str::FromStr;
+use utils::grid::{Grid, GridError};
+use utils::input_read::read_parsed;
+use utils::render::{render_grid, FrameSink, Glyphs};
+use utils::{Solver, Visualize};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Algorithm {
+    /// Looks up all nine pixels of every neighbourhood independently.
+    Naive,
+    /// Carries the neighbourhood index along the row, reading and writing the image a word at a time.
+    #[default]
+    Windowed,
+}
+
+impl FromStr for Algorithm {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "naive" => Ok(Algorithm::Naive),
+            "windowed" => Ok(Algorithm::Windowed),
+            other => Err(format!("{other} is not a valid algorithm")),
+        }
+    }
+}
+
+/// Bits of the neighbourhood index that stay within the window when it moves a column to the right,
+/// i.e. the middle and right columns of each of the rows.
+const WINDOW_RETAINED_BITS: usize = 0b110_110_110;
+
+/// Dense image whose pixels are stored as bits of the row-major `u64` words.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct Image {
+    width: usize,
+    height: usize,
+    words_per_row: usize,
+    words: Vec<u64>,
+}
+
+impl Image {
+    fn words_per_row(width: usize) -> usize {
+        width.div_ceil(u64::BITS as usize)
+    }
+
+    /// Resizes the image to the specified dimensions, turning off all the pixels.
+    fn reset(&mut self, width: usize, height: usize) {
+        self.width = width;
+        self.height = height;
+        self.words_per_row = Self::words_per_row(width);
+        self.words.clear();
+        self.words.resize(self.words_per_row * height, 0);
+    }
+
+    fn reserve(&mut self, width: usize, height: usize) {
+        let words = Self::words_per_row(width) * height;
+        self.words.reserve(words.saturating_sub(self.words.len()));
+    }
+
+    fn set(&mut self, x: usize, y: usize) {
+        let bit = x % u64::BITS as usize;
+        self.words[y * self.words_per_row + x / u64::BITS as usize] |= 1 << bit;
+    }
+
+    /// Looks up the pixel, treating everything outside of the image as the `background`.
+    fn get(&self, x: isize, y: isize, background: bool) -> bool {
+        if x < 0 || y < 0 || x as usize >= self.width || y as usize >= self.height {
+            return background;
+        }
+        let (x, y) = (x as usize, y as usize);
+        let bit = x % u64::BITS as usize;
+        (self.words[y * self.words_per_row + x / u64::BITS as usize] >> bit) & 1 == 1
+    }
+
+    /// Looks up the word of the row, treating everything outside of the image as the `background`.
+    fn word(&self, y: isize, index: isize, background: bool) -> u64 {
+        let fill = if background { u64::MAX } else { 0 };
+        if y < 0 || index < 0 || y as usize >= self.height || index as usize >= self.words_per_row {
+            return fill;
+        }
+        let word = self.words[y as usize * self.words_per_row + index as usize];
+        // the bits past the width of the image are always kept off
+        let valid = self.width - index as usize * u64::BITS as usize;
+        if valid < u64::BITS as usize {
+            word | (fill << valid)
+        } else {
+            word
+        }
+    }
+
+    /// Looks up the 64 pixels of the row starting at column `x`, with the pixel at `x` being
+    /// the least significant bit, treating everything outside of the image as the `background`.
+    fn window(&self, x: isize, y: isize, background: bool) -> u64 {
+        let bits = u64::BITS as isize;
+        let index = x.div_euclid(bits);
+        let offset = x.rem_euclid(bits);
+        let low = self.word(y, index, background);
+        if offset == 0 {
+            low
+        } else {
+            (low >> offset) | (self.word(y, index + 1, background) << (bits - offset))
+        }
+    }
+
+    fn set_word(&mut self, y: usize, index: usize, word: u64) {
+        // keep the bits past the width of the image off
+        let valid = self.width - index * u64::BITS as usize;
+        let word = if valid < u64::BITS as usize {
+            word & ((1 << valid) - 1)
+        } else {
+            word
+        };
+        self.words[y * self.words_per_row + index] = word;
+    }
+
+    fn lit_count(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TrenchMap {
+    enhancement_algorithm: [bool; 512],
+    image: Image,
+    // the image being produced by the current enhancement, kept around to reuse its allocation
+    buffer: Image,
+    infinity: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseError {
+    MissingAlgorithm,
+    InvalidAlgorithmLength(usize),
+    /// The algorithm and the image have to be separated by an empty line.
+    MissingSeparator,
+    EmptyImage,
+    /// Pixel that's neither `#` nor `.`, with line 0 being the algorithm.
+    InvalidPixel {
+        line: usize,
+        column: usize,
+        found: char,
+    },
+    RaggedImage {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// The algorithm lights up the entire infinite background and never turns it off again.
+    InfiniteLitPixels,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::MissingAlgorithm => write!(f, "the enhancement algorithm is missing"),
+            ParseError::InvalidAlgorithmLength(len) => write!(
+                f,
+                "the enhancement algorithm has {len} entries instead of {ALGORITHM_LEN}"
+            ),
+            ParseError::MissingSeparator => write!(
+                f,
+                "the enhancement algorithm is not followed by an empty line"
+            ),
+            ParseError::EmptyImage => write!(f, "the image is empty"),
+            ParseError::InvalidPixel {
+                line,
+                column,
+                found,
+            } => write!(f, "'{found}' at {line}:{column} is not a valid pixel"),
+            ParseError::RaggedImage {
+                row,
+                expected,
+                found,
+            } => write!(
+                f,
+                "image row {row} has {found} pixels while {expected} were expected"
+            ),
+            ParseError::InfiniteLitPixels => write!(
+                f,
+                "the enhancement algorithm lights up infinitely many pixels for good"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+const ALGORITHM_LEN: usize = 512;
+
+fn parse_pixels(line: &str, line_number: usize) -> Result<Vec<bool>, ParseError> {
+    line.chars()
+        .enumerate()
+        .map(|(column, pixel)| match pixel {
+            '#' => Ok(true),
+            '.' => Ok(false),
+            found => Err(ParseError::InvalidPixel {
+                line: line_number,
+                column,
+                found,
+            }),
+        })
+        .collect()
+}
+
+impl FromStr for TrenchMap {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines();
+        let algo = parse_pixels(lines.next().ok_or(ParseError::MissingAlgorithm)?, 0)?;
+        let algo: [bool; ALGORITHM_LEN] = algo
+            .try_into()
+            .map_err(|algo: Vec<_>| ParseError::InvalidAlgorithmLength(algo.len()))?;
+
+        // once the empty background lights up, the fully lit one would have to turn it off
+        if algo[0] && algo[ALGORITHM_LEN - 1] {
+            return Err(ParseError::InfiniteLitPixels);
+        }
+
+        match lines.next() {
+            Some("") => (),
+            Some(_) => return Err(ParseError::MissingSeparator),
+            None => return Err(ParseError::EmptyImage),
+        }
+
+        let rows = lines
+            .enumerate()
+            .map(|(row, line)| parse_pixels(line, row + 2))
+            .collect::<Result<Vec<_>, _>>()?;
+        let pixels = Grid::from_rows(rows).map_err(|err| match err {
+            GridError::RaggedRow {
+                row,
+                expected,
+                found,
+            } => ParseError::RaggedImage {
+                row,
+                expected,
+                found,
+            },
+            // the rows are never checked for digits, so the grid could only have been empty
+            GridError::Empty | GridError::InvalidDigit { .. } => ParseError::EmptyImage,
+        })?;
+
+        let mut image = Image::default();
+        image.reset(pixels.width(), pixels.height());
+        for ((x, y), _) in pixels.iter_with_pos().filter(|(_, &lit)| lit) {
+            image.set(x, y);
+        }
+
+        Ok(TrenchMap {
+            enhancement_algorithm: algo,
+            image,
+            buffer: Image::default(),
+            infinity: false,
+        })
+    }
+}
+
+impl TrenchMap {
+    // (x, y) is the position within the enhanced image, which is one pixel larger on every side
+    fn enhance_pixel(&self, x: isize, y: isize) -> bool {
+        let mut lookup = 0;
+        for dy in -1..=1 {
+            for dx in -1..=1 {
+                let pixel = self.image.get(x - 1 + dx, y - 1 + dy, self.infinity);
+                lookup = (lookup << 1) | usize::from(pixel);
+            }
+        }
+
+        self.enhancement_algorithm[lookup]
+    }
+
+    fn enhance_row_naive(&self, y: usize, enhanced: &mut Image) {
+        for x in 0..enhanced.width {
+            if self.enhance_pixel(x as isize, y as isize) {
+                enhanced.set(x, y);
+            }
+        }
+    }
+
+    fn enhance_row_windowed(&self, y: usize, enhanced: &mut Image) {
+        // the window starts fully outside the image, on the columns -3, -2 and -1
+        let mut lookup = if self.infinity { 0b111_111_111 } else { 0 };
+        let old_y = y as isize - 1;
+        for index in 0..enhanced.words_per_row {
+            // column `x` of the original image becomes the right column of the window of pixel `x`
+            let column = (index * u64::BITS as usize) as isize;
+            let top = self.image.window(column, old_y - 1, self.infinity);
+            let middle = self.image.window(column, old_y, self.infinity);
+            let bottom = self.image.window(column, old_y + 1, self.infinity);
+
+            let mut word = 0;
+            for bit in 0..u64::BITS {
+                let entering = (((top >> bit) & 1) << 6)
+                    | (((middle >> bit) & 1) << 3)
+                    | ((bottom >> bit) & 1);
+                lookup = ((lookup << 1) & WINDOW_RETAINED_BITS) | entering as usize;
+                word |= u64::from(self.enhancement_algorithm[lookup]) << bit;
+            }
+            enhanced.set_word(y, index, word);
+        }
+    }
+
+    fn enhance(&mut self, algorithm: Algorithm) {
+        let width = self.image.width + 2;
+        let height = self.image.height + 2;
+
+        let mut enhanced = mem::take(&mut self.buffer);
+        enhanced.reset(width, height);
+        for y in 0..height {
+            match algorithm {
+                Algorithm::Naive => self.enhance_row_naive(y, &mut enhanced),
+                Algorithm::Windowed => self.enhance_row_windowed(y, &mut enhanced),
+            }
+        }
+
+        if self.infinity {
+            self.infinity = self.enhancement_algorithm[ALGORITHM_LEN - 1];
+        } else {
+            self.infinity = self.enhancement_algorithm[0]
+        }
+
+        self.buffer = mem::replace(&mut self.image, enhanced);
+    }
+
+    /// Applies the enhancement the specified number of times and returns the number of lit pixels.
+    /// Note that if the background got lit by the enhancement, i.e. after an odd number of steps
+    /// with the algorithm mapping empty neighbourhoods to lit pixels, the count only covers the
+    /// finite part of the image.
+    pub fn enhance_n(&mut self, steps: usize, algorithm: Algorithm) -> usize {
+        // the image grows by a pixel on every side with each step
+        let width = self.image.width + 2 * steps;
+        let height = self.image.height + 2 * steps;
+        self.image.reserve(width, height);
+        self.buffer.reserve(width, height);
+
+        for _ in 0..steps {
+            self.enhance(algorithm);
+        }
+        self.image.lit_count()
+    }
+}
+
+/// Number of the background pixels rendered around the final image, so that its flashing is visible.
+const FRAME_MARGIN: usize = 2;
+
+/// Lit pixels of a single rendered step of the enhancement.
+pub type Frame = Vec<(usize, usize)>;
+
+impl Display for TrenchMap {
+    /// Draws the finite part of the image using the same characters as the input.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let rows = (0..self.image.height)
+            .map(|y| {
+                (0..self.image.width)
+                    .map(|x| self.image.get(x as isize, y as isize, self.infinity))
+                    .collect()
+            })
+            .collect();
+        match Grid::from_rows(rows) {
+            Ok(grid) => write!(f, "{}", render_grid(&grid, Glyphs::ASCII)),
+            // the image is never empty by construction
+            Err(_) => Ok(()),
+        }
+    }
+}
+
+impl TrenchMap {
+    // the image centred on the canvas of the specified size, with the rest filled with the background
+    fn render(&self, (width, height): (usize, usize)) -> Frame {
+        let margin_x = (width - self.image.width) as isize / 2;
+        let margin_y = (height - self.image.height) as isize / 2;
+
+        let mut lit = Vec::new();
+        for y in 0..height {
+            for x in 0..width {
+                let pixel =
+                    self.image
+                        .get(x as isize - margin_x, y as isize - margin_y, self.infinity);
+                if pixel {
+                    lit.push((x, y))
+                }
+            }
+        }
+        lit
+    }
+
+    /// Applies the enhancement the specified number of times, rendering the original image and
+    /// the result of every step on the same canvas. Returns the canvas size alongside the frames.
+    pub fn enhancement_frames(
+        &mut self,
+        steps: usize,
+        algorithm: Algorithm,
+    ) -> ((usize, usize), Vec<Frame>) {
+        let size = (
+            self.image.width + 2 * (steps + FRAME_MARGIN),
+            self.image.height + 2 * (steps + FRAME_MARGIN),
+        );
+
+        let mut frames = vec![self.render(size)];
+        for _ in 0..steps {
+            self.enhance(algorithm);
+            frames.push(self.render(size));
+        }
+        (size, frames)
+    }
+}
+
+const PART1_STEPS: usize = 2;
+pub const PART2_STEPS: usize = 50;
+
+pub fn part1(mut map: TrenchMap, algorithm: Algorithm) -> usize {
+    map.enhance_n(PART1_STEPS, algorithm)
+}
+
+pub fn part2(mut map: TrenchMap, algorithm: Algorithm) -> usize {
+    map.enhance_n(PART2_STEPS, algorithm)
+}
+
+/// Enhances the image produced by the ocean trench scanners.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Day20 {
+    pub algorithm: Algorithm,
+}
+
+impl Solver for Day20 {
+    const DAY: u8 = 20;
+
+    type Input = TrenchMap;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(&self, input_file: &Path) -> io::Result<Self::Input> {
+        read_parsed(input_file)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Self::Output1 {
+        part1(input.clone(), self.algorithm)
+    }
+
+    fn part2(&self, input: &Self::Input) -> Self::Output2 {
+        part2(input.clone(), self.algorithm)
+    }
+}
+
+impl Visualize for Day20 {
+    // the original image followed by all the enhancement steps of the second part
+    fn visualize(&self, input: &Self::Input, sink: &mut dyn FrameSink) -> io::Result<()> {
+        let (size, frames) = input
+            .clone()
+            .enhancement_frames(PART2_STEPS, self.algorithm);
+        frames
+            .iter()
+            .try_for_each(|frame| sink.push_frame(size, frame))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utils::render::FrameBuffer;
+
+    #[test]
+    fn part1_sample_input() {
+        let map = "..#.#..#####.#.#.#.###.##.....###.##.#..###.####..#####..#....#..#..##..###..######.###...####..#..#####..##..#.#####...##.#.#..#.##..#.#......#.###.######.###.####...#.##.##..#..#..#####.....#.#....###..#.##......#.....#..#..#..##..#...##.######.####.####.#.#...#.......#..#.#.#...####.##.#......#..#...##.#.##..#...##.#.##..###.#......#.#.......#.#.#.####.###.##...#.....####.#..#..#.##.#....##..#.####....##...##..#...#......#.#.......#.......##..####..#...#.#.#...##..#.#..###..#####........#..####......#..#
+
+#..#.
+#....
+##..#
+..#..
+..###"
+            .parse()
+            .unwrap();
+
+        let expected = 35;
+        assert_eq!(expected, part1(map, Algorithm::default()));
+    }
+
+    #[test]
+    fn enhancing_incrementally() {
+        let mut map: TrenchMap = "..#.#..#####.#.#.#.###.##.....###.##.#..###.####..#####..#....#..#..##..###..######.###...####..#..#####..##..#.#####...##.#.#..#.##..#.#......#.###.######.###.####...#.##.##..#..#..#####.....#.#....###..#.##......#.....#..#..#..##..#...##.######.####.####.#.#...#.......#..#.#.#...####.##.#......#..#...##.#.##..#...##.#.##..###.#......#.#.......#.#.#.####.###.##...#.....####.#..#..#.##.#....##..#.####....##...##..#...#......#.#.......#.......##..####..#...#.#.#...##..#.#..###..#####........#..####......#..#
+
+#..#.
+#....
+##..#
+..#..
+..###"
+            .parse()
+            .unwrap();
+
+        assert_eq!(10, map.enhance_n(0, Algorithm::Naive));
+        assert_eq!(24, map.enhance_n(1, Algorithm::Naive));
+        assert_eq!(35, map.enhance_n(1, Algorithm::Windowed));
+        assert_eq!(
+            3351,
+            map.enhance_n(PART2_STEPS - PART1_STEPS, Algorithm::Windowed)
+        );
+    }
+
+    #[test]
+    fn windowed_enhancement_matches_naive() {
+        let sample = "..#.#..#####.#.#.#.###.##.....###.##.#..###.####..#####..#....#..#..##..###..######.###...####..#..#####..##..#.#####...##.#.#..#.##..#.#......#.###.######.###.####...#.##.##..#..#..#####.....#.#....###..#.##......#.....#..#..#..##..#...##.######.####.####.#.#...#.......#..#.#.#...####.##.#......#..#...##.#.##..#...##.#.##..###.#......#.#.......#.#.#.####.###.##...#.....####.#..#..#.##.#....##..#.####....##...##..#...#......#.#.......#.......##..####..#...#.#.#...##..#.#..###..#####........#..####......#..#
+
+#..#.
+#....
+##..#
+..#..
+..###";
+        // same image, but with the flashing background
+        let flashing = format!("#{}.{}", &sample[1..511], &sample[512..]);
+
+        for input in [sample, &flashing] {
+            let mut naive: TrenchMap = input.parse().unwrap();
+            let mut windowed = naive.clone();
+            // enough steps for the rows to span multiple words
+            for _ in 0..40 {
+                naive.enhance(Algorithm::Naive);
+                windowed.enhance(Algorithm::Windowed);
+                assert_eq!(naive.image, windowed.image);
+                assert_eq!(naive.infinity, windowed.infinity);
+            }
+        }
+    }
+
+    #[test]
+    fn displaying_image() {
+        let image = "#..#.\n#....\n##..#\n..#..\n..###";
+        let sample = format!("{}\n\n{image}", "..#.#..#####.#.#.#.###.##.....###.##.#..###.####..#####..#....#..#..##..###..######.###...####..#..#####..##..#.#####...##.#.#..#.##..#.#......#.###.######.###.####...#.##.##..#..#..#####.....#.#....###..#.##......#.....#..#..#..##..#...##.######.####.####.#.#...#.......#..#.#.#...####.##.#......#..#...##.#.##..#...##.#.##..###.#......#.#.......#.#.#.####.###.##...#.....####.#..#..#.##.#....##..#.####....##...##..#...#......#.#.......#.......##..####..#...#.#.#...##..#.#..###..#####........#..####......#..#");
+        let mut map: TrenchMap = sample.parse().unwrap();
+        assert_eq!(image, map.to_string());
+
+        map.enhance(Algorithm::Naive);
+        assert_eq!(
+            ".##.##.\n#..#.#.\n##.#..#\n####..#\n.#..##.\n..##..#\n...#.#.",
+            map.to_string()
+        );
+    }
+
+    #[test]
+    fn rendering_enhancement_frames() {
+        let sample = "..#.#..#####.#.#.#.###.##.....###.##.#..###.####..#####..#....#..#..##..###..######.###...####..#..#####..##..#.#####...##.#.#..#.##..#.#......#.###.######.###.####...#.##.##..#..#..#####.....#.#....###..#.##......#.....#..#..#..##..#...##.######.####.####.#.#...#.......#..#.#.#...####.##.#......#..#...##.#.##..#...##.#.##..###.#......#.#.......#.#.#.####.###.##...#.....####.#..#..#.##.#....##..#.####....##...##..#...#......#.#.......#.......##..####..#...#.#.#...##..#.#..###..#####........#..####......#..#
+
+#..#.
+#....
+##..#
+..#..
+..###";
+        let mut map: TrenchMap = sample.parse().unwrap();
+        let (size, frames) = map.enhancement_frames(2, Algorithm::default());
+        assert_eq!((13, 13), size);
+        assert_eq!(
+            vec![10, 24, 35],
+            frames.iter().map(Vec::len).collect::<Vec<_>>()
+        );
+        // the original image is centred on the canvas
+        assert!(frames[0].contains(&(4, 4)));
+        assert!(!frames[0].contains(&(3, 3)));
+
+        // with the flashing background the entire canvas around the image lights up
+        let flashing = format!("#{}.{}", &sample[1..511], &sample[512..]);
+        let mut map: TrenchMap = flashing.parse().unwrap();
+        let (_, frames) = map.enhancement_frames(2, Algorithm::default());
+        assert!(frames[1].contains(&(0, 0)));
+        assert!(!frames[2].contains(&(0, 0)));
+        assert!(frames[1].len() >= 13 * 13 - 7 * 7);
+
+        let map: TrenchMap = sample.parse().unwrap();
+        let mut frames = FrameBuffer::default();
+        Day20::default().visualize(&map, &mut frames).unwrap();
+        assert_eq!(PART2_STEPS + 1, frames.frames.len());
+        assert_eq!(Some(3351), frames.frames.last().map(|(_, lit)| lit.len()));
+    }
+
+    #[test]
+    fn malformed_input() {
+        let algo = "..#.#..#####.#.#.#.###.##.....###.##.#..###.####..#####..#....#..#..##..###..######.###...####..#..#####..##..#.#####...##.#.#..#.##..#.#......#.###.######.###.####...#.##.##..#..#..#####.....#.#....###..#.##......#.....#..#..#..##..#...##.######.####.####.#.#...#.......#..#.#.#...####.##.#......#..#...##.#.##..#...##.#.##..###.#......#.#.......#.#.#.####.###.##...#.....####.#..#..#.##.#....##..#.####....##...##..#...#......#.#.......#.......##..####..#...#.#.#...##..#.#..###..#####........#..####......#..#";
+        let parse = |s: &str| s.parse::<TrenchMap>().map(|_| ());
+
+        assert_eq!(Err(ParseError::MissingAlgorithm), parse(""));
+        assert_eq!(
+            Err(ParseError::InvalidAlgorithmLength(511)),
+            parse(&format!("{}\n\n#.", &algo[1..]))
+        );
+        assert_eq!(
+            Err(ParseError::InvalidPixel {
+                line: 0,
+                column: 2,
+                found: 'x'
+            }),
+            parse(&format!("..x{}\n\n#.", &algo[3..]))
+        );
+        assert_eq!(
+            Err(ParseError::MissingSeparator),
+            parse(&format!("{algo}\n#.\n.#"))
+        );
+        assert_eq!(Err(ParseError::EmptyImage), parse(&format!("{algo}\n\n")));
+        assert_eq!(
+            Err(ParseError::InvalidPixel {
+                line: 3,
+                column: 1,
+                found: 'o'
+            }),
+            parse(&format!("{algo}\n\n#.\n.o"))
+        );
+        assert_eq!(
+            Err(ParseError::RaggedImage {
+                row: 1,
+                expected: 2,
+                found: 3
+            }),
+            parse(&format!("{algo}\n\n#.\n.#."))
+        );
+        assert_eq!(
+            Err(ParseError::InfiniteLitPixels),
+            parse(&format!("#{}#\n\n#.", &algo[1..511]))
+        );
+        assert_eq!(Ok(()), parse(&format!("{algo}\n\n#.\n.#")));
+    }
+
+    #[test]
+    fn part2_sample_input() {
+        let map = "..#.#..#####.#.#.#.###.##.....###.##.#..###.####..#####..#....#..#..##..###..######.###...####..#..#####..##..#.#####...##.#.#..#.##..#.#......#.###.######.###.####...#.##.##..#..#..#####.....#.#....###..#.##......#.....#..#..#..##..#...##.######.####.####.#.#...#.......#..#.#.#...####.##.#......#..#...##.#.##..#...##.#.##..###.#......#.#.......#.#.#.####.###.##...#.....####.#..#..#.##.#....##..#.####....##...##..#...#......#.#.......#.......##..####..#...#.#.#...##..#.#..###..#####........#..####......#..#
+
+#..#.
+#....
+##..#
+..#..
+..###"
+            .parse()
+            .unwrap();
+
+        let expected = 3351;
+        assert_eq!(expected, part2(map, Algorithm::default()));
+    }
+}
@@ -0,0 +1,37 @@
+// Copyright 2022 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use day21::{part2_using, Algorithm, DiracDice};
+use utils::input_read::read_parsed;
+
+fn part2_algorithms(c: &mut Criterion) {
+    let game: DiracDice = read_parsed(concat!(env!("CARGO_MANIFEST_DIR"), "/input"))
+        .expect("failed to read input file");
+
+    let mut group = c.benchmark_group("part2");
+    group.bench_function("breadth-wise", |b| {
+        b.iter(|| part2_using(black_box(game), Algorithm::Breadthwise))
+    });
+    group.bench_function("memoized", |b| {
+        b.iter(|| part2_using(black_box(game), Algorithm::Memoized))
+    });
+    group.bench_function("dense", |b| {
+        b.iter(|| part2_using(black_box(game), Algorithm::Dense))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, part2_algorithms);
+criterion_main!(benches);
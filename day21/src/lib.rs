@@ -0,0 +1,743 @@
+// Copyright 2021 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cmp::max;
+use std::collections::HashMap;
+use std::fmt::{self, Display, Formatter};
+use std::io;
+use std::mem;
+use std::path::Path;
+use std::str::FromStr;
+use utils::input_read::read_parsed;
+use utils::search::memoized_count;
+use utils::Solver;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Algorithm {
+    /// Simulates all the universes round by round, grouping the identical ones together.
+    #[default]
+    Breadthwise,
+    /// Recursively counts the wins reachable from each distinct state, caching the results.
+    Memoized,
+    /// Simulates the universes round by round, with their counts stored in a flat array
+    /// indexed by the state.
+    Dense,
+}
+
+impl FromStr for Algorithm {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "breadth-wise" => Ok(Algorithm::Breadthwise),
+            "memoized" => Ok(Algorithm::Memoized),
+            "dense" => Ok(Algorithm::Dense),
+            other => Err(format!("{other} is not a valid algorithm")),
+        }
+    }
+}
+
+const BOARD_SIZE: usize = 10;
+
+/// Number of sides of the die and the score required to win a variant of the game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rules {
+    pub die_sides: usize,
+    pub target_score: usize,
+}
+
+impl Rules {
+    /// Rules of the game played with the deterministic die.
+    pub const DETERMINISTIC: Rules = Rules {
+        die_sides: 100,
+        target_score: 1000,
+    };
+
+    /// Rules of the game played with the quantum die.
+    pub const QUANTUM: Rules = Rules {
+        die_sides: 3,
+        target_score: 21,
+    };
+
+    /// Sums of the three rolls of the die alongside the number of universes producing them,
+    /// e.g. for the three-sided die there's 1 universe with the sum of 3, 3 universes with 4,
+    /// 6 with 5, 7 with 6, 6 with 7, 3 with 8 and 1 with 9.
+    fn roll_distribution(&self) -> Vec<(usize, u128)> {
+        let mut universes = vec![0; 3 * self.die_sides + 1];
+        for first in 1..=self.die_sides {
+            for second in 1..=self.die_sides {
+                for third in 1..=self.die_sides {
+                    universes[first + second + third] += 1;
+                }
+            }
+        }
+        universes
+            .into_iter()
+            .enumerate()
+            .filter(|&(_, count)| count > 0)
+            .collect()
+    }
+
+    /// Number of distinct states of the unfinished quantum games, as each player can be on one of
+    /// the board spaces with a score lower than the target.
+    fn dense_states(&self) -> usize {
+        (BOARD_SIZE * self.target_score).pow(2)
+    }
+}
+
+/// Final state of the game played with the deterministic die.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeterministicOutcome {
+    pub winner: Player,
+    pub winner_score: usize,
+    pub loser_score: usize,
+    /// Total number of times the die got rolled.
+    pub rolls: usize,
+}
+
+/// Numbers of universes in which each of the players wins the game played with the quantum die.
+/// They grow exponentially with the target score, hence the wide integers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuantumOutcome {
+    pub player1_wins: u128,
+    pub player2_wins: u128,
+}
+
+impl QuantumOutcome {
+    pub fn most_wins(&self) -> u128 {
+        max(self.player1_wins, self.player2_wins)
+    }
+}
+
+/// State of the multiverse after a single turn of the quantum game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RoundStats {
+    pub player: Player,
+    /// Number of universes in which the player has won during this turn.
+    pub wins: u128,
+    /// Number of universes in which the game still goes on after this turn.
+    pub unfinished: u128,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Player {
+    One,
+    Two,
+}
+
+impl Player {
+    fn other(self) -> Self {
+        match self {
+            Player::One => Player::Two,
+            Player::Two => Player::One,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiracDice {
+    player1_position: Position,
+    player2_position: Position,
+    deterministic: Rules,
+    quantum: Rules,
+}
+
+/// Die rolling the consecutive numbers, which starts over after reaching its number of sides.
+#[derive(Debug, Clone, Copy)]
+struct DeterministicDie {
+    sides: usize,
+    last_roll: usize,
+    rolls: usize,
+}
+
+impl DeterministicDie {
+    fn new(sides: usize) -> Self {
+        DeterministicDie {
+            sides,
+            last_roll: 0,
+            rolls: 0,
+        }
+    }
+
+    fn roll(&mut self) -> usize {
+        self.last_roll = self.last_roll % self.sides + 1;
+        self.rolls += 1;
+        self.last_roll
+    }
+
+    fn roll_three_times(&mut self) -> usize {
+        if self.last_roll + 3 <= self.sides {
+            let res = 3 * self.last_roll + 6;
+            self.rolls += 3;
+            self.last_roll += 3;
+            res
+        } else {
+            self.roll() + self.roll() + self.roll()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+struct Position(usize);
+
+impl Position {
+    fn move_pawn(&mut self, val: usize) {
+        self.0 += val;
+        self.0 = (self.0 - 1) % 10 + 1
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    MissingPlayer(usize),
+    /// The line isn't in the form of `Player N starting position: P`, with `P` between 1 and 10.
+    InvalidStartingPosition {
+        player: usize,
+        line: String,
+    },
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::MissingPlayer(player) => {
+                write!(f, "the starting position of player {player} is missing")
+            }
+            ParseError::InvalidStartingPosition { player, line } => write!(
+                f,
+                "'{line}' is not a valid starting position of player {player}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+fn parse_starting_position(line: Option<&str>, player: usize) -> Result<Position, ParseError> {
+    let line = line.ok_or(ParseError::MissingPlayer(player))?;
+    line.strip_prefix(&format!("Player {player} starting position: "))
+        .and_then(|position| position.parse().ok())
+        .filter(|position| (1..=10).contains(position))
+        .map(Position)
+        .ok_or_else(|| ParseError::InvalidStartingPosition {
+            player,
+            line: line.to_string(),
+        })
+}
+
+impl FromStr for DiracDice {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines();
+        let player1_position = parse_starting_position(lines.next(), 1)?;
+        let player2_position = parse_starting_position(lines.next(), 2)?;
+
+        Ok(DiracDice {
+            player1_position,
+            player2_position,
+            deterministic: Rules::DETERMINISTIC,
+            quantum: Rules::QUANTUM,
+        })
+    }
+}
+
+impl DiracDice {
+    /// Sets up the game with the players starting on the specified board spaces, between 1 and 10,
+    /// and the rules of both of its variants.
+    ///
+    /// # Panics
+    /// Panics if either starting position is off the board, either die has no sides
+    /// or the quantum game has no target score.
+    pub fn new(positions: (usize, usize), deterministic: Rules, quantum: Rules) -> Self {
+        let on_board = 1..=BOARD_SIZE;
+        assert!(on_board.contains(&positions.0) && on_board.contains(&positions.1));
+        assert!(deterministic.die_sides > 0 && quantum.die_sides > 0);
+        // otherwise there would be no unfinished states to simulate
+        assert!(quantum.target_score > 0);
+        DiracDice {
+            player1_position: Position(positions.0),
+            player2_position: Position(positions.1),
+            deterministic,
+            quantum,
+        }
+    }
+
+    /// Plays the game using the deterministic die until either player reaches the target score.
+    pub fn play_deterministic(self) -> DeterministicOutcome {
+        let mut die = DeterministicDie::new(self.deterministic.die_sides);
+        let mut positions = [self.player1_position, self.player2_position];
+        let mut scores = [0, 0];
+        let mut player = Player::One;
+        loop {
+            let (current, other) = match player {
+                Player::One => (0, 1),
+                Player::Two => (1, 0),
+            };
+            positions[current].move_pawn(die.roll_three_times());
+            scores[current] += positions[current].0;
+            if scores[current] >= self.deterministic.target_score {
+                return DeterministicOutcome {
+                    winner: player,
+                    winner_score: scores[current],
+                    loser_score: scores[other],
+                    rolls: die.rolls,
+                };
+            }
+            player = player.other();
+        }
+    }
+
+    /// Counts the universes won by each player, using the specified algorithm.
+    pub fn play_quantum(self, algorithm: Algorithm) -> QuantumOutcome {
+        let (player1_wins, player2_wins) = match algorithm {
+            Algorithm::Breadthwise => count_wins_breadthwise(self),
+            Algorithm::Memoized => count_wins_memoized(self),
+            Algorithm::Dense => count_wins_dense(self),
+        };
+        QuantumOutcome {
+            player1_wins,
+            player2_wins,
+        }
+    }
+
+    /// Plays the quantum game turn by turn, recording the number of universes won on each of them.
+    pub fn quantum_rounds(self) -> Vec<RoundStats> {
+        simulate_dense(self)
+    }
+
+    fn universe_state(&self) -> UniverseState {
+        UniverseState {
+            player1_position: self.player1_position,
+            player1_score: 0,
+            player2_position: self.player2_position,
+            player2_score: 0,
+        }
+    }
+
+    fn into_quantum(self) -> QuantumDiracDice {
+        let mut game = QuantumDiracDice {
+            simulated_universes: Default::default(),
+            rolls: self.quantum.roll_distribution(),
+            target_score: self.quantum.target_score,
+            p1_wins: 0,
+            p2_wins: 0,
+        };
+        game.simulated_universes.insert(self.universe_state(), 1);
+
+        game
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+struct UniverseState {
+    player1_position: Position,
+    player1_score: usize,
+    player2_position: Position,
+    player2_score: usize,
+}
+
+impl UniverseState {
+    fn dense_index(&self, target_score: usize) -> usize {
+        let player1 = (self.player1_position.0 - 1) * target_score + self.player1_score;
+        let player2 = (self.player2_position.0 - 1) * target_score + self.player2_score;
+        player1 * BOARD_SIZE * target_score + player2
+    }
+
+    fn from_dense_index(index: usize, target_score: usize) -> Self {
+        let (player1, player2) = (
+            index / (BOARD_SIZE * target_score),
+            index % (BOARD_SIZE * target_score),
+        );
+        UniverseState {
+            player1_position: Position(player1 / target_score + 1),
+            player1_score: player1 % target_score,
+            player2_position: Position(player2 / target_score + 1),
+            player2_score: player2 % target_score,
+        }
+    }
+
+    fn winner(&self, target_score: usize) -> Option<Player> {
+        if self.player1_score >= target_score {
+            Some(Player::One)
+        } else if self.player2_score >= target_score {
+            Some(Player::Two)
+        } else {
+            None
+        }
+    }
+
+    fn add_throw(&mut self, throw: usize, player: Player, target_score: usize) -> bool {
+        let (position, score) = match player {
+            Player::One => (&mut self.player1_position, &mut self.player1_score),
+            Player::Two => (&mut self.player2_position, &mut self.player2_score),
+        };
+        position.move_pawn(throw);
+        *score += position.0;
+        *score >= target_score
+    }
+}
+
+struct QuantumDiracDice {
+    simulated_universes: HashMap<UniverseState, u128>,
+    rolls: Vec<(usize, u128)>,
+    target_score: usize,
+
+    p1_wins: u128,
+    p2_wins: u128,
+}
+
+impl QuantumDiracDice {
+    fn play_round(&mut self, player: Player) -> bool {
+        for (universe_state, count) in mem::take(&mut self.simulated_universes) {
+            for &(throw, universes) in &self.rolls {
+                let mut next = universe_state;
+                if next.add_throw(throw, player, self.target_score) {
+                    match player {
+                        Player::One => self.p1_wins += universes * count,
+                        Player::Two => self.p2_wins += universes * count,
+                    }
+                } else {
+                    *self.simulated_universes.entry(next).or_default() += universes * count
+                }
+            }
+        }
+
+        self.simulated_universes.is_empty()
+    }
+}
+
+fn count_wins_memoized(game: DiracDice) -> (u128, u128) {
+    let target_score = game.quantum.target_score;
+    let rolls = game.quantum.roll_distribution();
+    let [player1_wins, player2_wins] = memoized_count(
+        (game.universe_state(), Player::One),
+        |(state, _)| {
+            state.winner(target_score).map(|winner| match winner {
+                Player::One => [1, 0],
+                Player::Two => [0, 1],
+            })
+        },
+        |&(state, to_move)| {
+            rolls.iter().map(move |&(throw, universes)| {
+                let mut next = state;
+                next.add_throw(throw, to_move, target_score);
+                ((next, to_move.other()), universes)
+            })
+        },
+    );
+    (player1_wins, player2_wins)
+}
+
+fn count_wins_breadthwise(game: DiracDice) -> (u128, u128) {
+    let mut quantum_game = game.into_quantum();
+    let mut player = Player::One;
+    while !quantum_game.play_round(player) {
+        player = player.other();
+    }
+    (quantum_game.p1_wins, quantum_game.p2_wins)
+}
+
+fn simulate_dense(game: DiracDice) -> Vec<RoundStats> {
+    let target_score = game.quantum.target_score;
+    let rolls = game.quantum.roll_distribution();
+    let mut universes = vec![0; game.quantum.dense_states()];
+    // universes after the current round, kept around to avoid reallocating it every round
+    let mut next_universes = vec![0; game.quantum.dense_states()];
+    universes[game.universe_state().dense_index(target_score)] = 1;
+
+    let mut rounds = Vec::new();
+    let mut player = Player::One;
+    loop {
+        let mut round = RoundStats {
+            player,
+            wins: 0,
+            unfinished: 0,
+        };
+        next_universes.fill(0);
+        for (index, &count) in universes.iter().enumerate() {
+            if count == 0 {
+                continue;
+            }
+            let state = UniverseState::from_dense_index(index, target_score);
+            for &(throw, splits) in &rolls {
+                let mut next = state;
+                if next.add_throw(throw, player, target_score) {
+                    round.wins += splits * count;
+                } else {
+                    next_universes[next.dense_index(target_score)] += splits * count;
+                    round.unfinished += splits * count;
+                }
+            }
+        }
+
+        rounds.push(round);
+        if round.unfinished == 0 {
+            return rounds;
+        }
+        mem::swap(&mut universes, &mut next_universes);
+        player = player.other();
+    }
+}
+
+fn count_wins_dense(game: DiracDice) -> (u128, u128) {
+    let mut wins = (0, 0);
+    for round in simulate_dense(game) {
+        match round.player {
+            Player::One => wins.0 += round.wins,
+            Player::Two => wins.1 += round.wins,
+        }
+    }
+    wins
+}
+
+pub fn part1(game: DiracDice) -> usize {
+    let outcome = game.play_deterministic();
+    outcome.rolls * outcome.loser_score
+}
+
+pub fn part2(game: DiracDice) -> u128 {
+    part2_using(game, Algorithm::default())
+}
+
+pub fn part2_using(game: DiracDice, algorithm: Algorithm) -> u128 {
+    game.play_quantum(algorithm).most_wins()
+}
+
+/// Plays Dirac Dice, using the chosen algorithm for the quantum game.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Day21 {
+    pub algorithm: Algorithm,
+}
+
+impl Solver for Day21 {
+    const DAY: u8 = 21;
+
+    type Input = DiracDice;
+    type Output1 = usize;
+    type Output2 = u128;
+
+    fn parse(&self, input_file: &Path) -> io::Result<Self::Input> {
+        read_parsed(input_file)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Self::Output1 {
+        part1(*input)
+    }
+
+    fn part2(&self, input: &Self::Input) -> Self::Output2 {
+        part2_using(*input, self.algorithm)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn moving_pawn() {
+        let mut pos1 = Position(4);
+        let mut pos2 = Position(8);
+
+        pos1.move_pawn(1 + 2 + 3);
+        assert_eq!(Position(10), pos1);
+
+        pos2.move_pawn(4 + 5 + 6);
+        assert_eq!(Position(3), pos2);
+
+        pos1.move_pawn(7 + 8 + 9);
+        assert_eq!(Position(4), pos1);
+
+        pos2.move_pawn(10 + 11 + 12);
+        assert_eq!(Position(6), pos2);
+
+        pos1.move_pawn(13 + 14 + 15);
+        assert_eq!(Position(6), pos1);
+
+        pos2.move_pawn(16 + 17 + 18);
+        assert_eq!(Position(7), pos2);
+
+        pos1.move_pawn(19 + 20 + 21);
+        assert_eq!(Position(6), pos1);
+
+        pos2.move_pawn(22 + 23 + 24);
+        assert_eq!(Position(6), pos2);
+    }
+
+    #[test]
+    fn part1_sample_input() {
+        let game = DiracDice::new((4, 8), Rules::DETERMINISTIC, Rules::QUANTUM);
+
+        let expected = 739785;
+        assert_eq!(expected, part1(game))
+    }
+
+    #[test]
+    fn part2_sample_input() {
+        let game = DiracDice::new((4, 8), Rules::DETERMINISTIC, Rules::QUANTUM);
+
+        let expected = 444356092776315;
+        assert_eq!(expected, part2(game))
+    }
+
+    #[test]
+    fn memoized_quantum_game() {
+        let game = DiracDice::new((4, 8), Rules::DETERMINISTIC, Rules::QUANTUM);
+
+        let expected = (444356092776315, 341960390180808);
+        assert_eq!(expected, count_wins_memoized(game));
+        assert_eq!(expected, count_wins_breadthwise(game));
+    }
+
+    #[test]
+    fn game_outcomes() {
+        let game = DiracDice::new((4, 8), Rules::DETERMINISTIC, Rules::QUANTUM);
+
+        let expected = DeterministicOutcome {
+            winner: Player::One,
+            winner_score: 1000,
+            loser_score: 745,
+            rolls: 993,
+        };
+        assert_eq!(expected, game.play_deterministic());
+
+        let expected = QuantumOutcome {
+            player1_wins: 444356092776315,
+            player2_wins: 341960390180808,
+        };
+        for algorithm in [
+            Algorithm::Breadthwise,
+            Algorithm::Memoized,
+            Algorithm::Dense,
+        ] {
+            assert_eq!(expected, game.play_quantum(algorithm));
+        }
+
+        let rounds = game.quantum_rounds();
+        // it takes at least three turns to score 21 points
+        assert!(rounds[..4].iter().all(|round| round.wins == 0));
+        assert_eq!(27, rounds[0].unfinished);
+        assert_eq!(Player::Two, rounds[1].player);
+        assert_eq!(0, rounds.last().unwrap().unfinished);
+        let player1_wins: u128 = rounds
+            .iter()
+            .filter(|round| round.player == Player::One)
+            .map(|round| round.wins)
+            .sum();
+        assert_eq!(expected.player1_wins, player1_wins);
+    }
+
+    #[test]
+    fn dense_quantum_game() {
+        for index in [0, 1, 20, 21, 209, 210, Rules::QUANTUM.dense_states() - 1] {
+            assert_eq!(
+                index,
+                UniverseState::from_dense_index(index, 21).dense_index(21)
+            );
+        }
+
+        for (p1, p2) in [(4, 8), (1, 1), (10, 3), (7, 10)] {
+            let game = DiracDice::new((p1, p2), Rules::DETERMINISTIC, Rules::QUANTUM);
+            assert_eq!(count_wins_breadthwise(game), count_wins_dense(game));
+        }
+    }
+
+    #[test]
+    fn custom_rules() {
+        assert_eq!(
+            vec![(3, 1), (4, 3), (5, 6), (6, 7), (7, 6), (8, 3), (9, 1)],
+            Rules::QUANTUM.roll_distribution()
+        );
+        assert_eq!(
+            vec![(3, 1)],
+            Rules {
+                die_sides: 1,
+                target_score: 21
+            }
+            .roll_distribution()
+        );
+
+        // the ten-sided die wraps around during the fourth turn
+        let rules = Rules {
+            die_sides: 10,
+            target_score: 20,
+        };
+        let expected = DeterministicOutcome {
+            winner: Player::One,
+            winner_score: 20,
+            loser_score: 9,
+            rolls: 15,
+        };
+        assert_eq!(
+            expected,
+            DiracDice::new((4, 8), rules, Rules::QUANTUM).play_deterministic()
+        );
+
+        for quantum in [
+            Rules {
+                die_sides: 2,
+                target_score: 15,
+            },
+            Rules {
+                die_sides: 4,
+                target_score: 10,
+            },
+            // the numbers of wins no longer fit in 64 bits
+            Rules {
+                die_sides: 3,
+                target_score: 28,
+            },
+        ] {
+            let game = DiracDice::new((4, 8), Rules::DETERMINISTIC, quantum);
+            let expected = game.play_quantum(Algorithm::Breadthwise);
+            assert_eq!(expected, game.play_quantum(Algorithm::Memoized));
+            assert_eq!(expected, game.play_quantum(Algorithm::Dense));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn quantum_game_without_target() {
+        let quantum = Rules {
+            die_sides: 3,
+            target_score: 0,
+        };
+        DiracDice::new((4, 8), Rules::DETERMINISTIC, quantum);
+    }
+
+    #[test]
+    fn parsing_game() {
+        let game: DiracDice = "Player 1 starting position: 4\nPlayer 2 starting position: 8"
+            .parse()
+            .unwrap();
+        assert_eq!(Position(4), game.player1_position);
+        assert_eq!(Position(8), game.player2_position);
+
+        assert_eq!(
+            Err(ParseError::MissingPlayer(2)),
+            "Player 1 starting position: 4"
+                .parse::<DiracDice>()
+                .map(|_| ())
+        );
+        assert_eq!(
+            Err(ParseError::InvalidStartingPosition {
+                player: 2,
+                line: "Player 2 starting position: 11".to_string()
+            }),
+            "Player 1 starting position: 4\nPlayer 2 starting position: 11"
+                .parse::<DiracDice>()
+                .map(|_| ())
+        );
+    }
+}
@@ -0,0 +1,55 @@
+// Copyright 2022 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use day22::{part2, Algorithm, Step};
+use utils::parallel::with_threads;
+
+const SYNTHETIC_STEPS: usize = 10_000;
+
+fn synthetic_steps(count: usize) -> Vec<Step> {
+    generators::day22::reboot_steps(0x2022_1222, count)
+        .lines()
+        .map(|line| line.parse().expect("generated an invalid step"))
+        .collect()
+}
+
+fn reboot_algorithms(c: &mut Criterion) {
+    let steps = synthetic_steps(SYNTHETIC_STEPS);
+
+    let mut group = c.benchmark_group("reboot");
+    group.sample_size(10);
+    group.bench_function("signed-volume", |b| {
+        b.iter(|| part2(black_box(&steps), Algorithm::SignedVolume))
+    });
+    group.bench_function("parallel", |b| {
+        b.iter(|| part2(black_box(&steps), Algorithm::Parallel))
+    });
+    group.bench_function("parallel-2-threads", |b| {
+        b.iter(|| {
+            with_threads(2, || part2(black_box(&steps), Algorithm::Parallel))
+                .expect("failed to build the thread pool")
+        })
+    });
+    group.bench_function("disjoint", |b| {
+        b.iter(|| part2(black_box(&steps), Algorithm::Disjoint))
+    });
+    group.bench_function("merged", |b| {
+        b.iter(|| part2(black_box(&steps), Algorithm::Merged))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, reboot_algorithms);
+criterion_main!(benches);
@@ -0,0 +1,130 @@
+// Copyright 2022 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::intersection::Intersection;
+use crate::Cuboid;
+use std::ops::RangeInclusive;
+
+pub(crate) trait Difference: Sized {
+    /// Disjoint parts of `self` that are not contained within `other`.
+    fn difference(&self, other: &Self) -> Vec<Self>;
+}
+
+fn non_empty(start: isize, end: isize) -> Option<RangeInclusive<isize>> {
+    (start <= end).then_some(start..=end)
+}
+
+impl Difference for Cuboid {
+    fn difference(&self, other: &Self) -> Vec<Self> {
+        let common = match self.intersection(other) {
+            Some(common) => common,
+            None => return vec![self.clone()],
+        };
+
+        // slabs on either side of the common part along the x axis span the entire cuboid,
+        // the ones along the y axis are restricted to the common x range, and finally
+        // the ones along the z axis are restricted to both the common x and y ranges
+        let mut remainder = Vec::with_capacity(6);
+        let x_slabs = [
+            non_empty(*self.x_range.start(), common.x_range.start() - 1),
+            non_empty(common.x_range.end() + 1, *self.x_range.end()),
+        ];
+        for x_range in x_slabs.into_iter().flatten() {
+            remainder.push(Cuboid {
+                x_range,
+                y_range: self.y_range.clone(),
+                z_range: self.z_range.clone(),
+            })
+        }
+
+        let y_slabs = [
+            non_empty(*self.y_range.start(), common.y_range.start() - 1),
+            non_empty(common.y_range.end() + 1, *self.y_range.end()),
+        ];
+        for y_range in y_slabs.into_iter().flatten() {
+            remainder.push(Cuboid {
+                x_range: common.x_range.clone(),
+                y_range,
+                z_range: self.z_range.clone(),
+            })
+        }
+
+        let z_slabs = [
+            non_empty(*self.z_range.start(), common.z_range.start() - 1),
+            non_empty(common.z_range.end() + 1, *self.z_range.end()),
+        ];
+        for z_range in z_slabs.into_iter().flatten() {
+            remainder.push(Cuboid {
+                x_range: common.x_range.clone(),
+                y_range: common.y_range.clone(),
+                z_range,
+            })
+        }
+
+        remainder
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cuboid(
+        x_range: RangeInclusive<isize>,
+        y_range: RangeInclusive<isize>,
+        z_range: RangeInclusive<isize>,
+    ) -> Cuboid {
+        Cuboid {
+            x_range,
+            y_range,
+            z_range,
+        }
+    }
+
+    #[test]
+    fn removing_the_centre() {
+        let outer = cuboid(0..=2, 0..=2, 0..=2);
+        let centre = cuboid(1..=1, 1..=1, 1..=1);
+
+        let remainder = outer.difference(&centre);
+        assert_eq!(6, remainder.len());
+        assert_eq!(26, remainder.iter().map(Cuboid::size).sum::<usize>());
+        for (i, a) in remainder.iter().enumerate() {
+            assert!(!a.intersects(&centre));
+            for b in &remainder[i + 1..] {
+                assert!(!a.intersects(b));
+            }
+        }
+    }
+
+    #[test]
+    fn partial_and_missing_overlaps() {
+        let a = cuboid(0..=9, 0..=9, 0..=9);
+
+        let disjoint = cuboid(20..=30, 0..=9, 0..=9);
+        assert_eq!(vec![a.clone()], a.difference(&disjoint));
+
+        let covering = cuboid(-5..=15, -5..=15, -5..=15);
+        assert!(a.difference(&covering).is_empty());
+
+        // removes the corner, leaving three slabs
+        let corner = cuboid(5..=15, 5..=15, 5..=15);
+        let remainder = a.difference(&corner);
+        assert_eq!(3, remainder.len());
+        assert_eq!(
+            1000 - 125,
+            remainder.iter().map(Cuboid::size).sum::<usize>()
+        );
+    }
+}
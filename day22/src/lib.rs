@@ -0,0 +1,825 @@
+// Copyright 2021-2022 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::csg::Difference;
+use crate::intersection::Intersection;
+use anyhow::Error;
+use itertools::iproduct;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::io;
+use std::ops::RangeInclusive;
+use std::path::Path;
+use std::str::FromStr;
+use utils::geometry::Vec3;
+use utils::input_read::read_parsed_line_input;
+use utils::parsing::parse_raw_range;
+use utils::Solver;
+
+mod csg;
+mod intersection;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Algorithm {
+    /// Keeps track of every cuboid turned on alongside the intersections that had been counted
+    /// more than once and thus have to be subtracted.
+    #[default]
+    SignedVolume,
+    /// Keeps track of the disjoint cuboids of the cubes that are on.
+    Disjoint,
+    /// Same as `SignedVolume`, but merges the identical cuboids into a single signed count,
+    /// so that the repeated intersections don't keep growing the lists.
+    Merged,
+    /// Same as `SignedVolume`, but intersects the new cuboids with the existing ones in parallel.
+    #[cfg(feature = "parallel")]
+    Parallel,
+}
+
+impl FromStr for Algorithm {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "signed-volume" => Ok(Algorithm::SignedVolume),
+            "disjoint" => Ok(Algorithm::Disjoint),
+            "merged" => Ok(Algorithm::Merged),
+            #[cfg(feature = "parallel")]
+            "parallel" => Ok(Algorithm::Parallel),
+            other => Err(format!("{other} is not a valid algorithm")),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Step {
+    pub on: bool,
+    pub cuboid: Cuboid,
+}
+
+impl FromStr for Step {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let on = s.starts_with("on");
+        let mut ranges = if on {
+            s.strip_prefix("on ")
+                .ok_or_else(|| Error::msg("incomplete input"))?
+                .split(',')
+        } else {
+            s.strip_prefix("off ")
+                .ok_or_else(|| Error::msg("incomplete input"))?
+                .split(',')
+        };
+
+        let x_range = parse_raw_range(
+            ranges
+                .next()
+                .ok_or_else(|| Error::msg("incomplete input"))?,
+        )?;
+        let y_range = parse_raw_range(
+            ranges
+                .next()
+                .ok_or_else(|| Error::msg("incomplete input"))?,
+        )?;
+        let z_range = parse_raw_range(
+            ranges
+                .next()
+                .ok_or_else(|| Error::msg("incomplete input"))?,
+        )?;
+
+        Ok(Step {
+            on,
+            cuboid: Cuboid {
+                x_range,
+                y_range,
+                z_range,
+            },
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Cuboid {
+    pub x_range: RangeInclusive<isize>,
+    pub y_range: RangeInclusive<isize>,
+    pub z_range: RangeInclusive<isize>,
+}
+
+impl Display for Cuboid {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "x={}..{},y={}..{},z={}..{}",
+            self.x_range.start(),
+            self.x_range.end(),
+            self.y_range.start(),
+            self.y_range.end(),
+            self.z_range.start(),
+            self.z_range.end()
+        )
+    }
+}
+
+impl Cuboid {
+    /// Enumerates every cube within the cuboid, as long as there are no more than `limit` of them.
+    /// Cuboids of the full reboot can contain upwards of 10^14 cubes, so they'd never fit in memory.
+    pub fn iter_cubes(&self, limit: usize) -> Option<impl Iterator<Item = Cube>> {
+        if self.size() > limit {
+            return None;
+        }
+
+        Some(
+            iproduct!(
+                self.x_range.clone(),
+                self.y_range.clone(),
+                self.z_range.clone()
+            )
+            .map(Into::into),
+        )
+    }
+
+    pub fn contains(&self, cube: Cube) -> bool {
+        self.x_range.contains(&cube.x)
+            && self.y_range.contains(&cube.y)
+            && self.z_range.contains(&cube.z)
+    }
+
+    pub fn size(&self) -> usize {
+        let x_size = (self.x_range.end() - self.x_range.start()).unsigned_abs() + 1;
+        let y_size = (self.y_range.end() - self.y_range.start()).unsigned_abs() + 1;
+        let z_size = (self.z_range.end() - self.z_range.start()).unsigned_abs() + 1;
+
+        x_size * y_size * z_size
+    }
+}
+
+/// Single cube of the reactor, identified by its coordinates.
+pub type Cube = Vec3;
+
+pub fn initialization_area() -> Cuboid {
+    Cuboid {
+        x_range: RangeInclusive::new(-50, 50),
+        y_range: RangeInclusive::new(-50, 50),
+        z_range: RangeInclusive::new(-50, 50),
+    }
+}
+
+pub trait Reactor {
+    fn run_initialization_step(&mut self, cuboid: Cuboid, on: bool);
+
+    fn active_region_size(&self) -> usize;
+
+    fn is_on(&self, cube: Cube) -> bool;
+
+    /// Number of cubes that are on within the specified region.
+    fn count_on_within(&self, region: &Cuboid) -> usize;
+}
+
+fn intersection_volume(cuboids: &[Cuboid], region: &Cuboid) -> usize {
+    cuboids
+        .iter()
+        .filter_map(|cuboid| cuboid.intersection(region))
+        .map(|intersection| intersection.size())
+        .sum()
+}
+
+// below this many cuboids the cost of splitting the work outweighs any gains
+#[cfg(feature = "parallel")]
+const PARALLEL_THRESHOLD: usize = 1024;
+
+#[derive(Debug, Default)]
+pub struct ReactorCore {
+    additive_cuboids: Vec<Cuboid>,
+    subtractive_cuboids: Vec<Cuboid>,
+    // running total of the cubes that are on, updated with each step
+    active: usize,
+    #[cfg(feature = "parallel")]
+    parallel: bool,
+}
+
+impl ReactorCore {
+    /// Creates reactor core that computes the intersections of each step on the rayon thread pool.
+    /// It only pays off once the cuboid lists grow into thousands of entries.
+    #[cfg(feature = "parallel")]
+    pub fn parallel() -> Self {
+        ReactorCore {
+            parallel: true,
+            ..Default::default()
+        }
+    }
+
+    // intersections of the cuboid with each of the existing ones, in the order of the existing cuboids
+    fn intersections(&self, cuboid: &Cuboid, existing: &[Cuboid]) -> Vec<Cuboid> {
+        #[cfg(feature = "parallel")]
+        if self.parallel && existing.len() >= PARALLEL_THRESHOLD {
+            return existing
+                .par_iter()
+                .filter_map(|other| cuboid.intersection(other))
+                .collect();
+        }
+
+        existing
+            .iter()
+            .filter_map(|other| cuboid.intersection(other))
+            .collect()
+    }
+}
+
+impl Reactor for ReactorCore {
+    fn is_on(&self, cube: Cube) -> bool {
+        let additive = self
+            .additive_cuboids
+            .iter()
+            .filter(|c| c.contains(cube))
+            .count();
+        let subtractive = self
+            .subtractive_cuboids
+            .iter()
+            .filter(|c| c.contains(cube))
+            .count();
+        additive > subtractive
+    }
+
+    fn count_on_within(&self, region: &Cuboid) -> usize {
+        intersection_volume(&self.additive_cuboids, region)
+            - intersection_volume(&self.subtractive_cuboids, region)
+    }
+
+    fn active_region_size(&self) -> usize {
+        self.active
+    }
+
+    fn run_initialization_step(&mut self, cuboid: Cuboid, on: bool) {
+        // since our input consists only of a double digit of cuboids, this naive approach is more than sufficient
+        let mut new_subs = self.intersections(&cuboid, &self.additive_cuboids);
+        let mut new_adds = self.intersections(&cuboid, &self.subtractive_cuboids);
+        let removed = new_subs.iter().map(Cuboid::size).sum::<usize>();
+        let restored = new_adds.iter().map(Cuboid::size).sum::<usize>();
+
+        self.additive_cuboids.append(&mut new_adds);
+        self.subtractive_cuboids.append(&mut new_subs);
+        // whatever was on within the cuboid is now off
+        self.active = self.active + restored - removed;
+
+        if on {
+            self.active += cuboid.size();
+            self.additive_cuboids.push(cuboid)
+        }
+    }
+}
+
+/// Reactor core represented by the disjoint cuboids of the cubes that are on.
+#[derive(Debug, Default)]
+pub struct DisjointReactorCore {
+    on_cuboids: Vec<Cuboid>,
+    active: usize,
+}
+
+impl Reactor for DisjointReactorCore {
+    fn is_on(&self, cube: Cube) -> bool {
+        self.on_cuboids.iter().any(|c| c.contains(cube))
+    }
+
+    fn count_on_within(&self, region: &Cuboid) -> usize {
+        intersection_volume(&self.on_cuboids, region)
+    }
+
+    fn active_region_size(&self) -> usize {
+        self.active
+    }
+
+    fn run_initialization_step(&mut self, cuboid: Cuboid, on: bool) {
+        // carve the new cuboid out of everything that's on, so that it could be added as a whole
+        let mut remaining = Vec::with_capacity(self.on_cuboids.len());
+        for existing in self.on_cuboids.drain(..) {
+            if let Some(intersection) = existing.intersection(&cuboid) {
+                self.active -= intersection.size();
+                remaining.extend(existing.difference(&cuboid))
+            } else {
+                remaining.push(existing)
+            }
+        }
+        self.on_cuboids = remaining;
+
+        if on {
+            self.active += cuboid.size();
+            self.on_cuboids.push(cuboid)
+        }
+    }
+}
+
+/// Reactor core represented by the distinct cuboids alongside the number of times each of them
+/// has been added, with the negative counts standing for the subtracted intersections.
+#[derive(Debug, Default)]
+pub struct MergedReactorCore {
+    cuboids: HashMap<Cuboid, isize>,
+    active: usize,
+}
+
+impl Reactor for MergedReactorCore {
+    fn is_on(&self, cube: Cube) -> bool {
+        self.cuboids
+            .iter()
+            .filter(|(cuboid, _)| cuboid.contains(cube))
+            .map(|(_, count)| count)
+            .sum::<isize>()
+            > 0
+    }
+
+    fn count_on_within(&self, region: &Cuboid) -> usize {
+        self.cuboids
+            .iter()
+            .filter_map(|(cuboid, count)| {
+                Some(cuboid.intersection(region)?.size() as isize * count)
+            })
+            .sum::<isize>() as usize
+    }
+
+    fn active_region_size(&self) -> usize {
+        self.active
+    }
+
+    fn run_initialization_step(&mut self, cuboid: Cuboid, on: bool) {
+        // whatever was on within the cuboid gets cancelled out by its intersections with
+        // the existing cuboids counted with the opposite sign
+        let mut changes: HashMap<Cuboid, isize> = HashMap::new();
+        for (existing, count) in &self.cuboids {
+            if let Some(intersection) = existing.intersection(&cuboid) {
+                *changes.entry(intersection).or_default() -= count;
+            }
+        }
+        if on {
+            *changes.entry(cuboid).or_default() += 1;
+        }
+
+        let mut active = self.active as isize;
+        for (cuboid, change) in changes {
+            active += cuboid.size() as isize * change;
+            match self.cuboids.entry(cuboid) {
+                Entry::Occupied(mut entry) => {
+                    *entry.get_mut() += change;
+                    if *entry.get() == 0 {
+                        entry.remove();
+                    }
+                }
+                Entry::Vacant(entry) => {
+                    if change != 0 {
+                        entry.insert(change);
+                    }
+                }
+            }
+        }
+        self.active = active as usize;
+    }
+}
+
+pub fn reboot(input: &[Step], algorithm: Algorithm) -> Box<dyn Reactor> {
+    reboot_steps(
+        input.iter().map(|step| (step.cuboid.clone(), step.on)),
+        algorithm,
+    )
+}
+
+/// Runs the steps clipped to the specified region, meaning only the queries within the region
+/// are going to be answered correctly.
+pub fn reboot_within(input: &[Step], region: &Cuboid, algorithm: Algorithm) -> Box<dyn Reactor> {
+    // filter out cuboids completely outside the area
+    let clipped = input
+        .iter()
+        .filter_map(|step| Some((region.intersection(&step.cuboid)?, step.on)));
+    reboot_steps(clipped, algorithm)
+}
+
+/// Yields the number of cubes that are on after each consecutive reboot step.
+pub fn active_counts(input: &[Step], algorithm: Algorithm) -> impl Iterator<Item = usize> + '_ {
+    let mut reactor_core = new_reactor(algorithm);
+    input.iter().map(move |step| {
+        reactor_core.run_initialization_step(step.cuboid.clone(), step.on);
+        reactor_core.active_region_size()
+    })
+}
+
+fn new_reactor(algorithm: Algorithm) -> Box<dyn Reactor> {
+    match algorithm {
+        Algorithm::SignedVolume => Box::<ReactorCore>::default(),
+        Algorithm::Disjoint => Box::<DisjointReactorCore>::default(),
+        Algorithm::Merged => Box::<MergedReactorCore>::default(),
+        #[cfg(feature = "parallel")]
+        Algorithm::Parallel => Box::new(ReactorCore::parallel()),
+    }
+}
+
+fn reboot_steps<I>(steps: I, algorithm: Algorithm) -> Box<dyn Reactor>
+where
+    I: Iterator<Item = (Cuboid, bool)>,
+{
+    let mut reactor_core = new_reactor(algorithm);
+    for (cuboid, on) in steps {
+        reactor_core.run_initialization_step(cuboid, on);
+    }
+
+    reactor_core
+}
+
+pub fn part1(input: &[Step], algorithm: Algorithm) -> usize {
+    let area = initialization_area();
+    reboot_within(input, &area, algorithm).count_on_within(&area)
+}
+
+pub fn part2(input: &[Step], algorithm: Algorithm) -> usize {
+    reboot(input, algorithm).active_region_size()
+}
+
+/// Reboots the reactor using the chosen algorithm.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Day22 {
+    pub algorithm: Algorithm,
+}
+
+impl Solver for Day22 {
+    const DAY: u8 = 22;
+
+    type Input = Vec<Step>;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(&self, input_file: &Path) -> io::Result<Self::Input> {
+        read_parsed_line_input(input_file)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Self::Output1 {
+        part1(input, self.algorithm)
+    }
+
+    fn part2(&self, input: &Self::Input) -> Self::Output2 {
+        part2(input, self.algorithm)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cuboid_size() {
+        assert_eq!(
+            Cuboid {
+                x_range: 1..=1,
+                y_range: 1..=1,
+                z_range: 1..=1
+            }
+            .size(),
+            1
+        );
+
+        assert_eq!(
+            Cuboid {
+                x_range: 1..=10,
+                y_range: 1..=10,
+                z_range: 1..=10
+            }
+            .size(),
+            1000
+        );
+
+        assert_eq!(
+            Cuboid {
+                x_range: -10..=-1,
+                y_range: -10..=-1,
+                z_range: -10..=-1
+            }
+            .size(),
+            1000
+        );
+    }
+
+    #[test]
+    fn cuboid_display() {
+        let step: Step = "on x=-54112..-39298,y=-85059..-49293,z=-27449..7877"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            "x=-54112..-39298,y=-85059..-49293,z=-27449..7877",
+            step.cuboid.to_string()
+        );
+    }
+
+    #[test]
+    fn bounded_cube_enumeration() {
+        let cuboid = Cuboid {
+            x_range: 1..=2,
+            y_range: 0..=0,
+            z_range: -1..=0,
+        };
+        let cubes = cuboid
+            .iter_cubes(4)
+            .unwrap()
+            .map(|cube| cube.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(vec!["1,0,-1", "1,0,0", "2,0,-1", "2,0,0"], cubes);
+        assert!(cuboid.iter_cubes(3).is_none());
+        assert!(initialization_area().iter_cubes(101 * 101 * 101).is_some());
+    }
+
+    #[test]
+    fn part1_small_example() {
+        let input = vec![
+            "on x=10..12,y=10..12,z=10..12".parse().unwrap(),
+            "on x=11..13,y=11..13,z=11..13".parse().unwrap(),
+            "off x=9..11,y=9..11,z=9..11".parse().unwrap(),
+            "on x=10..10,y=10..10,z=10..10".parse().unwrap(),
+        ];
+
+        let expected = 39;
+        assert_eq!(expected, part1(&input, Algorithm::SignedVolume));
+        assert_eq!(expected, part1(&input, Algorithm::Disjoint));
+        assert_eq!(expected, part1(&input, Algorithm::Merged));
+        #[cfg(feature = "parallel")]
+        assert_eq!(expected, part1(&input, Algorithm::Parallel))
+    }
+
+    #[test]
+    fn querying_the_reactor() {
+        let input: Vec<Step> = vec![
+            "on x=10..12,y=10..12,z=10..12".parse().unwrap(),
+            "on x=11..13,y=11..13,z=11..13".parse().unwrap(),
+            "off x=9..11,y=9..11,z=9..11".parse().unwrap(),
+            "on x=10..10,y=10..10,z=10..10".parse().unwrap(),
+        ];
+
+        let lower_corner = Cuboid {
+            x_range: 9..=11,
+            y_range: 9..=11,
+            z_range: 9..=11,
+        };
+        for algorithm in [
+            Algorithm::SignedVolume,
+            Algorithm::Disjoint,
+            Algorithm::Merged,
+            #[cfg(feature = "parallel")]
+            Algorithm::Parallel,
+        ] {
+            let reactor = reboot(&input, algorithm);
+            assert!(reactor.is_on((10, 10, 10).into()));
+            assert!(reactor.is_on((13, 13, 13).into()));
+            assert!(reactor.is_on((12, 10, 10).into()));
+            assert!(!reactor.is_on((11, 11, 11).into()));
+            assert!(!reactor.is_on((9, 9, 9).into()));
+
+            assert_eq!(39, reactor.count_on_within(&initialization_area()));
+            assert_eq!(1, reactor.count_on_within(&lower_corner));
+
+            let clipped = reboot_within(&input, &lower_corner, algorithm);
+            assert_eq!(1, clipped.count_on_within(&lower_corner));
+            assert_eq!(1, clipped.active_region_size());
+        }
+    }
+
+    #[test]
+    fn active_counts_after_each_step() {
+        let input: Vec<Step> = vec![
+            "on x=10..12,y=10..12,z=10..12".parse().unwrap(),
+            "on x=11..13,y=11..13,z=11..13".parse().unwrap(),
+            "off x=9..11,y=9..11,z=9..11".parse().unwrap(),
+            "on x=10..10,y=10..10,z=10..10".parse().unwrap(),
+        ];
+
+        for algorithm in [
+            Algorithm::SignedVolume,
+            Algorithm::Disjoint,
+            Algorithm::Merged,
+            #[cfg(feature = "parallel")]
+            Algorithm::Parallel,
+        ] {
+            assert_eq!(
+                vec![27, 46, 38, 39],
+                active_counts(&input, algorithm).collect::<Vec<_>>()
+            );
+        }
+    }
+
+    #[test]
+    fn part1_sample_input() {
+        let input = vec![
+            "on x=-20..26,y=-36..17,z=-47..7".parse().unwrap(),
+            "on x=-20..33,y=-21..23,z=-26..28".parse().unwrap(),
+            "on x=-22..28,y=-29..23,z=-38..16".parse().unwrap(),
+            "on x=-46..7,y=-6..46,z=-50..-1".parse().unwrap(),
+            "on x=-49..1,y=-3..46,z=-24..28".parse().unwrap(),
+            "on x=2..47,y=-22..22,z=-23..27".parse().unwrap(),
+            "on x=-27..23,y=-28..26,z=-21..29".parse().unwrap(),
+            "on x=-39..5,y=-6..47,z=-3..44".parse().unwrap(),
+            "on x=-30..21,y=-8..43,z=-13..34".parse().unwrap(),
+            "on x=-22..26,y=-27..20,z=-29..19".parse().unwrap(),
+            "off x=-48..-32,y=26..41,z=-47..-37".parse().unwrap(),
+            "on x=-12..35,y=6..50,z=-50..-2".parse().unwrap(),
+            "off x=-48..-32,y=-32..-16,z=-15..-5".parse().unwrap(),
+            "on x=-18..26,y=-33..15,z=-7..46".parse().unwrap(),
+            "off x=-40..-22,y=-38..-28,z=23..41".parse().unwrap(),
+            "on x=-16..35,y=-41..10,z=-47..6".parse().unwrap(),
+            "off x=-32..-23,y=11..30,z=-14..3".parse().unwrap(),
+            "on x=-49..-5,y=-3..45,z=-29..18".parse().unwrap(),
+            "off x=18..30,y=-20..-8,z=-3..13".parse().unwrap(),
+            "on x=-41..9,y=-7..43,z=-33..15".parse().unwrap(),
+            "on x=-54112..-39298,y=-85059..-49293,z=-27449..7877"
+                .parse()
+                .unwrap(),
+            "on x=967..23432,y=45373..81175,z=27513..53682"
+                .parse()
+                .unwrap(),
+        ];
+
+        let expected = 590784;
+        assert_eq!(expected, part1(&input, Algorithm::SignedVolume));
+        assert_eq!(expected, part1(&input, Algorithm::Disjoint));
+        assert_eq!(expected, part1(&input, Algorithm::Merged));
+        #[cfg(feature = "parallel")]
+        assert_eq!(expected, part1(&input, Algorithm::Parallel))
+    }
+
+    #[test]
+    fn part2_sample_input() {
+        let input = vec![
+            "on x=-5..47,y=-31..22,z=-19..33".parse().unwrap(),
+            "on x=-44..5,y=-27..21,z=-14..35".parse().unwrap(),
+            "on x=-49..-1,y=-11..42,z=-10..38".parse().unwrap(),
+            "on x=-20..34,y=-40..6,z=-44..1".parse().unwrap(),
+            "off x=26..39,y=40..50,z=-2..11".parse().unwrap(),
+            "on x=-41..5,y=-41..6,z=-36..8".parse().unwrap(),
+            "off x=-43..-33,y=-45..-28,z=7..25".parse().unwrap(),
+            "on x=-33..15,y=-32..19,z=-34..11".parse().unwrap(),
+            "off x=35..47,y=-46..-34,z=-11..5".parse().unwrap(),
+            "on x=-14..36,y=-6..44,z=-16..29".parse().unwrap(),
+            "on x=-57795..-6158,y=29564..72030,z=20435..90618"
+                .parse()
+                .unwrap(),
+            "on x=36731..105352,y=-21140..28532,z=16094..90401"
+                .parse()
+                .unwrap(),
+            "on x=30999..107136,y=-53464..15513,z=8553..71215"
+                .parse()
+                .unwrap(),
+            "on x=13528..83982,y=-99403..-27377,z=-24141..23996"
+                .parse()
+                .unwrap(),
+            "on x=-72682..-12347,y=18159..111354,z=7391..80950"
+                .parse()
+                .unwrap(),
+            "on x=-1060..80757,y=-65301..-20884,z=-103788..-16709"
+                .parse()
+                .unwrap(),
+            "on x=-83015..-9461,y=-72160..-8347,z=-81239..-26856"
+                .parse()
+                .unwrap(),
+            "on x=-52752..22273,y=-49450..9096,z=54442..119054"
+                .parse()
+                .unwrap(),
+            "on x=-29982..40483,y=-108474..-28371,z=-24328..38471"
+                .parse()
+                .unwrap(),
+            "on x=-4958..62750,y=40422..118853,z=-7672..65583"
+                .parse()
+                .unwrap(),
+            "on x=55694..108686,y=-43367..46958,z=-26781..48729"
+                .parse()
+                .unwrap(),
+            "on x=-98497..-18186,y=-63569..3412,z=1232..88485"
+                .parse()
+                .unwrap(),
+            "on x=-726..56291,y=-62629..13224,z=18033..85226"
+                .parse()
+                .unwrap(),
+            "on x=-110886..-34664,y=-81338..-8658,z=8914..63723"
+                .parse()
+                .unwrap(),
+            "on x=-55829..24974,y=-16897..54165,z=-121762..-28058"
+                .parse()
+                .unwrap(),
+            "on x=-65152..-11147,y=22489..91432,z=-58782..1780"
+                .parse()
+                .unwrap(),
+            "on x=-120100..-32970,y=-46592..27473,z=-11695..61039"
+                .parse()
+                .unwrap(),
+            "on x=-18631..37533,y=-124565..-50804,z=-35667..28308"
+                .parse()
+                .unwrap(),
+            "on x=-57817..18248,y=49321..117703,z=5745..55881"
+                .parse()
+                .unwrap(),
+            "on x=14781..98692,y=-1341..70827,z=15753..70151"
+                .parse()
+                .unwrap(),
+            "on x=-34419..55919,y=-19626..40991,z=39015..114138"
+                .parse()
+                .unwrap(),
+            "on x=-60785..11593,y=-56135..2999,z=-95368..-26915"
+                .parse()
+                .unwrap(),
+            "on x=-32178..58085,y=17647..101866,z=-91405..-8878"
+                .parse()
+                .unwrap(),
+            "on x=-53655..12091,y=50097..105568,z=-75335..-4862"
+                .parse()
+                .unwrap(),
+            "on x=-111166..-40997,y=-71714..2688,z=5609..50954"
+                .parse()
+                .unwrap(),
+            "on x=-16602..70118,y=-98693..-44401,z=5197..76897"
+                .parse()
+                .unwrap(),
+            "on x=16383..101554,y=4615..83635,z=-44907..18747"
+                .parse()
+                .unwrap(),
+            "off x=-95822..-15171,y=-19987..48940,z=10804..104439"
+                .parse()
+                .unwrap(),
+            "on x=-89813..-14614,y=16069..88491,z=-3297..45228"
+                .parse()
+                .unwrap(),
+            "on x=41075..99376,y=-20427..49978,z=-52012..13762"
+                .parse()
+                .unwrap(),
+            "on x=-21330..50085,y=-17944..62733,z=-112280..-30197"
+                .parse()
+                .unwrap(),
+            "on x=-16478..35915,y=36008..118594,z=-7885..47086"
+                .parse()
+                .unwrap(),
+            "off x=-98156..-27851,y=-49952..43171,z=-99005..-8456"
+                .parse()
+                .unwrap(),
+            "off x=2032..69770,y=-71013..4824,z=7471..94418"
+                .parse()
+                .unwrap(),
+            "on x=43670..120875,y=-42068..12382,z=-24787..38892"
+                .parse()
+                .unwrap(),
+            "off x=37514..111226,y=-45862..25743,z=-16714..54663"
+                .parse()
+                .unwrap(),
+            "off x=25699..97951,y=-30668..59918,z=-15349..69697"
+                .parse()
+                .unwrap(),
+            "off x=-44271..17935,y=-9516..60759,z=49131..112598"
+                .parse()
+                .unwrap(),
+            "on x=-61695..-5813,y=40978..94975,z=8655..80240"
+                .parse()
+                .unwrap(),
+            "off x=-101086..-9439,y=-7088..67543,z=33935..83858"
+                .parse()
+                .unwrap(),
+            "off x=18020..114017,y=-48931..32606,z=21474..89843"
+                .parse()
+                .unwrap(),
+            "off x=-77139..10506,y=-89994..-18797,z=-80..59318"
+                .parse()
+                .unwrap(),
+            "off x=8476..79288,y=-75520..11602,z=-96624..-24783"
+                .parse()
+                .unwrap(),
+            "on x=-47488..-1262,y=24338..100707,z=16292..72967"
+                .parse()
+                .unwrap(),
+            "off x=-84341..13987,y=2429..92914,z=-90671..-1318"
+                .parse()
+                .unwrap(),
+            "off x=-37810..49457,y=-71013..-7894,z=-105357..-13188"
+                .parse()
+                .unwrap(),
+            "off x=-27365..46395,y=31009..98017,z=15428..76570"
+                .parse()
+                .unwrap(),
+            "off x=-70369..-16548,y=22648..78696,z=-1892..86821"
+                .parse()
+                .unwrap(),
+            "on x=-53470..21291,y=-120233..-33476,z=-44150..38147"
+                .parse()
+                .unwrap(),
+            "off x=-93533..-4276,y=-16170..68771,z=-104985..-24507"
+                .parse()
+                .unwrap(),
+        ];
+
+        let expected = 2758514936282235;
+        assert_eq!(expected, part2(&input, Algorithm::SignedVolume));
+        assert_eq!(expected, part2(&input, Algorithm::Disjoint));
+        assert_eq!(expected, part2(&input, Algorithm::Merged));
+        #[cfg(feature = "parallel")]
+        assert_eq!(expected, part2(&input, Algorithm::Parallel))
+    }
+}
@@ -0,0 +1,41 @@
+// Copyright 2022 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use day23::{part1, part2, Algorithm, Burrow, FOLDED_DEPTH};
+
+const SAMPLE: &str = "#############
+#...........#
+###B#C#B#D###
+  #A#D#C#A#
+  #########";
+
+fn organize_algorithms(c: &mut Criterion) {
+    let burrow: Burrow<FOLDED_DEPTH> = SAMPLE.parse().expect("failed to parse the sample");
+
+    let mut group = c.benchmark_group("organize");
+    group.sample_size(10);
+    for algorithm in [Algorithm::Dijkstra, Algorithm::AStar] {
+        group.bench_function(format!("part1 {algorithm:?}"), |b| {
+            b.iter(|| part1(black_box(burrow.clone()), algorithm))
+        });
+        group.bench_function(format!("part2 {algorithm:?}"), |b| {
+            b.iter(|| part2(black_box(burrow.clone()), algorithm))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, organize_algorithms);
+criterion_main!(benches);
@@ -0,0 +1,585 @@
+// Copyright 2022 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::{self, Display, Formatter};
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+use utils::input_read::read_parsed;
+use utils::search::{astar, dijkstra};
+use utils::Solver;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Algorithm {
+    /// Dijkstra's algorithm operating directly on the burrow states.
+    Dijkstra,
+    /// A* using the energy needed for every amphipod to reach its room, ignoring all others,
+    /// as the heuristic, operating on the states packed into single integers.
+    #[default]
+    AStar,
+}
+
+impl FromStr for Algorithm {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "dijkstra" => Ok(Algorithm::Dijkstra),
+            "astar" => Ok(Algorithm::AStar),
+            other => Err(format!("{other} is not a valid algorithm")),
+        }
+    }
+}
+
+const HALLWAY_LENGTH: usize = 11;
+const ROOMS: usize = 4;
+
+/// Depth of the rooms as initially read off the diagram.
+pub const FOLDED_DEPTH: usize = 2;
+/// Depth of the rooms once the folded part of the diagram is revealed.
+pub const UNFOLDED_DEPTH: usize = 4;
+
+/// Rows hidden within the folded diagram, to be inserted between its two rows of rooms.
+const HIDDEN_ROWS: [[Amphipod; ROOMS]; UNFOLDED_DEPTH - FOLDED_DEPTH] = [
+    [
+        Amphipod::Desert,
+        Amphipod::Copper,
+        Amphipod::Bronze,
+        Amphipod::Amber,
+    ],
+    [
+        Amphipod::Desert,
+        Amphipod::Bronze,
+        Amphipod::Amber,
+        Amphipod::Copper,
+    ],
+];
+
+/// Number of bits used by every cell of the packed burrow, enough for an empty space
+/// or any of the amphipods.
+const PACKED_CELL_BITS: usize = 3;
+const PACKED_CELL_MASK: u128 = (1 << PACKED_CELL_BITS) - 1;
+
+/// Hallway positions directly outside each of the rooms. Amphipods never stop on them.
+const ROOM_ENTRANCES: [usize; ROOMS] = [2, 4, 6, 8];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Amphipod {
+    Amber,
+    Bronze,
+    Copper,
+    Desert,
+}
+
+impl Amphipod {
+    const ALL: [Amphipod; ROOMS] = [
+        Amphipod::Amber,
+        Amphipod::Bronze,
+        Amphipod::Copper,
+        Amphipod::Desert,
+    ];
+
+    fn from_char(c: char) -> Option<Self> {
+        match c {
+            'A' => Some(Amphipod::Amber),
+            'B' => Some(Amphipod::Bronze),
+            'C' => Some(Amphipod::Copper),
+            'D' => Some(Amphipod::Desert),
+            _ => None,
+        }
+    }
+
+    fn as_char(&self) -> char {
+        match self {
+            Amphipod::Amber => 'A',
+            Amphipod::Bronze => 'B',
+            Amphipod::Copper => 'C',
+            Amphipod::Desert => 'D',
+        }
+    }
+
+    /// Energy required to move the amphipod by a single step.
+    pub fn energy(&self) -> usize {
+        match self {
+            Amphipod::Amber => 1,
+            Amphipod::Bronze => 10,
+            Amphipod::Copper => 100,
+            Amphipod::Desert => 1000,
+        }
+    }
+
+    /// Index of the room the amphipod has to end up in.
+    fn target_room(&self) -> usize {
+        *self as usize
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    MissingRow(usize),
+    InvalidCell {
+        row: usize,
+        column: usize,
+        found: Option<char>,
+    },
+    UnbalancedAmphipods(Amphipod),
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::MissingRow(row) => write!(f, "the diagram is missing row {row}"),
+            ParseError::InvalidCell {
+                row,
+                column,
+                found: Some(found),
+            } => write!(
+                f,
+                "{found} at row {row}, column {column} is neither an amphipod nor an empty space"
+            ),
+            ParseError::InvalidCell {
+                row,
+                column,
+                found: None,
+            } => write!(f, "row {row} ends before reaching column {column}"),
+            ParseError::UnbalancedAmphipods(amphipod) => write!(
+                f,
+                "amphipods of type {} wouldn't fill exactly one room",
+                amphipod.as_char()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// State of the burrow with rooms of the specified depth, i.e. positions of all the amphipods.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Burrow<const DEPTH: usize> {
+    hallway: [Option<Amphipod>; HALLWAY_LENGTH],
+    // each room is ordered from the slot closest to the hallway
+    rooms: [[Option<Amphipod>; DEPTH]; ROOMS],
+}
+
+fn parse_cell(lines: &[&str], row: usize, column: usize) -> Result<Option<Amphipod>, ParseError> {
+    let line = lines.get(row).ok_or(ParseError::MissingRow(row))?;
+    match line.chars().nth(column) {
+        Some('.') => Ok(None),
+        found => found
+            .and_then(Amphipod::from_char)
+            .map(Some)
+            .ok_or(ParseError::InvalidCell { row, column, found }),
+    }
+}
+
+impl<const DEPTH: usize> FromStr for Burrow<DEPTH> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // #############
+        // #...........#
+        // ###B#C#B#D###
+        //   #A#D#C#A#
+        //   #########
+        let lines = s.lines().collect::<Vec<_>>();
+
+        let mut hallway = [None; HALLWAY_LENGTH];
+        for (position, cell) in hallway.iter_mut().enumerate() {
+            *cell = parse_cell(&lines, 1, position + 1)?;
+        }
+
+        let mut rooms = [[None; DEPTH]; ROOMS];
+        for (room, entrance) in rooms.iter_mut().zip(ROOM_ENTRANCES) {
+            for (depth, cell) in room.iter_mut().enumerate() {
+                *cell = parse_cell(&lines, depth + 2, entrance + 1)?;
+            }
+        }
+
+        let burrow = Burrow { hallway, rooms };
+        for amphipod in Amphipod::ALL {
+            if burrow.amphipods().filter(|&a| a == amphipod).count() != DEPTH {
+                return Err(ParseError::UnbalancedAmphipods(amphipod));
+            }
+        }
+
+        Ok(burrow)
+    }
+}
+
+impl<const DEPTH: usize> Display for Burrow<DEPTH> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let cell = |amphipod: &Option<Amphipod>| amphipod.map(|a| a.as_char()).unwrap_or('.');
+
+        writeln!(f, "{}", "#".repeat(HALLWAY_LENGTH + 2))?;
+        write!(f, "#")?;
+        for amphipod in &self.hallway {
+            write!(f, "{}", cell(amphipod))?;
+        }
+        writeln!(f, "#")?;
+
+        for depth in 0..DEPTH {
+            // the outer walls only extend to the sides of the top row
+            let (left, right) = if depth == 0 {
+                ("###", "##")
+            } else {
+                ("  #", "")
+            };
+            write!(f, "{left}")?;
+            for room in &self.rooms {
+                write!(f, "{}#", cell(&room[depth]))?;
+            }
+            writeln!(f, "{right}")?;
+        }
+        write!(f, "  {}", "#".repeat(HALLWAY_LENGTH - 2))
+    }
+}
+
+impl Burrow<FOLDED_DEPTH> {
+    /// Reveals the folded part of the diagram, placing the hidden amphipods in the middle
+    /// of each room.
+    pub fn unfold(&self) -> Burrow<UNFOLDED_DEPTH> {
+        let mut rooms = [[None; UNFOLDED_DEPTH]; ROOMS];
+        for (index, room) in rooms.iter_mut().enumerate() {
+            room[0] = self.rooms[index][0];
+            for (depth, row) in HIDDEN_ROWS.iter().enumerate() {
+                room[depth + 1] = Some(row[index]);
+            }
+            room[UNFOLDED_DEPTH - 1] = self.rooms[index][FOLDED_DEPTH - 1];
+        }
+
+        Burrow {
+            hallway: self.hallway,
+            rooms,
+        }
+    }
+}
+
+/// Burrow state with each cell, hallway first followed by the rooms, stored within
+/// consecutive `PACKED_CELL_BITS` bits, making it far cheaper to hash and to store.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct PackedBurrow(u128);
+
+impl<const DEPTH: usize> Burrow<DEPTH> {
+    const PACKED_BITS: usize = (HALLWAY_LENGTH + ROOMS * DEPTH) * PACKED_CELL_BITS;
+
+    fn cells(&self) -> impl Iterator<Item = &Option<Amphipod>> + '_ {
+        self.hallway.iter().chain(self.rooms.iter().flatten())
+    }
+
+    fn pack(&self) -> PackedBurrow {
+        // make sure at compile time all the cells fit within the packed representation
+        const { assert!(Self::PACKED_BITS <= u128::BITS as usize) };
+
+        let packed = self.cells().enumerate().fold(0, |packed, (index, cell)| {
+            let value = cell
+                .map(|amphipod| amphipod as u128 + 1)
+                .unwrap_or_default();
+            packed | value << (index * PACKED_CELL_BITS)
+        });
+        PackedBurrow(packed)
+    }
+
+    fn unpack(PackedBurrow(mut packed): PackedBurrow) -> Self {
+        let mut next_cell = || {
+            let value = (packed & PACKED_CELL_MASK) as usize;
+            packed >>= PACKED_CELL_BITS;
+            value.checked_sub(1).map(|index| Amphipod::ALL[index])
+        };
+
+        let mut burrow = Burrow {
+            hallway: [None; HALLWAY_LENGTH],
+            rooms: [[None; DEPTH]; ROOMS],
+        };
+        for cell in burrow.hallway.iter_mut() {
+            *cell = next_cell();
+        }
+        for cell in burrow.rooms.iter_mut().flatten() {
+            *cell = next_cell();
+        }
+        burrow
+    }
+
+    /// Energy needed to move every amphipod into its room if all others didn't exist.
+    /// Since they do exist, the real cost can only be higher, making it an admissible heuristic.
+    fn unobstructed_energy(&self) -> usize {
+        let mut energy = 0;
+        for (position, amphipod) in self.hallway.iter().enumerate() {
+            if let Some(amphipod) = amphipod {
+                let entrance = ROOM_ENTRANCES[amphipod.target_room()];
+                energy += (position.abs_diff(entrance) + 1) * amphipod.energy();
+            }
+        }
+
+        for (room, entrance) in ROOM_ENTRANCES.iter().enumerate() {
+            for (depth, cell) in self.rooms[room].iter().enumerate() {
+                let Some(amphipod) = cell else { continue };
+                let target = amphipod.target_room();
+                let steps = if target != room {
+                    depth + 1 + entrance.abs_diff(ROOM_ENTRANCES[target]) + 1
+                } else if self.rooms[room][depth + 1..]
+                    .iter()
+                    .any(|below| *below != Some(*amphipod))
+                {
+                    // it has to make way for whoever doesn't belong underneath and come back
+                    depth + 1 + 2 + 1
+                } else {
+                    0
+                };
+                energy += steps * amphipod.energy();
+            }
+        }
+
+        energy
+    }
+
+    fn amphipods(&self) -> impl Iterator<Item = Amphipod> + '_ {
+        self.cells().filter_map(|&cell| cell)
+    }
+
+    pub fn is_organized(&self) -> bool {
+        self.rooms.iter().enumerate().all(|(index, room)| {
+            room.iter()
+                .all(|cell| cell.map(|a| a.target_room()) == Some(index))
+        })
+    }
+
+    // a room can only be entered once it no longer contains any amphipods that don't belong there
+    fn accepts_amphipods(&self, room: usize) -> bool {
+        self.rooms[room]
+            .iter()
+            .flatten()
+            .all(|amphipod| amphipod.target_room() == room)
+    }
+
+    // whether the hallway between the two positions, excluding the starting one, is empty
+    fn is_hallway_clear(&self, from: usize, to: usize) -> bool {
+        let range = if from < to {
+            from + 1..=to
+        } else {
+            to..=from - 1
+        };
+        self.hallway[range].iter().all(Option::is_none)
+    }
+
+    /// All the states reachable with a single move of any amphipod, alongside the energy it costs.
+    /// An amphipod can either leave its room to stop somewhere in the hallway or go from the
+    /// hallway straight to its destination room.
+    fn moves(&self) -> Vec<(Self, usize)> {
+        let mut moves = Vec::new();
+
+        for (position, amphipod) in self.hallway.iter().enumerate() {
+            let Some(amphipod) = amphipod else { continue };
+            let room = amphipod.target_room();
+            let entrance = ROOM_ENTRANCES[room];
+            if !self.accepts_amphipods(room) || !self.is_hallway_clear(position, entrance) {
+                continue;
+            }
+            // always go as deep as possible
+            let Some(depth) = self.rooms[room].iter().rposition(Option::is_none) else {
+                continue;
+            };
+
+            let steps = position.abs_diff(entrance) + depth + 1;
+            let mut next = self.clone();
+            next.hallway[position] = None;
+            next.rooms[room][depth] = Some(*amphipod);
+            moves.push((next, steps * amphipod.energy()));
+        }
+
+        for (room, entrance) in ROOM_ENTRANCES.iter().copied().enumerate() {
+            if self.accepts_amphipods(room) {
+                // nobody in there has any reason to leave
+                continue;
+            }
+            let Some(depth) = self.rooms[room].iter().position(Option::is_some) else {
+                continue;
+            };
+            let amphipod = self.rooms[room][depth].unwrap();
+
+            for position in 0..HALLWAY_LENGTH {
+                if ROOM_ENTRANCES.contains(&position) || !self.is_hallway_clear(entrance, position)
+                {
+                    continue;
+                }
+
+                let steps = depth + 1 + position.abs_diff(entrance);
+                let mut next = self.clone();
+                next.rooms[room][depth] = None;
+                next.hallway[position] = Some(amphipod);
+                moves.push((next, steps * amphipod.energy()));
+            }
+        }
+
+        moves
+    }
+
+    /// Finds the sequence of states that organizes the amphipods using the least amount of energy.
+    pub fn organize(&self, algorithm: Algorithm) -> Option<(Vec<Self>, usize)> {
+        match algorithm {
+            Algorithm::Dijkstra => dijkstra(self, Burrow::moves, Burrow::is_organized),
+            Algorithm::AStar => {
+                let (path, energy) = astar(
+                    &self.pack(),
+                    |&packed| {
+                        Burrow::<DEPTH>::unpack(packed)
+                            .moves()
+                            .into_iter()
+                            .map(|(next, energy)| (next.pack(), energy))
+                    },
+                    |&packed| Burrow::<DEPTH>::unpack(packed).unobstructed_energy(),
+                    |&packed| Burrow::<DEPTH>::unpack(packed).is_organized(),
+                )?;
+                Some((path.into_iter().map(Burrow::unpack).collect(), energy))
+            }
+        }
+    }
+}
+
+fn minimum_energy<const DEPTH: usize>(burrow: &Burrow<DEPTH>, algorithm: Algorithm) -> usize {
+    burrow
+        .organize(algorithm)
+        .map(|(_, energy)| energy)
+        .expect("the amphipods can't be organized")
+}
+
+pub fn part1(burrow: Burrow<FOLDED_DEPTH>, algorithm: Algorithm) -> usize {
+    minimum_energy(&burrow, algorithm)
+}
+
+pub fn part2(burrow: Burrow<FOLDED_DEPTH>, algorithm: Algorithm) -> usize {
+    minimum_energy(&burrow.unfold(), algorithm)
+}
+
+/// Organizes the amphipods using the chosen search algorithm.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Day23 {
+    pub algorithm: Algorithm,
+}
+
+impl Solver for Day23 {
+    const DAY: u8 = 23;
+
+    type Input = Burrow<FOLDED_DEPTH>;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(&self, input_file: &Path) -> io::Result<Self::Input> {
+        read_parsed(input_file)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Self::Output1 {
+        part1(input.clone(), self.algorithm)
+    }
+
+    fn part2(&self, input: &Self::Input) -> Self::Output2 {
+        part2(input.clone(), self.algorithm)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "#############
+#...........#
+###B#C#B#D###
+  #A#D#C#A#
+  #########";
+
+    #[test]
+    fn parsing_and_displaying_the_diagram() {
+        let burrow: Burrow<FOLDED_DEPTH> = SAMPLE.parse().unwrap();
+        assert_eq!(SAMPLE, burrow.to_string());
+        assert!(!burrow.is_organized());
+
+        assert_eq!(
+            Err(ParseError::MissingRow(3)),
+            "#############\n#...........#\n###B#C#B#D###".parse::<Burrow<FOLDED_DEPTH>>()
+        );
+        assert_eq!(
+            Err(ParseError::InvalidCell {
+                row: 2,
+                column: 5,
+                found: Some('E')
+            }),
+            SAMPLE.replacen("C", "E", 1).parse::<Burrow<FOLDED_DEPTH>>()
+        );
+        assert_eq!(
+            Err(ParseError::UnbalancedAmphipods(Amphipod::Amber)),
+            SAMPLE.replacen("A", "B", 1).parse::<Burrow<FOLDED_DEPTH>>()
+        );
+    }
+
+    #[test]
+    fn unfolding_the_diagram() {
+        let burrow: Burrow<FOLDED_DEPTH> = SAMPLE.parse().unwrap();
+        let unfolded = "#############
+#...........#
+###B#C#B#D###
+  #D#C#B#A#
+  #D#B#A#C#
+  #A#D#C#A#
+  #########";
+        assert_eq!(unfolded, burrow.unfold().to_string());
+        assert_eq!(Ok(burrow.unfold()), unfolded.parse());
+    }
+
+    #[test]
+    fn packing_the_burrow() {
+        let burrow: Burrow<FOLDED_DEPTH> = SAMPLE.parse().unwrap();
+        let (next, _) = burrow.moves().pop().unwrap();
+        assert_eq!(burrow, Burrow::unpack(burrow.pack()));
+        assert_eq!(next, Burrow::unpack(next.pack()));
+        assert_ne!(burrow.pack(), next.pack());
+
+        let unfolded = burrow.unfold();
+        assert_eq!(unfolded, Burrow::unpack(unfolded.pack()));
+    }
+
+    #[test]
+    fn heuristic_never_overestimates() {
+        let burrow: Burrow<FOLDED_DEPTH> = SAMPLE.parse().unwrap();
+        let (path, energy) = burrow.organize(Algorithm::Dijkstra).unwrap();
+        assert_eq!(0, path.last().unwrap().unobstructed_energy());
+
+        let mut spent = 0;
+        for (state, next) in path.iter().zip(path.iter().skip(1)) {
+            assert!(state.unobstructed_energy() <= energy - spent);
+            spent += state
+                .moves()
+                .into_iter()
+                .find(|(candidate, _)| candidate == next)
+                .map(|(_, cost)| cost)
+                .unwrap();
+        }
+    }
+
+    #[test]
+    fn part1_sample_input() {
+        let burrow: Burrow<FOLDED_DEPTH> = SAMPLE.parse().unwrap();
+
+        let expected = 12521;
+        assert_eq!(expected, part1(burrow.clone(), Algorithm::Dijkstra));
+        assert_eq!(expected, part1(burrow, Algorithm::AStar))
+    }
+
+    #[test]
+    fn part2_sample_input() {
+        let burrow: Burrow<FOLDED_DEPTH> = SAMPLE.parse().unwrap();
+
+        let expected = 44169;
+        assert_eq!(expected, part2(burrow.clone(), Algorithm::Dijkstra));
+        assert_eq!(expected, part2(burrow, Algorithm::AStar))
+    }
+}
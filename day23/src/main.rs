@@ -0,0 +1,24 @@
+// Copyright 2022 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use day23::{Algorithm, Day23};
+use utils::cli::parsed_flag_value;
+use utils::execute;
+
+#[cfg(not(tarpaulin))]
+fn main() {
+    // e.g. `cargo run --release -- --algorithm dijkstra`
+    let algorithm: Algorithm = parsed_flag_value("--algorithm").unwrap_or_default();
+    execute(&Day23 { algorithm }, "input")
+}
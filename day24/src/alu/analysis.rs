@@ -0,0 +1,296 @@
+// Copyright 2022 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::alu::{Alu, Instruction, Operand, Variable};
+use std::fmt::{Display, Formatter};
+
+/// Inclusive range of values a variable could possibly hold.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) struct Interval {
+    pub(crate) min: isize,
+    pub(crate) max: isize,
+}
+
+impl Display for Interval {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{}, {}]", self.min, self.max)
+    }
+}
+
+impl Interval {
+    /// Range of every digit of the model number.
+    pub(crate) const DIGIT: Interval = Interval::new(1, 9);
+
+    pub(crate) const fn new(min: isize, max: isize) -> Self {
+        Interval { min, max }
+    }
+
+    pub(crate) const fn exact(value: isize) -> Self {
+        Interval::new(value, value)
+    }
+
+    pub(crate) fn contains(&self, value: isize) -> bool {
+        self.min <= value && value <= self.max
+    }
+
+    fn is_exact(&self) -> bool {
+        self.min == self.max
+    }
+
+    // smallest interval containing all of the values
+    fn spanning(values: [isize; 4]) -> Self {
+        Interval::new(
+            values.into_iter().min().unwrap(),
+            values.into_iter().max().unwrap(),
+        )
+    }
+
+    fn add(self, other: Self) -> Self {
+        Interval::new(
+            self.min.saturating_add(other.min),
+            self.max.saturating_add(other.max),
+        )
+    }
+
+    fn mul(self, other: Self) -> Self {
+        Interval::spanning([
+            self.min.saturating_mul(other.min),
+            self.min.saturating_mul(other.max),
+            self.max.saturating_mul(other.min),
+            self.max.saturating_mul(other.max),
+        ])
+    }
+
+    // the division truncates towards zero and is thus monotonic in both operands
+    // as long as the divisor doesn't change its sign
+    fn div(self, divisor: Self) -> Option<Self> {
+        let quotients = |divisor: Interval| {
+            Interval::spanning([
+                self.min / divisor.min,
+                self.min / divisor.max,
+                self.max / divisor.min,
+                self.max / divisor.max,
+            ])
+        };
+
+        // dividing by zero halts the program, so those paths never produce any value
+        let negative =
+            (divisor.min < 0).then(|| quotients(Interval::new(divisor.min, divisor.max.min(-1))));
+        let positive =
+            (divisor.max > 0).then(|| quotients(Interval::new(divisor.min.max(1), divisor.max)));
+        match (negative, positive) {
+            (Some(negative), Some(positive)) => Some(negative.union(positive)),
+            (negative, positive) => negative.or(positive),
+        }
+    }
+
+    // the program halts for negative dividends or non-positive divisors
+    fn rem(self, divisor: Self) -> Option<Self> {
+        if self.max < 0 || divisor.max <= 0 {
+            return None;
+        }
+        let dividend = Interval::new(self.min.max(0), self.max);
+        let divisor = Interval::new(divisor.min.max(1), divisor.max);
+
+        if dividend.max < divisor.min {
+            // the dividend is always smaller than the divisor
+            Some(dividend)
+        } else if divisor.is_exact() && dividend.min / divisor.min == dividend.max / divisor.min {
+            // the dividend doesn't wrap around
+            Some(Interval::new(
+                dividend.min % divisor.min,
+                dividend.max % divisor.min,
+            ))
+        } else {
+            Some(Interval::new(0, dividend.max.min(divisor.max - 1)))
+        }
+    }
+
+    fn eql(self, other: Self) -> Self {
+        if self.is_exact() && self == other {
+            Interval::exact(1)
+        } else if self.max < other.min || other.max < self.min {
+            Interval::exact(0)
+        } else {
+            Interval::new(0, 1)
+        }
+    }
+
+    fn union(self, other: Self) -> Self {
+        Interval::new(self.min.min(other.min), self.max.max(other.max))
+    }
+}
+
+/// Reasons the analysed program is guaranteed to halt before finishing.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) struct InvalidOperation {
+    pub(crate) instruction: usize,
+}
+
+/// Possible values of each of the ALU variables at some point of the program.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) struct Ranges {
+    pub(crate) w: Interval,
+    pub(crate) x: Interval,
+    pub(crate) y: Interval,
+    pub(crate) z: Interval,
+}
+
+impl Default for Ranges {
+    fn default() -> Self {
+        Ranges {
+            w: Interval::exact(0),
+            x: Interval::exact(0),
+            y: Interval::exact(0),
+            z: Interval::exact(0),
+        }
+    }
+}
+
+impl From<Alu> for Ranges {
+    fn from(alu: Alu) -> Self {
+        Ranges {
+            w: Interval::exact(alu.get(Variable::W)),
+            x: Interval::exact(alu.get(Variable::X)),
+            y: Interval::exact(alu.get(Variable::Y)),
+            z: Interval::exact(alu.get(Variable::Z)),
+        }
+    }
+}
+
+impl Ranges {
+    pub(crate) fn with_z(z: Interval) -> Self {
+        Ranges {
+            z,
+            ..Default::default()
+        }
+    }
+
+    pub(crate) fn get(&self, variable: Variable) -> Interval {
+        match variable {
+            Variable::W => self.w,
+            Variable::X => self.x,
+            Variable::Y => self.y,
+            Variable::Z => self.z,
+        }
+    }
+
+    fn get_mut(&mut self, variable: Variable) -> &mut Interval {
+        match variable {
+            Variable::W => &mut self.w,
+            Variable::X => &mut self.x,
+            Variable::Y => &mut self.y,
+            Variable::Z => &mut self.z,
+        }
+    }
+
+    fn operand(&self, operand: Operand) -> Interval {
+        match operand {
+            Operand::Var(variable) => self.get(variable),
+            Operand::Number(value) => Interval::exact(value),
+        }
+    }
+
+    /// Propagates the ranges through the instructions, with each input being any value
+    /// of the corresponding interval. As the analysis over-approximates, any value outside
+    /// the resultant ranges is provably impossible.
+    /// When all the ranges and inputs are exact, this is equivalent to executing the program.
+    pub(crate) fn propagate<I>(
+        mut self,
+        instructions: &[Instruction],
+        inputs: I,
+    ) -> Result<Self, InvalidOperation>
+    where
+        I: IntoIterator<Item = Interval>,
+    {
+        let mut inputs = inputs.into_iter();
+        for (index, instruction) in instructions.iter().enumerate() {
+            let invalid = InvalidOperation { instruction: index };
+            match *instruction {
+                Instruction::Input(a) => *self.get_mut(a) = inputs.next().ok_or(invalid)?,
+                Instruction::Add(a, b) => *self.get_mut(a) = self.get(a).add(self.operand(b)),
+                Instruction::Mul(a, b) => *self.get_mut(a) = self.get(a).mul(self.operand(b)),
+                Instruction::Div(a, b) => {
+                    *self.get_mut(a) = self.get(a).div(self.operand(b)).ok_or(invalid)?
+                }
+                Instruction::Mod(a, b) => {
+                    *self.get_mut(a) = self.get(a).rem(self.operand(b)).ok_or(invalid)?
+                }
+                Instruction::Equal(a, b) => *self.get_mut(a) = self.get(a).eql(self.operand(b)),
+            }
+        }
+
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(program: &str) -> Vec<Instruction> {
+        program.lines().map(|line| line.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn interval_arithmetic() {
+        let digit = Interval::DIGIT;
+        assert_eq!(Interval::new(2, 18), digit.add(digit));
+        assert_eq!(Interval::new(-9, 81), digit.mul(Interval::new(-1, 9)));
+        assert_eq!(Some(Interval::new(-9, 9)), digit.div(Interval::new(-1, 1)));
+        assert_eq!(None, digit.div(Interval::exact(0)));
+        assert_eq!(Some(Interval::new(0, 4)), digit.rem(Interval::exact(5)));
+        assert_eq!(Some(digit), digit.rem(Interval::exact(26)));
+        assert_eq!(
+            Some(Interval::new(1, 3)),
+            Interval::new(27, 29).rem(Interval::exact(26))
+        );
+        assert_eq!(
+            Some(Interval::new(0, 25)),
+            Interval::new(25, 27).rem(Interval::exact(26))
+        );
+        assert_eq!(None, Interval::exact(-1).rem(Interval::exact(26)));
+        assert_eq!(Interval::new(0, 1), digit.eql(Interval::exact(5)));
+        assert_eq!(Interval::exact(0), digit.eql(Interval::exact(10)));
+        assert_eq!(
+            Interval::exact(1),
+            Interval::exact(3).eql(Interval::exact(3))
+        );
+    }
+
+    #[test]
+    fn propagating_through_program() {
+        // z = 3 * w + x where x is the second input
+        let program = parse("inp w\ninp x\nmul w 3\nadd w x\nadd z w");
+        let ranges = Ranges::default()
+            .propagate(&program, [Interval::DIGIT, Interval::new(0, 1)])
+            .unwrap();
+        assert_eq!(Interval::new(3, 28), ranges.z);
+        assert_eq!(Interval::new(0, 1), ranges.x);
+
+        let exact = Ranges::default()
+            .propagate(&program, [Interval::exact(4), Interval::exact(1)])
+            .unwrap();
+        assert_eq!(Interval::exact(13), exact.z);
+
+        assert_eq!(
+            Err(InvalidOperation { instruction: 1 }),
+            Ranges::default().propagate(&parse("inp w\ndiv w x"), [Interval::DIGIT])
+        );
+        assert_eq!(
+            Err(InvalidOperation { instruction: 1 }),
+            Ranges::default().propagate(&program, [Interval::DIGIT])
+        );
+    }
+}
@@ -24,8 +24,8 @@ const DIV: &str = "div";
 const MOD: &str = "mod";
 const EQUAL: &str = "eql";
 
-#[derive(Debug, Copy, Clone)]
-pub(crate) enum Instruction {
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Instruction {
     Input(Variable),
     Add(Variable, Operand),
     Mul(Variable, Operand),
@@ -34,6 +34,47 @@ pub(crate) enum Instruction {
     Equal(Variable, Operand),
 }
 
+impl Instruction {
+    /// Variable modified by the instruction.
+    pub(crate) fn target(&self) -> Variable {
+        match *self {
+            Instruction::Input(a)
+            | Instruction::Add(a, _)
+            | Instruction::Mul(a, _)
+            | Instruction::Div(a, _)
+            | Instruction::Mod(a, _)
+            | Instruction::Equal(a, _) => a,
+        }
+    }
+
+    /// Variable the instruction reads apart from its target, if any.
+    pub(crate) fn source(&self) -> Option<Variable> {
+        match *self {
+            Instruction::Input(_) => None,
+            Instruction::Add(_, b)
+            | Instruction::Mul(_, b)
+            | Instruction::Div(_, b)
+            | Instruction::Mod(_, b)
+            | Instruction::Equal(_, b) => match b {
+                Operand::Var(variable) => Some(variable),
+                Operand::Number(_) => None,
+            },
+        }
+    }
+
+    /// Checks whether swapping the two instructions can't possibly change the result of the
+    /// program, i.e. neither of them reads or writes what the other one writes.
+    pub(crate) fn commutes_with(&self, other: &Instruction) -> bool {
+        let both_read_input =
+            matches!(self, Instruction::Input(_)) && matches!(other, Instruction::Input(_));
+
+        !both_read_input
+            && self.target() != other.target()
+            && self.source() != Some(other.target())
+            && other.source() != Some(self.target())
+    }
+}
+
 impl FromStr for Instruction {
     type Err = anyhow::Error;
 
@@ -0,0 +1,229 @@
+// Copyright 2022 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::alu::{Instruction, Operand, Variable};
+use std::fmt::{Display, Formatter};
+
+/// Reason the ALU halted before reaching the end of its program.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum AluError {
+    MissingInput { instruction: usize },
+    DivisionByZero { instruction: usize },
+    InvalidModulo { instruction: usize },
+    Overflow { instruction: usize },
+}
+
+impl Display for AluError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AluError::MissingInput { instruction } => {
+                write!(f, "instruction {instruction} ran out of inputs")
+            }
+            AluError::DivisionByZero { instruction } => {
+                write!(f, "instruction {instruction} attempted to divide by zero")
+            }
+            AluError::InvalidModulo { instruction } => write!(
+                f,
+                "instruction {instruction} attempted modulo of a negative number or by a non-positive one"
+            ),
+            AluError::Overflow { instruction } => {
+                write!(f, "instruction {instruction} overflowed")
+            }
+        }
+    }
+}
+
+impl std::error::Error for AluError {}
+
+/// Arithmetic logic unit with its four variables.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Alu {
+    w: isize,
+    x: isize,
+    y: isize,
+    z: isize,
+}
+
+impl Alu {
+    pub fn with_z(z: isize) -> Self {
+        Alu {
+            z,
+            ..Default::default()
+        }
+    }
+
+    pub fn get(&self, variable: Variable) -> isize {
+        match variable {
+            Variable::W => self.w,
+            Variable::X => self.x,
+            Variable::Y => self.y,
+            Variable::Z => self.z,
+        }
+    }
+
+    fn get_mut(&mut self, variable: Variable) -> &mut isize {
+        match variable {
+            Variable::W => &mut self.w,
+            Variable::X => &mut self.x,
+            Variable::Y => &mut self.y,
+            Variable::Z => &mut self.z,
+        }
+    }
+
+    fn operand(&self, operand: Operand) -> isize {
+        match operand {
+            Operand::Var(variable) => self.get(variable),
+            Operand::Number(value) => value,
+        }
+    }
+
+    /// Runs the program on a fresh ALU and returns its final state.
+    pub fn run_program_with_inputs<I>(
+        instructions: &[Instruction],
+        inputs: I,
+    ) -> Result<Self, AluError>
+    where
+        I: IntoIterator<Item = isize>,
+    {
+        let mut alu = Alu::default();
+        alu.execute_program(instructions, inputs)?;
+        Ok(alu)
+    }
+
+    /// Executes all of the instructions, with the `inp` ones consuming consecutive inputs.
+    pub fn execute_program<I>(
+        &mut self,
+        instructions: &[Instruction],
+        inputs: I,
+    ) -> Result<(), AluError>
+    where
+        I: IntoIterator<Item = isize>,
+    {
+        let mut inputs = inputs.into_iter();
+        for (instruction, op) in instructions.iter().enumerate() {
+            match *op {
+                Instruction::Input(a) => {
+                    *self.get_mut(a) = inputs
+                        .next()
+                        .ok_or(AluError::MissingInput { instruction })?
+                }
+                Instruction::Add(a, b) => {
+                    *self.get_mut(a) = self
+                        .get(a)
+                        .checked_add(self.operand(b))
+                        .ok_or(AluError::Overflow { instruction })?
+                }
+                Instruction::Mul(a, b) => {
+                    *self.get_mut(a) = self
+                        .get(a)
+                        .checked_mul(self.operand(b))
+                        .ok_or(AluError::Overflow { instruction })?
+                }
+                Instruction::Div(a, b) => {
+                    let divisor = self.operand(b);
+                    if divisor == 0 {
+                        return Err(AluError::DivisionByZero { instruction });
+                    }
+                    // the only overflowing division is the one of the smallest number by -1
+                    *self.get_mut(a) = self
+                        .get(a)
+                        .checked_div(divisor)
+                        .ok_or(AluError::Overflow { instruction })?
+                }
+                Instruction::Mod(a, b) => {
+                    let divisor = self.operand(b);
+                    if self.get(a) < 0 || divisor <= 0 {
+                        return Err(AluError::InvalidModulo { instruction });
+                    }
+                    *self.get_mut(a) %= divisor
+                }
+                Instruction::Equal(a, b) => {
+                    *self.get_mut(a) = isize::from(self.get(a) == self.operand(b))
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(program: &str) -> Vec<Instruction> {
+        program.lines().map(|line| line.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn executing_programs() {
+        // converts the input into binary, with the lowest bit stored in z
+        let binary = parse(
+            "inp w\nadd z w\nmod z 2\ndiv w 2\nadd y w\nmod y 2\ndiv w 2\nadd x w\nmod x 2\ndiv w 2\nmod w 2",
+        );
+        let mut alu = Alu::default();
+        alu.execute_program(&binary, [13]).unwrap();
+        assert_eq!(1, alu.get(Variable::W));
+        assert_eq!(1, alu.get(Variable::X));
+        assert_eq!(0, alu.get(Variable::Y));
+        assert_eq!(1, alu.get(Variable::Z));
+
+        // checks whether the second input is three times larger than the first one
+        let mut alu = Alu::default();
+        let triple = parse("inp z\ninp x\nmul z 3\neql z x");
+        alu.execute_program(&triple, [3, 9]).unwrap();
+        assert_eq!(1, alu.get(Variable::Z));
+
+        assert_eq!(
+            Err(AluError::MissingInput { instruction: 1 }),
+            Alu::default().execute_program(&triple, [3])
+        );
+        assert_eq!(
+            Err(AluError::DivisionByZero { instruction: 0 }),
+            Alu::default().execute_program(&parse("div x y"), [])
+        );
+        assert_eq!(
+            Err(AluError::InvalidModulo { instruction: 1 }),
+            Alu::default().execute_program(&parse("add x -1\nmod x 2"), [])
+        );
+
+        // raises the input to the fourth power and doubles it
+        let power = parse("inp x\nmul x x\nmul x x\nadd x x");
+        assert_eq!(
+            Err(AluError::Overflow { instruction: 1 }),
+            Alu::default().execute_program(&power, [isize::MAX / 2])
+        );
+        assert_eq!(
+            Err(AluError::Overflow { instruction: 3 }),
+            Alu::default().execute_program(&power, [50000])
+        );
+        assert_eq!(
+            Err(AluError::Overflow { instruction: 2 }),
+            Alu::default().execute_program(&parse("inp x\ninp y\ndiv x y"), [isize::MIN, -1])
+        );
+    }
+
+    #[test]
+    fn running_programs_on_fresh_alu() {
+        let negate = parse("inp x\nmul x -1");
+        let alu = Alu::run_program_with_inputs(&negate, [7]).unwrap();
+        assert_eq!(-7, alu.get(Variable::X));
+        assert_eq!(0, alu.get(Variable::Z));
+
+        assert_eq!(
+            Err(AluError::MissingInput { instruction: 0 }),
+            Alu::run_program_with_inputs(&negate, [])
+        );
+    }
+}
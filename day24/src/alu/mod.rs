@@ -12,8 +12,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub(crate) mod analysis;
 mod instruction;
+mod machine;
 mod operand;
+pub mod optimizer;
 
-pub(crate) use instruction::Instruction;
-pub(crate) use operand::{Operand, Variable};
+pub use instruction::Instruction;
+pub use machine::{Alu, AluError};
+pub use operand::{Operand, Variable};
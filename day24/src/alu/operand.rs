@@ -16,8 +16,8 @@ use anyhow::bail;
 use std::fmt::{Display, Formatter};
 use std::str::FromStr;
 
-#[derive(Debug, Copy, Clone)]
-pub(crate) enum Variable {
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Variable {
     W,
     X,
     Y,
@@ -29,10 +29,10 @@ impl FromStr for Variable {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
-            x if x == "w" => Ok(Variable::W),
-            x if x == "x" => Ok(Variable::X),
-            x if x == "y" => Ok(Variable::Y),
-            x if x == "z" => Ok(Variable::Z),
+            "w" => Ok(Variable::W),
+            "x" => Ok(Variable::X),
+            "y" => Ok(Variable::Y),
+            "z" => Ok(Variable::Z),
             _ => bail!("not a valid variable"),
         }
     }
@@ -49,21 +49,12 @@ impl Display for Variable {
     }
 }
 
-#[derive(Debug, Copy, Clone)]
-pub(crate) enum Operand {
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Operand {
     Var(Variable),
     Number(isize),
 }
 
-impl Operand {
-    pub(crate) fn get_number(&self) -> Option<isize> {
-        match self {
-            Operand::Var(_) => None,
-            Operand::Number(val) => Some(*val),
-        }
-    }
-}
-
 impl Display for Operand {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -0,0 +1,288 @@
+// Copyright 2022 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::alu::analysis::{Interval, Ranges};
+use crate::alu::{Instruction, Operand, Variable};
+
+fn index(variable: Variable) -> usize {
+    match variable {
+        Variable::W => 0,
+        Variable::X => 1,
+        Variable::Y => 2,
+        Variable::Z => 3,
+    }
+}
+
+fn with_operand(instruction: Instruction, b: Operand) -> Instruction {
+    match instruction {
+        Instruction::Input(a) => Instruction::Input(a),
+        Instruction::Add(a, _) => Instruction::Add(a, b),
+        Instruction::Mul(a, _) => Instruction::Mul(a, b),
+        Instruction::Div(a, _) => Instruction::Div(a, b),
+        Instruction::Mod(a, _) => Instruction::Mod(a, b),
+        Instruction::Equal(a, _) => Instruction::Equal(a, b),
+    }
+}
+
+fn operand(instruction: &Instruction) -> Option<Operand> {
+    match *instruction {
+        Instruction::Input(_) => None,
+        Instruction::Add(_, b)
+        | Instruction::Mul(_, b)
+        | Instruction::Div(_, b)
+        | Instruction::Mod(_, b)
+        | Instruction::Equal(_, b) => Some(b),
+    }
+}
+
+// `mul a 0` is the only instruction whose result doesn't depend on the current value of its target
+fn reads_target(instruction: &Instruction) -> bool {
+    !matches!(
+        instruction,
+        Instruction::Input(_) | Instruction::Mul(_, Operand::Number(0))
+    )
+}
+
+fn is_identity(instruction: &Instruction) -> bool {
+    matches!(
+        instruction,
+        Instruction::Add(_, Operand::Number(0))
+            | Instruction::Mul(_, Operand::Number(1))
+            | Instruction::Div(_, Operand::Number(1))
+    )
+}
+
+// makes sure the variable actually holds the value the optimizer knows about
+fn materialize(
+    folded: &mut Vec<Instruction>,
+    a: Variable,
+    known: Option<isize>,
+    register: &mut Option<isize>,
+) {
+    let Some(value) = known else { return };
+    match *register {
+        Some(current) if current == value => return,
+        Some(current) => folded.push(Instruction::Add(a, Operand::Number(value - current))),
+        None => {
+            folded.push(Instruction::Mul(a, Operand::Number(0)));
+            if value != 0 {
+                folded.push(Instruction::Add(a, Operand::Number(value)))
+            }
+        }
+    }
+    *register = Some(value);
+}
+
+// value of the variable, as long as there's only a single one it could possibly hold
+fn exact_value(ranges: &Ranges, variable: Variable) -> Option<isize> {
+    let range = ranges.get(variable);
+    (range.min == range.max).then_some(range.min)
+}
+
+/// Replaces computations on values known ahead of time, assuming every input is a non-zero digit,
+/// with their results and substitutes the known variables with their values.
+fn fold_constants(instructions: &[Instruction]) -> Vec<Instruction> {
+    // possible values of the variables in the original program
+    let mut ranges = Ranges::default();
+    // values the variables are known to actually hold at this point of the optimized program
+    let mut registers = [Some(0); 4];
+    let mut folded = Vec::with_capacity(instructions.len());
+
+    for &instruction in instructions {
+        let a = instruction.target();
+        let i = index(a);
+        let Some(b) = operand(&instruction) else {
+            ranges = ranges
+                .propagate(&[instruction], [Interval::DIGIT])
+                .expect("reading an input never halts the program");
+            folded.push(instruction);
+            registers[i] = None;
+            continue;
+        };
+
+        let b = match b {
+            Operand::Var(variable) => exact_value(&ranges, variable)
+                .map(Operand::Number)
+                .unwrap_or(b),
+            number => number,
+        };
+        let instruction = with_operand(instruction, b);
+        let known = exact_value(&ranges, a);
+        match ranges.propagate(&[instruction], []) {
+            Ok(next) => ranges = next,
+            Err(_) => {
+                // the program always halts here, so leave it be
+                materialize(&mut folded, a, known, &mut registers[i]);
+                folded.push(instruction);
+                registers[i] = None;
+                continue;
+            }
+        }
+
+        // the result is known ahead of time, so it only has to be materialized if it's ever needed
+        if is_identity(&instruction) || exact_value(&ranges, a).is_some() {
+            continue;
+        }
+
+        materialize(&mut folded, a, known, &mut registers[i]);
+        folded.push(instruction);
+        registers[i] = None;
+    }
+
+    let z = index(Variable::Z);
+    materialize(
+        &mut folded,
+        Variable::Z,
+        exact_value(&ranges, Variable::Z),
+        &mut registers[z],
+    );
+    folded
+}
+
+/// Removes the instructions whose results are never going to be read, given that only `z`
+/// matters once the program finishes. The inputs are always kept since they consume the digits.
+/// Note that the removed instructions might have halted the program had they been executed.
+fn eliminate_dead_stores(instructions: &[Instruction]) -> Vec<Instruction> {
+    let mut live = [false; 4];
+    live[index(Variable::Z)] = true;
+
+    let mut kept = Vec::with_capacity(instructions.len());
+    for instruction in instructions.iter().rev() {
+        let a = index(instruction.target());
+        let is_input = matches!(instruction, Instruction::Input(_));
+        if !live[a] && !is_input {
+            continue;
+        }
+
+        live[a] = reads_target(instruction);
+        if let Some(source) = instruction.source() {
+            live[index(source)] = true;
+        }
+        kept.push(*instruction);
+    }
+
+    kept.reverse();
+    kept
+}
+
+/// Simplifies the program while preserving the final value of `z` for every input.
+pub fn optimize(instructions: &[Instruction]) -> Vec<Instruction> {
+    let mut optimized = instructions.to_vec();
+    loop {
+        let next = eliminate_dead_stores(&fold_constants(&optimized));
+        if next == optimized {
+            return optimized;
+        }
+        optimized = next;
+    }
+}
+
+fn pseudocode(instruction: &Instruction, digit: usize) -> String {
+    match *instruction {
+        Instruction::Input(a) => format!("{a} = digit[{digit}]"),
+        Instruction::Add(a, b) => format!("{a} += {b}"),
+        Instruction::Mul(a, Operand::Number(0)) => format!("{a} = 0"),
+        Instruction::Mul(a, b) => format!("{a} *= {b}"),
+        Instruction::Div(a, b) => format!("{a} /= {b}"),
+        Instruction::Mod(a, b) => format!("{a} %= {b}"),
+        Instruction::Equal(a, b) => format!("{a} = ({a} == {b}) as int"),
+    }
+}
+
+/// Lists the instructions alongside their pseudocode, with each of the digits processed
+/// in a separate block.
+pub fn disassemble(instructions: &[Instruction]) -> String {
+    let mut listing = String::new();
+    let mut digits = 0usize;
+    for (line, instruction) in instructions.iter().enumerate() {
+        let is_input = matches!(instruction, Instruction::Input(_));
+        if is_input {
+            if line > 0 {
+                listing.push('\n');
+            }
+            listing.push_str(&format!("; digit {digits}\n"));
+        }
+
+        let pseudocode = pseudocode(instruction, digits);
+        listing.push_str(&format!(
+            "{line:>4}  {:<12}{pseudocode}\n",
+            instruction.to_string()
+        ));
+        if is_input {
+            digits += 1;
+        }
+    }
+    listing
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alu::Alu;
+
+    fn parse(program: &str) -> Vec<Instruction> {
+        program.lines().map(|line| line.parse().unwrap()).collect()
+    }
+
+    fn final_z(instructions: &[Instruction], inputs: &[isize]) -> isize {
+        let mut alu = Alu::default();
+        alu.execute_program(instructions, inputs.iter().copied())
+            .unwrap();
+        alu.get(Variable::Z)
+    }
+
+    #[test]
+    fn folding_constants() {
+        let program = parse("mul x 0\nadd x 5\nmul x 3\nadd y x\nadd z y\ndiv z 1\nmul z 1");
+        assert_eq!(parse("add z 15"), optimize(&program));
+
+        // `y` is known to be zero
+        let program = parse("inp w\nadd z w\nmul y x\neql y 0\nadd z y");
+        assert_eq!(parse("inp w\nadd z w\nadd z 1"), optimize(&program));
+
+        // the digit can never be equal to 10
+        let program = parse("inp w\nadd x 10\neql x w\nadd z x\nadd z w");
+        assert_eq!(parse("inp w\nadd z w"), optimize(&program));
+    }
+
+    #[test]
+    fn eliminating_dead_stores() {
+        let program = parse("inp w\nadd x w\nmul x 2\nmul x 0\nadd y w\nadd z w\ninp w");
+        assert_eq!(parse("inp w\nadd z w\ninp w"), optimize(&program));
+    }
+
+    #[test]
+    fn optimized_chunk_is_equivalent() {
+        let chunk = parse(
+            "inp w\nmul x 0\nadd x z\nmod x 26\ndiv z 1\nadd x 14\neql x w\neql x 0\nmul y 0\n\
+            add y 25\nmul y x\nadd y 1\nmul z y\nmul y 0\nadd y w\nadd y 7\nmul y x\nadd z y",
+        );
+        let program = [chunk.clone(), chunk].concat();
+        let optimized = optimize(&program);
+        assert!(optimized.len() < program.len());
+
+        for inputs in [[1, 1], [9, 9], [3, 7], [8, 2]] {
+            assert_eq!(final_z(&program, &inputs), final_z(&optimized, &inputs));
+        }
+    }
+
+    #[test]
+    fn disassembling() {
+        let listing = disassemble(&parse("inp w\nmul x 0\neql x w\ninp w"));
+        assert_eq!(
+            "; digit 0\n   0  inp w       w = digit[0]\n   1  mul x 0     x = 0\n   2  eql x w     x = (x == w) as int\n\n; digit 1\n   3  inp w       w = digit[1]\n",
+            listing
+        );
+    }
+}
@@ -12,28 +12,95 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::alu::analysis::{Interval, Ranges};
 use crate::alu::Instruction;
 
-// It turns out the input is in the form of the following chunks repeat 14 times:
-// inp w
-// mul x 0
-// add x z
-// mod x 26
-// div z z_div
-// add x x_add
-// eql x w
-// eql x 0
-// mul y 0
-// add y 25
-// mul y x
-// add y 1
-// mul z y
-// mul y 0
-// add y w
-// add y y_add
-// mul y x
-// add z y
+use std::fmt::{Display, Formatter};
+
+// It turns out the input is in the form of the following chunks repeat 14 times,
 // the only thing linking chunks together is the value of `z`. Both `x` and `y` are irrelevant (and `w` is always overwritten with input)
+const TEMPLATE: [&str; CHUNK_LENGTH] = [
+    "inp w",
+    "mul x 0",
+    "add x z",
+    "mod x 26",
+    "div z z_div",
+    "add x x_add",
+    "eql x w",
+    "eql x 0",
+    "mul y 0",
+    "add y 25",
+    "mul y x",
+    "add y 1",
+    "mul z y",
+    "mul y 0",
+    "add y w",
+    "add y y_add",
+    "mul y x",
+    "add z y",
+];
+
+pub(crate) const CHUNK_LENGTH: usize = 18;
+
+/// Reasons the instructions couldn't be turned into a chunk.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChunkError {
+    /// The instruction, at the specified index within the chunk, doesn't match the template
+    /// and can't be reordered to match it either.
+    Deviation {
+        index: usize,
+        found: Instruction,
+        expected: &'static str,
+    },
+    Missing {
+        expected: &'static str,
+    },
+}
+
+impl Display for ChunkError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ChunkError::Deviation {
+                index,
+                found,
+                expected,
+            } => write!(
+                f,
+                "instruction {index} (`{found}`) deviates from the template, expected `{expected}`"
+            ),
+            ChunkError::Missing { expected } => {
+                write!(f, "the chunk ends before the expected `{expected}`")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ChunkError {}
+
+const PLACEHOLDERS: [&str; 3] = ["z_div", "x_add", "y_add"];
+
+// attempts to match the instruction against the template line, returning the value of its
+// placeholder, if there was any
+fn match_template(
+    instruction: &Instruction,
+    template: &'static str,
+) -> Option<Option<(&'static str, isize)>> {
+    let instruction = instruction.to_string();
+    let mut captured = None;
+    for (token, expected) in instruction.split(' ').zip(template.split(' ')) {
+        if PLACEHOLDERS.contains(&expected) {
+            let value = token.parse().ok()?;
+            // the division by zero halts the program, so it can't be simplified
+            if expected == "z_div" && value == 0 {
+                return None;
+            }
+            captured = Some((expected, value))
+        } else if token != expected {
+            return None;
+        }
+    }
+    Some(captured)
+}
 
 #[derive(Debug, Copy, Clone, Hash, PartialOrd, PartialEq, Eq)]
 pub(crate) struct Chunk {
@@ -43,31 +110,70 @@ pub(crate) struct Chunk {
 }
 
 impl Chunk {
-    pub(crate) fn from_instructions(instructions: &[Instruction]) -> Self {
-        assert_eq!(instructions.len(), 18, "invalid instructions provided");
-        let z_div = if let Instruction::Div(_, op) = instructions[4] {
-            op.get_number().expect("invalid instructions provided")
-        } else {
-            panic!("invalid instructions provided")
+    /// Extracts the parameters of the chunk, as long as the instructions follow the template,
+    /// allowing for any reordering of instructions that doesn't change the result.
+    pub(crate) fn from_instructions(instructions: &[Instruction]) -> Result<Self, ChunkError> {
+        let mut remaining = instructions.iter().enumerate().collect::<Vec<_>>();
+        let mut chunk = Chunk {
+            z_div: 0,
+            x_add: 0,
+            y_add: 0,
         };
 
-        let x_add = if let Instruction::Add(_, op) = instructions[5] {
-            op.get_number().expect("invalid instructions provided")
-        } else {
-            panic!("invalid instructions provided")
-        };
+        for expected in TEMPLATE {
+            // the instruction can be matched as long as it could be moved in front of all
+            // the ones that haven't been matched yet
+            let matched = remaining
+                .iter()
+                .enumerate()
+                .find_map(|(position, (_, instruction))| {
+                    let captured = match_template(instruction, expected)?;
+                    remaining[..position]
+                        .iter()
+                        .all(|(_, before)| before.commutes_with(instruction))
+                        .then_some((position, captured))
+                });
 
-        let y_add = if let Instruction::Add(_, op) = instructions[15] {
-            op.get_number().expect("invalid instructions provided")
-        } else {
-            panic!("invalid instructions provided")
-        };
+            let Some((position, captured)) = matched else {
+                return Err(match remaining.first() {
+                    Some(&(index, &found)) => ChunkError::Deviation {
+                        index,
+                        found,
+                        expected,
+                    },
+                    None => ChunkError::Missing { expected },
+                });
+            };
+            remaining.remove(position);
 
-        Chunk {
-            z_div,
-            x_add,
-            y_add,
+            match captured {
+                Some(("z_div", value)) => chunk.z_div = value,
+                Some(("x_add", value)) => chunk.x_add = value,
+                Some((_, value)) => chunk.y_add = value,
+                None => (),
+            }
         }
+
+        if let Some(&(index, &found)) = remaining.first() {
+            return Err(ChunkError::Deviation {
+                index,
+                found,
+                expected: "end of the chunk",
+            });
+        }
+
+        Ok(chunk)
+    }
+
+    /// Checks whether the chunk produces exactly the same `z` as the instructions it was extracted
+    /// from would, for every digit and the specified input `z`.
+    pub(crate) fn agrees_with(&self, instructions: &[Instruction], input_z: isize) -> bool {
+        (1..=9).all(|w| {
+            Ranges::with_z(Interval::exact(input_z))
+                .propagate(instructions, [Interval::exact(w)])
+                .map(|ranges| ranges.z == Interval::exact(self.execute(w, input_z)))
+                .unwrap_or_default()
+        })
     }
 
     pub(crate) fn execute(&self, w: isize, input_z: isize) -> isize {
@@ -81,3 +187,104 @@ impl Chunk {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunk_instructions(z_div: isize, x_add: isize, y_add: isize) -> Vec<Instruction> {
+        format!(
+            "inp w\nmul x 0\nadd x z\nmod x 26\ndiv z {z_div}\nadd x {x_add}\neql x w\neql x 0\n\
+            mul y 0\nadd y 25\nmul y x\nadd y 1\nmul z y\nmul y 0\nadd y w\nadd y {y_add}\n\
+            mul y x\nadd z y"
+        )
+        .lines()
+        .map(|line| line.trim().parse().unwrap())
+        .collect()
+    }
+
+    #[test]
+    fn chunk_matches_the_program() {
+        for (z_div, x_add, y_add) in [(1, 14, 0), (26, -8, 3), (26, 0, 15)] {
+            let instructions = chunk_instructions(z_div, x_add, y_add);
+            let chunk = Chunk::from_instructions(&instructions).unwrap();
+            assert_eq!(
+                Chunk {
+                    z_div,
+                    x_add,
+                    y_add
+                },
+                chunk
+            );
+            for input_z in [0, 7, 26, 17 * 26 + 3, 12345] {
+                assert!(chunk.agrees_with(&instructions, input_z));
+            }
+
+            let mismatched = Chunk {
+                y_add: y_add + 1,
+                ..chunk
+            };
+            assert!(!mismatched.agrees_with(&instructions, 0));
+        }
+    }
+    #[test]
+    fn reordered_chunk() {
+        let mut instructions = chunk_instructions(26, -8, 3);
+        // `div z 26` doesn't depend on anything happening to `x`
+        instructions.swap(4, 5);
+        // neither does resetting `y`
+        instructions.swap(7, 8);
+        let chunk = Chunk::from_instructions(&instructions).unwrap();
+        assert_eq!(
+            Chunk {
+                z_div: 26,
+                x_add: -8,
+                y_add: 3
+            },
+            chunk
+        );
+        assert!(chunk.agrees_with(&instructions, 12345));
+    }
+
+    #[test]
+    fn deviating_chunk() {
+        let mut instructions = chunk_instructions(26, -8, 3);
+        // `x` has to be compared with `w` before being compared with 0
+        instructions.swap(6, 7);
+        assert_eq!(
+            Err(ChunkError::Deviation {
+                index: 6,
+                found: "eql x 0".parse().unwrap(),
+                expected: "eql x w"
+            }),
+            Chunk::from_instructions(&instructions)
+        );
+
+        assert_eq!(
+            Err(ChunkError::Missing {
+                expected: "eql x w"
+            }),
+            Chunk::from_instructions(&instructions[..6])
+        );
+
+        let mut instructions = chunk_instructions(26, -8, 3);
+        instructions[15] = "add y z".parse().unwrap();
+        assert_eq!(
+            Err(ChunkError::Deviation {
+                index: 15,
+                found: "add y z".parse().unwrap(),
+                expected: "add y y_add"
+            }),
+            Chunk::from_instructions(&instructions)
+        );
+        // dividing by zero halts the program
+        assert_eq!(
+            Err(ChunkError::Deviation {
+                index: 4,
+                found: "div z 0".parse().unwrap(),
+                expected: "div z z_div"
+            }),
+            Chunk::from_instructions(&chunk_instructions(0, -8, 3))
+        );
+    }
+}
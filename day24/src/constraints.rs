@@ -0,0 +1,221 @@
+// Copyright 2022 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::alu::{Alu, Variable};
+use crate::chunk::Chunk;
+use crate::{Segment, SolutionType, MODEL_NUMBER_DIGITS};
+
+// The chunks operate on `z` as if it was a stack of base 26 digits. Chunks with `z_div = 1` always
+// push `w + y_add`, as their `x_add` is too large for the comparison with the digit to ever hold,
+// while the ones with `z_div = 26` pop the top value and push it back unless `top + x_add = w`.
+// Since there's as many pushes as there are pops, `z = 0` can only be reached if every pop
+// matches the digit, so each of the pushing chunks constrains the digit of its popping chunk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operation {
+    Push { y_add: isize },
+    Pop { x_add: isize },
+}
+
+fn operation(chunk: &Chunk) -> Option<Operation> {
+    match chunk.z_div {
+        // the pushed value has to remain a single base 26 digit for any `w`
+        1 if chunk.x_add > 9 && chunk.y_add >= -1 && chunk.y_add + 9 < 26 => {
+            Some(Operation::Push { y_add: chunk.y_add })
+        }
+        26 => Some(Operation::Pop { x_add: chunk.x_add }),
+        _ => None,
+    }
+}
+
+/// Pair of the digits, identified by their indices, such that `digits[popped] = digits[pushed] + offset`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Constraint {
+    pushed: usize,
+    popped: usize,
+    offset: isize,
+}
+
+impl Constraint {
+    // the best pair of digits satisfying the constraint
+    fn digits(&self, solution_type: SolutionType) -> Option<(isize, isize)> {
+        let pushed = match solution_type {
+            SolutionType::Largest => 9.min(9 - self.offset),
+            SolutionType::Smallest => 1.max(1 - self.offset),
+        };
+        let popped = pushed + self.offset;
+        ((1..=9).contains(&pushed) && (1..=9).contains(&popped)).then_some((pushed, popped))
+    }
+}
+
+// pairs the pushing chunks with the chunks popping their values,
+// as long as the program consists exclusively of such chunks
+fn constraints(segments: &[Segment<'_>]) -> Option<Vec<Constraint>> {
+    let mut stack = Vec::new();
+    let mut constraints = Vec::with_capacity(segments.len() / 2);
+    for (index, segment) in segments.iter().enumerate() {
+        match operation(segment.chunk.as_ref().ok()?)? {
+            Operation::Push { y_add } => stack.push((index, y_add)),
+            Operation::Pop { x_add } => {
+                let (pushed, y_add) = stack.pop()?;
+                constraints.push(Constraint {
+                    pushed,
+                    popped: index,
+                    offset: y_add + x_add,
+                })
+            }
+        }
+    }
+    stack.is_empty().then_some(constraints)
+}
+
+/// Derives the model number directly out of the constraints between the pairs of digits,
+/// or returns `None` if the program doesn't consist of the paired chunks or if there are no valid
+/// model numbers.
+pub(crate) fn solve(
+    alu: Alu,
+    segments: &[Segment<'_>],
+    solution_type: SolutionType,
+) -> Option<usize> {
+    // anything already on the stack would have to be popped as well
+    if alu.get(Variable::Z) != 0 || segments.len() != MODEL_NUMBER_DIGITS {
+        return None;
+    }
+
+    let mut digits = [0; MODEL_NUMBER_DIGITS];
+    for constraint in constraints(segments)? {
+        let (pushed, popped) = constraint.digits(solution_type)?;
+        digits[constraint.pushed] = pushed;
+        digits[constraint.popped] = popped;
+    }
+    Some(
+        digits
+            .iter()
+            .fold(0, |number, &digit| 10 * number + digit as usize),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{search, split_program, Instruction};
+
+    fn program(chunks: &[(isize, isize, isize)]) -> Vec<Instruction> {
+        chunks
+            .iter()
+            .flat_map(|(z_div, x_add, y_add)| {
+                format!(
+                    "inp w\nmul x 0\nadd x z\nmod x 26\ndiv z {z_div}\nadd x {x_add}\neql x w\n\
+                    eql x 0\nmul y 0\nadd y 25\nmul y x\nadd y 1\nmul z y\nmul y 0\nadd y w\n\
+                    add y {y_add}\nmul y x\nadd z y"
+                )
+                .lines()
+                .map(|line| line.trim().parse().unwrap())
+                .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    const PAIRED_CHUNKS: [(isize, isize, isize); MODEL_NUMBER_DIGITS] = [
+        (1, 11, 6),
+        (1, 13, 14),
+        (1, 15, 14),
+        (26, -8, 10),
+        (1, 13, 9),
+        (1, 15, 12),
+        (26, -11, 8),
+        (26, -4, 13),
+        (26, -15, 12),
+        (1, 14, 6),
+        (1, 14, 9),
+        (26, -1, 15),
+        (26, -8, 4),
+        (26, -14, 10),
+    ];
+
+    #[test]
+    fn pairing_the_chunks() {
+        let instructions = program(&PAIRED_CHUNKS);
+        let (_, segments) = split_program(&instructions);
+        assert_eq!(
+            Some(vec![
+                Constraint {
+                    pushed: 2,
+                    popped: 3,
+                    offset: 6
+                },
+                Constraint {
+                    pushed: 5,
+                    popped: 6,
+                    offset: 1
+                },
+                Constraint {
+                    pushed: 4,
+                    popped: 7,
+                    offset: 5
+                },
+                Constraint {
+                    pushed: 1,
+                    popped: 8,
+                    offset: -1
+                },
+                Constraint {
+                    pushed: 10,
+                    popped: 11,
+                    offset: 8
+                },
+                Constraint {
+                    pushed: 9,
+                    popped: 12,
+                    offset: -2
+                },
+                Constraint {
+                    pushed: 0,
+                    popped: 13,
+                    offset: -8
+                },
+            ]),
+            constraints(&segments)
+        );
+    }
+
+    #[test]
+    fn matches_the_search() {
+        let instructions = program(&PAIRED_CHUNKS);
+        let (_, segments) = split_program(&instructions);
+        for solution_type in [SolutionType::Largest, SolutionType::Smallest] {
+            assert_eq!(
+                search(Alu::default(), &segments, true, solution_type),
+                solve(Alu::default(), &segments, solution_type)
+            );
+        }
+    }
+
+    #[test]
+    fn unpaired_chunks() {
+        let mut chunks = PAIRED_CHUNKS;
+        // the chunk might not push anything now
+        chunks[0] = (1, 5, 6);
+        let instructions = program(&chunks);
+        let (_, segments) = split_program(&instructions);
+        assert_eq!(
+            None,
+            solve(Alu::default(), &segments, SolutionType::Largest)
+        );
+
+        // there's a pop without any matching push
+        let instructions = program(&PAIRED_CHUNKS[3..]);
+        let (_, segments) = split_program(&instructions);
+        assert_eq!(None, constraints(&segments));
+    }
+}
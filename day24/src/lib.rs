@@ -0,0 +1,456 @@
+// Copyright 2022 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::alu::analysis::{Interval, Ranges};
+use crate::alu::{Alu, AluError};
+use crate::chunk::Chunk;
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+use std::io;
+use std::iter;
+use std::path::Path;
+use std::str::FromStr;
+use utils::input_read::read_parsed_line_input;
+use utils::Solver;
+
+use crate::alu::{Instruction, Variable};
+
+/// Standalone interpreter for the MONAD instruction set.
+pub mod alu;
+mod chunk;
+mod constraints;
+
+pub use chunk::ChunkError;
+#[cfg(feature = "parallel")]
+mod parallel;
+
+const DIGITS_ASC: &[isize] = &[1isize, 2, 3, 4, 5, 6, 7, 8, 9];
+const DIGITS_DESC: &[isize] = &[9isize, 8, 7, 6, 5, 4, 3, 2, 1];
+
+const MODEL_NUMBER_DIGITS: usize = 14;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Algorithm {
+    /// Pairs the chunks pushing onto and popping from the `z` stack and derives the digits
+    /// directly out of the constraints between them. Falls back to `Pruned` if the program
+    /// doesn't consist of such chunks.
+    #[default]
+    Constraints,
+    /// Tries all the digits, remembering the states that had lead to dead ends.
+    Bruteforce,
+    /// Additionally skips the states for which the interval analysis of the remaining
+    /// instructions proves `z = 0` can't be reached.
+    Pruned,
+    /// Same as `Pruned`, but searches each combination of the leading digits in parallel,
+    /// with the dead ends shared between all of them.
+    #[cfg(feature = "parallel")]
+    Parallel,
+}
+
+impl FromStr for Algorithm {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "constraints" => Ok(Algorithm::Constraints),
+            "bruteforce" => Ok(Algorithm::Bruteforce),
+            "pruned" => Ok(Algorithm::Pruned),
+            #[cfg(feature = "parallel")]
+            "parallel" => Ok(Algorithm::Parallel),
+            other => Err(format!("{other} is not a valid algorithm")),
+        }
+    }
+}
+
+/// Reasons the MONAD program rejected a model number.
+#[derive(Debug)]
+enum VerificationError {
+    Halted(AluError),
+    NonZero(isize),
+}
+
+impl Display for VerificationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VerificationError::Halted(err) => write!(f, "the program halted early - {err}"),
+            VerificationError::NonZero(z) => write!(f, "the program finished with z = {z}"),
+        }
+    }
+}
+
+impl std::error::Error for VerificationError {}
+
+/// Runs the full program, rather than the extracted chunks, to check whether the model number
+/// is valid.
+fn verify_model_number(
+    instructions: &[Instruction],
+    model_number: usize,
+) -> Result<(), VerificationError> {
+    let digits = model_number
+        .to_string()
+        .bytes()
+        .map(|digit| (digit - b'0') as isize)
+        .collect::<Vec<_>>();
+    debug_assert_eq!(MODEL_NUMBER_DIGITS, digits.len());
+
+    let alu =
+        Alu::run_program_with_inputs(instructions, digits).map_err(VerificationError::Halted)?;
+    match alu.get(Variable::Z) {
+        0 => Ok(()),
+        z => Err(VerificationError::NonZero(z)),
+    }
+}
+
+#[derive(Copy, Clone)]
+enum SolutionType {
+    Largest,
+    Smallest,
+}
+
+impl SolutionType {
+    // digits in the order they should be tried
+    fn digits(&self) -> &'static [isize] {
+        match self {
+            SolutionType::Smallest => DIGITS_ASC,
+            SolutionType::Largest => DIGITS_DESC,
+        }
+    }
+}
+
+/// Part of the program processing a single digit of the model number.
+struct Segment<'a> {
+    instructions: &'a [Instruction],
+    // all the instructions from the start of this segment until the end of the program
+    remaining: &'a [Instruction],
+    // simplified form of the segment, as long as it follows the expected template
+    chunk: Result<Chunk, ChunkError>,
+    // the chunk only keeps track of `z`, so it can stand in for the instructions only if none of
+    // the later segments relies on the values left in the remaining registers
+    use_chunk: bool,
+}
+
+impl<'a> Segment<'a> {
+    fn new(instructions: &'a [Instruction], remaining: &'a [Instruction]) -> Self {
+        let chunk = Chunk::from_instructions(instructions);
+        if let Ok(chunk) = chunk {
+            debug_assert!((0..26 * 26).all(|z| chunk.agrees_with(instructions, z)));
+        }
+        Segment {
+            instructions,
+            remaining,
+            use_chunk: chunk.is_ok(),
+            chunk,
+        }
+    }
+
+    fn execute(&self, alu: Alu, w: isize) -> Option<Alu> {
+        match &self.chunk {
+            Ok(chunk) if self.use_chunk => {
+                Some(Alu::with_z(chunk.execute(w, alu.get(Variable::Z))))
+            }
+            _ => {
+                let mut alu = alu;
+                alu.execute_program(self.instructions, [w]).ok()?;
+                Some(alu)
+            }
+        }
+    }
+}
+
+/// Splits the program into the part executed before any input is read and the segments starting
+/// with each of the `inp` instructions. Segments that don't follow the template, alongside all
+/// the ones preceding them, are going to be simulated on the ALU instruction by instruction.
+fn split_program(instructions: &[Instruction]) -> (&[Instruction], Vec<Segment<'_>>) {
+    let starts = instructions
+        .iter()
+        .enumerate()
+        .filter(|(_, instruction)| matches!(instruction, Instruction::Input(_)))
+        .map(|(index, _)| index)
+        .collect::<Vec<_>>();
+    let prelude = &instructions[..starts.first().copied().unwrap_or(instructions.len())];
+
+    let mut segments = starts
+        .iter()
+        .enumerate()
+        .map(|(i, &start)| {
+            let end = starts.get(i + 1).copied().unwrap_or(instructions.len());
+            Segment::new(&instructions[start..end], &instructions[start..])
+        })
+        .collect::<Vec<_>>();
+
+    // the chunks reset all the registers apart from `z`, which the deviating segments might rely on
+    if let Some(last_deviation) = segments.iter().rposition(|segment| segment.chunk.is_err()) {
+        for segment in &mut segments[..last_deviation] {
+            segment.use_chunk = false
+        }
+    }
+
+    (prelude, segments)
+}
+
+/// Segment of the program that doesn't follow the expected template,
+/// so it's simulated on the ALU instruction by instruction rather than solved directly.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SegmentDeviation {
+    /// Index of the segment, i.e. of the digit it processes.
+    pub segment: usize,
+    /// Index of the `inp` instruction starting the segment.
+    pub start: usize,
+    pub error: ChunkError,
+}
+
+impl Display for SegmentDeviation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "segment {}", self.segment)?;
+        if let ChunkError::Deviation { index, .. } = self.error {
+            write!(f, " (line {})", self.start + index + 1)?;
+        }
+        write!(
+            f,
+            " will be simulated on the ALU as it doesn't follow the expected template - {}",
+            self.error
+        )
+    }
+}
+
+impl std::error::Error for SegmentDeviation {}
+
+/// Checks every segment of the program against the expected template,
+/// returning the ones that deviate from it.
+pub fn validate(instructions: &[Instruction]) -> Vec<SegmentDeviation> {
+    let (_, segments) = split_program(instructions);
+    segments
+        .iter()
+        .enumerate()
+        .filter_map(|(segment, s)| {
+            let error = s.chunk.as_ref().err()?.clone();
+            Some(SegmentDeviation {
+                segment,
+                start: instructions.len() - s.remaining.len(),
+                error,
+            })
+        })
+        .collect()
+}
+
+// simple bruteforce with pruning
+fn can_reach_zero(instructions: &[Instruction], alu: Alu) -> bool {
+    Ranges::from(alu)
+        .propagate(instructions, iter::repeat(Interval::DIGIT))
+        .map(|ranges| ranges.z.contains(0))
+        .unwrap_or_default()
+}
+
+/// States, alongside the number of the remaining segments, from which `z = 0` can't be reached.
+trait DeadEnds {
+    fn contains(&self, state: &(Alu, usize)) -> bool;
+
+    fn insert(&mut self, state: (Alu, usize));
+}
+
+impl DeadEnds for HashSet<(Alu, usize)> {
+    fn contains(&self, state: &(Alu, usize)) -> bool {
+        HashSet::contains(self, state)
+    }
+
+    fn insert(&mut self, state: (Alu, usize)) {
+        HashSet::insert(self, state);
+    }
+}
+
+fn check_segments<D: DeadEnds>(
+    dead_ends: &mut D,
+    alu: Alu,
+    segments: &[Segment<'_>],
+    prune: bool,
+    prefix: usize,
+    solution_type: SolutionType,
+) -> (usize, bool) {
+    // have we already seen this state at this depth?
+    if dead_ends.contains(&(alu, segments.len())) {
+        // not worth following
+        return (prefix, false);
+    }
+
+    // we have reached the final segment
+    let Some(segment) = segments.first() else {
+        return (prefix, alu.get(Variable::Z) == 0);
+    };
+
+    if prune && !can_reach_zero(segment.remaining, alu) {
+        dead_ends.insert((alu, segments.len()));
+        return (prefix, false);
+    }
+
+    for &w in solution_type.digits() {
+        // the program would have crashed with this digit
+        let Some(output) = segment.execute(alu, w) else {
+            continue;
+        };
+
+        let (val, found_valid_solution) = check_segments(
+            dead_ends,
+            output,
+            &segments[1..],
+            prune,
+            10 * prefix + w as usize,
+            solution_type,
+        );
+        // we're done, propagate the answer to the top
+        if found_valid_solution {
+            return (val, true);
+        }
+    }
+
+    // nothing useful in this branch
+    dead_ends.insert((alu, segments.len()));
+    (prefix, false)
+}
+
+fn search(
+    alu: Alu,
+    segments: &[Segment<'_>],
+    prune: bool,
+    solution_type: SolutionType,
+) -> Option<usize> {
+    let mut dead_ends = HashSet::new();
+    let (solution, is_solution_valid) =
+        check_segments(&mut dead_ends, alu, segments, prune, 0, solution_type);
+    is_solution_valid.then_some(solution)
+}
+
+fn solve(instructions: &[Instruction], solution_type: SolutionType, algorithm: Algorithm) -> usize {
+    let (prelude, segments) = split_program(instructions);
+    let alu = Alu::run_program_with_inputs(prelude, [])
+        .expect("the program halted before reading any input");
+
+    let solution = match algorithm {
+        Algorithm::Constraints => constraints::solve(alu, &segments, solution_type)
+            .or_else(|| search(alu, &segments, true, solution_type)),
+        Algorithm::Bruteforce => search(alu, &segments, false, solution_type),
+        Algorithm::Pruned => search(alu, &segments, true, solution_type),
+        #[cfg(feature = "parallel")]
+        Algorithm::Parallel => parallel::search(alu, &segments, solution_type),
+    }
+    .expect("there are no valid model numbers");
+
+    // the chunks might not have been extracted correctly, so make sure the program agrees
+    if let Err(err) = verify_model_number(instructions, solution) {
+        panic!("{solution} was rejected by the MONAD program - {err}")
+    }
+    solution
+}
+
+pub fn part1(instructions: &[Instruction], algorithm: Algorithm) -> usize {
+    solve(instructions, SolutionType::Largest, algorithm)
+}
+
+pub fn part2(instructions: &[Instruction], algorithm: Algorithm) -> usize {
+    solve(instructions, SolutionType::Smallest, algorithm)
+}
+
+/// Finds the largest and the smallest model numbers accepted by the MONAD program.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Day24 {
+    pub algorithm: Algorithm,
+}
+
+impl Solver for Day24 {
+    const DAY: u8 = 24;
+
+    type Input = Vec<Instruction>;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(&self, input_file: &Path) -> io::Result<Self::Input> {
+        read_parsed_line_input(input_file)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Self::Output1 {
+        part1(input, self.algorithm)
+    }
+
+    fn part2(&self, input: &Self::Input) -> Self::Output2 {
+        part2(input, self.algorithm)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verifying_model_numbers() {
+        // the last digit has to be equal to the first one
+        let mut program = vec!["inp z"];
+        program.extend(["inp w"; MODEL_NUMBER_DIGITS - 1]);
+        program.push("eql z w");
+        program.push("eql z 0");
+        let instructions = program
+            .into_iter()
+            .map(|line| line.parse().unwrap())
+            .collect::<Vec<Instruction>>();
+
+        assert!(verify_model_number(&instructions, 12345678912341).is_ok());
+        assert!(matches!(
+            verify_model_number(&instructions, 12345678912345),
+            Err(VerificationError::NonZero(1))
+        ));
+
+        let mut expecting_more_digits = instructions.clone();
+        expecting_more_digits.push("inp y".parse().unwrap());
+        assert!(matches!(
+            verify_model_number(&expecting_more_digits, 12345678912341),
+            Err(VerificationError::Halted(AluError::MissingInput {
+                instruction: 16
+            }))
+        ));
+    }
+    #[test]
+    fn deviating_segment_relying_on_registers() {
+        // every chunk replaces `z` with the digit while setting `x` to 1, which the final segment,
+        // deviating from the template, relies on to reset `z` to 0 regardless of the digits
+        let chunk = "inp w\nmul x 0\nadd x z\nmod x 26\ndiv z 26\nadd x 10\neql x w\neql x 0\n\
+            mul y 0\nadd y 25\nmul y x\nadd y 1\nmul z y\nmul y 0\nadd y w\nadd y 0\n\
+            mul y x\nadd z y";
+        let program = format!(
+            "{}\ninp w\nmul z 0\nadd z x\nadd z -1",
+            [chunk; MODEL_NUMBER_DIGITS - 1].join("\n")
+        );
+        let instructions = program
+            .lines()
+            .map(|line| line.trim().parse().unwrap())
+            .collect::<Vec<Instruction>>();
+
+        assert_eq!(1, validate(&instructions).len());
+        for algorithm in [Algorithm::Constraints, Algorithm::Pruned] {
+            assert_eq!(99999999999999, part1(&instructions, algorithm));
+            assert_eq!(11111111111111, part2(&instructions, algorithm));
+        }
+    }
+
+    #[test]
+    fn validating_segments() {
+        let instructions = ["mul z 0", "inp w", "add z w", "inp w", "mul x 0"]
+            .into_iter()
+            .map(|line| line.parse().unwrap())
+            .collect::<Vec<Instruction>>();
+
+        let deviations = validate(&instructions);
+        assert_eq!(2, deviations.len());
+        assert_eq!((0, 1), (deviations[0].segment, deviations[0].start));
+        assert_eq!((1, 3), (deviations[1].segment, deviations[1].start));
+        assert!(deviations[0].to_string().starts_with("segment 0 (line 3)"));
+    }
+}
@@ -12,95 +12,29 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::alu::Instruction;
-use crate::chunk::Chunk;
-use std::collections::HashSet;
-use utils::execute_slice;
-use utils::input_read::read_parsed_line_input;
+use day24::alu::optimizer::{disassemble, optimize};
+use day24::alu::Instruction;
+use day24::{validate, Algorithm, Day24};
+use utils::cli::{has_flag, parsed_flag_value};
+use utils::execute_keeping_input;
 
-mod alu;
-mod chunk;
-
-const DIGITS_ASC: &[isize] = &[1isize, 2, 3, 4, 5, 6, 7, 8, 9];
-const DIGITS_DESC: &[isize] = &[9isize, 8, 7, 6, 5, 4, 3, 2, 1];
-
-#[derive(Copy, Clone)]
-enum SolutionType {
-    Largest,
-    Smallest,
-}
-
-// simple bruteforce with pruning
-fn check_chunks(
-    dead_ends: &mut HashSet<(isize, usize)>,
-    input_z: isize,
-    chunks: &[Chunk],
-    prefix: usize,
-    solution_type: SolutionType,
-) -> (usize, bool) {
-    // have we already seen this input z at this depth?
-    if dead_ends.contains(&(input_z, chunks.len())) {
-        // not worth following
-        return (prefix, false);
-    }
+#[cfg(not(tarpaulin))]
+fn main() {
+    // e.g. `cargo run --release -- --algorithm bruteforce`, or `--algorithm parallel --threads 4`
+    let algorithm: Algorithm = parsed_flag_value("--algorithm").unwrap_or_default();
+    let instructions: Vec<Instruction> = execute_keeping_input(&Day24 { algorithm }, "input");
 
-    // we have reached the final chunk
-    if chunks.is_empty() {
-        return (prefix, input_z == 0);
+    for deviation in validate(&instructions) {
+        eprintln!("{deviation}")
     }
 
-    let ws = match solution_type {
-        SolutionType::Smallest => DIGITS_ASC,
-        SolutionType::Largest => DIGITS_DESC,
-    };
-
-    for &w in ws {
-        let output_z = chunks[0].execute(w, input_z);
-
-        let (val, found_valid_solution) = check_chunks(
-            dead_ends,
-            output_z,
-            &chunks[1..],
-            10 * prefix + w as usize,
-            solution_type,
+    if has_flag("--disassemble") {
+        let optimized = optimize(&instructions);
+        println!(
+            "\noptimized the program from {} to {} instructions\n\n{}",
+            instructions.len(),
+            optimized.len(),
+            disassemble(&optimized)
         );
-        // we're done, propagate the answer to the top
-        if found_valid_solution {
-            return (val, true);
-        }
     }
-
-    // nothing useful in this branch
-    dead_ends.insert((input_z, chunks.len()));
-    (prefix, false)
-}
-
-fn bruteforce(chunks: &[Chunk], solution_type: SolutionType) -> usize {
-    let mut dead_ends = HashSet::new();
-    let (solution, is_solution_valid) = check_chunks(&mut dead_ends, 0, chunks, 0, solution_type);
-    assert!(is_solution_valid);
-    solution
-}
-
-fn part1(instructions: &[Instruction]) -> usize {
-    let chunks = instructions
-        .chunks_exact(18)
-        .map(Chunk::from_instructions)
-        .collect::<Vec<_>>();
-
-    bruteforce(&chunks, SolutionType::Largest)
-}
-
-fn part2(instructions: &[Instruction]) -> usize {
-    let chunks = instructions
-        .chunks_exact(18)
-        .map(Chunk::from_instructions)
-        .collect::<Vec<_>>();
-
-    bruteforce(&chunks, SolutionType::Smallest)
-}
-
-#[cfg(not(tarpaulin))]
-fn main() {
-    execute_slice("input", read_parsed_line_input, part1, part2)
 }
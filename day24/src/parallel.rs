@@ -0,0 +1,73 @@
+// Copyright 2022 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::alu::Alu;
+use crate::{check_segments, DeadEnds, Segment, SolutionType};
+use dashmap::DashSet;
+use rayon::prelude::*;
+
+/// Number of the leading digits whose combinations are searched in parallel.
+const PARALLEL_DIGITS: usize = 2;
+
+// the dead ends don't depend on the digits that lead to them, so they can be shared between threads
+impl DeadEnds for &DashSet<(Alu, usize)> {
+    fn contains(&self, state: &(Alu, usize)) -> bool {
+        DashSet::contains(self, state)
+    }
+
+    fn insert(&mut self, state: (Alu, usize)) {
+        DashSet::insert(self, state);
+    }
+}
+
+// all the combinations of the leading digits, in the order they should be tried,
+// alongside the state they lead to
+fn digit_prefixes(
+    alu: Alu,
+    segments: &[Segment<'_>],
+    solution_type: SolutionType,
+) -> Vec<(usize, Alu)> {
+    let mut prefixes = vec![(0, alu)];
+    for segment in segments.iter().take(PARALLEL_DIGITS) {
+        prefixes = prefixes
+            .into_iter()
+            .flat_map(|(prefix, alu)| {
+                solution_type.digits().iter().filter_map(move |&w| {
+                    Some((10 * prefix + w as usize, segment.execute(alu, w)?))
+                })
+            })
+            .collect();
+    }
+    prefixes
+}
+
+/// Searches the combinations of the leading digits on the rayon thread pool, with the dead ends
+/// found by any of them shared between all of them.
+pub(crate) fn search(
+    alu: Alu,
+    segments: &[Segment<'_>],
+    solution_type: SolutionType,
+) -> Option<usize> {
+    let dead_ends = DashSet::new();
+    let remaining = &segments[PARALLEL_DIGITS.min(segments.len())..];
+
+    // the first prefix, in the order of preference, that leads to any solution wins
+    digit_prefixes(alu, segments, solution_type)
+        .into_par_iter()
+        .find_map_first(|(prefix, alu)| {
+            let (solution, is_solution_valid) =
+                check_segments(&mut &dead_ends, alu, remaining, true, prefix, solution_type);
+            is_solution_valid.then_some(solution)
+        })
+}
@@ -0,0 +1,159 @@
+// Copyright 2021 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::Cell;
+use utils::grid::Grid;
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// Sea floor with each herd stored as rows of bits, so that all the sea cucumbers within a row
+/// are moved at once using shifts and masks rather than cell by cell.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct BitBoard {
+    width: usize,
+    height: usize,
+    // number of words making up each row, the bits beyond the width are always unset
+    stride: usize,
+    east: Vec<u64>,
+    south: Vec<u64>,
+}
+
+impl BitBoard {
+    pub(crate) fn new(grid: &Grid<Cell>) -> Self {
+        let (width, height) = (grid.width(), grid.height());
+        let stride = width.div_ceil(WORD_BITS);
+        let mut board = BitBoard {
+            width,
+            height,
+            stride,
+            east: vec![0; stride * height],
+            south: vec![0; stride * height],
+        };
+        for ((x, y), cell) in grid.iter_with_pos() {
+            let word = y * stride + x / WORD_BITS;
+            match cell {
+                Cell::East => board.east[word] |= 1 << (x % WORD_BITS),
+                Cell::South => board.south[word] |= 1 << (x % WORD_BITS),
+                Cell::Empty => (),
+            }
+        }
+        board
+    }
+
+    pub(crate) fn write_to(&self, grid: &mut Grid<Cell>) {
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let word = y * self.stride + x / WORD_BITS;
+                let bit = 1 << (x % WORD_BITS);
+                grid[(x, y)] = if self.east[word] & bit != 0 {
+                    Cell::East
+                } else if self.south[word] & bit != 0 {
+                    Cell::South
+                } else {
+                    Cell::Empty
+                };
+            }
+        }
+    }
+
+    // bits of the last word of each row that correspond to the actual cells
+    fn last_word_mask(&self) -> u64 {
+        match self.width % WORD_BITS {
+            0 => !0,
+            bits => (1 << bits) - 1,
+        }
+    }
+
+    // moves every cell one place towards the east, wrapping around the end of the row
+    fn rotate_east(&self, row: &[u64], rotated: &mut [u64]) {
+        let mut carry = 0;
+        for (word, rotated) in row.iter().zip(rotated.iter_mut()) {
+            *rotated = word << 1 | carry;
+            carry = word >> (WORD_BITS - 1);
+        }
+        // the last cell got pushed past the end of the row, so it reappears at its start
+        let last = self.width - 1;
+        let wrapped = row[last / WORD_BITS] >> (last % WORD_BITS) & 1;
+        rotated[self.stride - 1] &= self.last_word_mask();
+        rotated[0] |= wrapped;
+    }
+
+    // moves every cell one place towards the west, wrapping around the start of the row
+    fn rotate_west(&self, row: &[u64], rotated: &mut [u64]) {
+        let mut carry = 0;
+        for (word, rotated) in row.iter().zip(rotated.iter_mut()).rev() {
+            *rotated = word >> 1 | carry;
+            carry = word << (WORD_BITS - 1);
+        }
+        let last = self.width - 1;
+        rotated[last / WORD_BITS] |= (row[0] & 1) << (last % WORD_BITS);
+    }
+
+    fn move_east_herd(&mut self) -> bool {
+        let mut moved = false;
+        let mut occupied = vec![0; self.stride];
+        let mut ahead = vec![0; self.stride];
+        let mut moving = vec![0; self.stride];
+        let mut arrived = vec![0; self.stride];
+        for row in 0..self.height {
+            let words = row * self.stride..(row + 1) * self.stride;
+            for (i, word) in words.clone().enumerate() {
+                occupied[i] = self.east[word] | self.south[word];
+            }
+            // a sea cucumber moves if the cell to its east is empty
+            self.rotate_west(&occupied, &mut ahead);
+            for (i, word) in words.clone().enumerate() {
+                moving[i] = self.east[word] & !ahead[i];
+                moved |= moving[i] != 0;
+            }
+            self.rotate_east(&moving, &mut arrived);
+            for (i, word) in words.enumerate() {
+                self.east[word] = self.east[word] & !moving[i] | arrived[i];
+            }
+        }
+        moved
+    }
+
+    fn move_south_herd(&mut self) -> bool {
+        // all the rows have to be checked before any sea cucumber moves into the row above it
+        let mut moving = vec![0; self.south.len()];
+        for row in 0..self.height {
+            let below = (row + 1) % self.height * self.stride;
+            for i in 0..self.stride {
+                let word = row * self.stride + i;
+                moving[word] = self.south[word] & !(self.east[below + i] | self.south[below + i]);
+            }
+        }
+        if moving.iter().all(|&word| word == 0) {
+            return false;
+        }
+
+        for row in 0..self.height {
+            let above = (row + self.height - 1) % self.height * self.stride;
+            for i in 0..self.stride {
+                let word = row * self.stride + i;
+                self.south[word] = self.south[word] & !moving[word] | moving[above + i];
+            }
+        }
+        true
+    }
+
+    /// Moves the east-facing herd followed by the south-facing one,
+    /// returning whether any sea cucumber has moved.
+    pub(crate) fn step(&mut self) -> bool {
+        let east_moved = self.move_east_herd();
+        let south_moved = self.move_south_herd();
+        east_moved || south_moved
+    }
+}
@@ -0,0 +1,411 @@
+// Copyright 2021 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::bitboard::BitBoard;
+use std::fmt::{self, Display, Formatter};
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+use utils::grid::{Grid, GridError, Position};
+use utils::input_read::read_parsed;
+use utils::render::FrameSink;
+use utils::{Solver, Visualize};
+
+mod bitboard;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Algorithm {
+    /// Checks every cell of the map for a sea cucumber able to move.
+    Cells,
+    /// Moves whole rows of sea cucumbers at once, with every herd stored as bitmasks.
+    #[default]
+    Bitboard,
+}
+
+impl FromStr for Algorithm {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "cells" => Ok(Algorithm::Cells),
+            "bitboard" => Ok(Algorithm::Bitboard),
+            other => Err(format!("{other} is not a valid algorithm")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MalformedSeaFloor {
+    InvalidCell { position: Position, found: char },
+    Grid(GridError),
+}
+
+impl Display for MalformedSeaFloor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            MalformedSeaFloor::InvalidCell { position, found } => {
+                write!(
+                    f,
+                    "{found} at {position:?} is neither a sea cucumber nor an empty cell"
+                )
+            }
+            MalformedSeaFloor::Grid(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl std::error::Error for MalformedSeaFloor {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Cell {
+    Empty,
+    East,
+    South,
+}
+
+impl TryFrom<char> for Cell {
+    type Error = char;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '.' => Ok(Cell::Empty),
+            '>' => Ok(Cell::East),
+            'v' => Ok(Cell::South),
+            other => Err(other),
+        }
+    }
+}
+
+impl Display for Cell {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Cell::Empty => write!(f, "."),
+            Cell::East => write!(f, ">"),
+            Cell::South => write!(f, "v"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeaFloor {
+    grid: Grid<Cell>,
+}
+
+impl FromStr for SeaFloor {
+    type Err = MalformedSeaFloor;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let rows = s
+            .lines()
+            .enumerate()
+            .map(|(y, line)| {
+                line.chars()
+                    .enumerate()
+                    .map(|(x, c)| {
+                        Cell::try_from(c).map_err(|found| MalformedSeaFloor::InvalidCell {
+                            position: (x, y),
+                            found,
+                        })
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(SeaFloor {
+            grid: Grid::from_rows(rows).map_err(MalformedSeaFloor::Grid)?,
+        })
+    }
+}
+
+impl Display for SeaFloor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for y in 0..self.grid.height() {
+            for x in 0..self.grid.width() {
+                write!(f, "{}", self.grid[(x, y)])?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl SeaFloor {
+    // the sea cucumbers leaving one edge of the map reappear on the opposite one
+    fn destination(&self, (x, y): Position, herd: Cell) -> Position {
+        match herd {
+            Cell::East => ((x + 1) % self.grid.width(), y),
+            _ => (x, (y + 1) % self.grid.height()),
+        }
+    }
+
+    // all the sea cucumbers of the herd consider the adjacent location simultaneously,
+    // so they can only move into the cells that were empty before any of them moved
+    fn move_herd(&mut self, herd: Cell) -> bool {
+        let moving = (0..self.grid.height())
+            .flat_map(|y| (0..self.grid.width()).map(move |x| (x, y)))
+            .filter(|&position| {
+                self.grid[position] == herd
+                    && self.grid[self.destination(position, herd)] == Cell::Empty
+            })
+            .collect::<Vec<_>>();
+
+        for &position in &moving {
+            let destination = self.destination(position, herd);
+            self.grid[position] = Cell::Empty;
+            self.grid[destination] = herd;
+        }
+        !moving.is_empty()
+    }
+
+    /// Moves the east-facing herd followed by the south-facing one,
+    /// returning whether any sea cucumber has moved.
+    pub fn step(&mut self) -> bool {
+        let east_moved = self.move_herd(Cell::East);
+        let south_moved = self.move_herd(Cell::South);
+        east_moved || south_moved
+    }
+
+    /// Number of the first step during which no sea cucumber moves,
+    /// with the sea floor left in its final state.
+    pub fn steps_until_stopped(&mut self, algorithm: Algorithm) -> usize {
+        let mut steps = 1;
+        match algorithm {
+            Algorithm::Cells => {
+                while self.step() {
+                    steps += 1
+                }
+            }
+            Algorithm::Bitboard => {
+                let mut board = BitBoard::new(&self.grid);
+                while board.step() {
+                    steps += 1
+                }
+                board.write_to(&mut self.grid);
+            }
+        }
+        steps
+    }
+}
+
+pub fn part1(sea_floor: &SeaFloor) -> usize {
+    part1_using(sea_floor, Algorithm::default())
+}
+
+pub fn part1_using(sea_floor: &SeaFloor, algorithm: Algorithm) -> usize {
+    sea_floor.clone().steps_until_stopped(algorithm)
+}
+
+/// There's no second puzzle on the last day, its star is awarded for collecting all the others.
+pub fn part2(_: &SeaFloor) -> &'static str {
+    "Merry Christmas!"
+}
+
+/// Moves the sea cucumbers using the chosen algorithm until they stop.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Day25 {
+    pub algorithm: Algorithm,
+}
+
+impl Solver for Day25 {
+    const DAY: u8 = 25;
+
+    type Input = SeaFloor;
+    type Output1 = usize;
+    type Output2 = &'static str;
+
+    fn parse(&self, input_file: &Path) -> io::Result<Self::Input> {
+        read_parsed(input_file)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Self::Output1 {
+        part1_using(input, self.algorithm)
+    }
+
+    fn part2(&self, input: &Self::Input) -> Self::Output2 {
+        part2(input)
+    }
+}
+
+/// Number of steps after which the visualisation stops waiting for the herds to stop moving.
+pub const MAX_VISUALIZED_STEPS: usize = 1000;
+
+// both herds after every step, starting from the initial state, until they stop moving
+fn push_herd_frames(
+    input: &SeaFloor,
+    sink: &mut dyn FrameSink,
+    max_steps: usize,
+) -> io::Result<()> {
+    let mut sea_floor = input.clone();
+    let size = (sea_floor.grid.width(), sea_floor.grid.height());
+    for _ in 0..max_steps {
+        let occupied = sea_floor
+            .grid
+            .iter_with_pos()
+            .filter(|(_, &cell)| cell != Cell::Empty)
+            .map(|(position, _)| position)
+            .collect::<Vec<_>>();
+        sink.push_frame(size, &occupied)?;
+        if !sea_floor.step() {
+            return Ok(());
+        }
+    }
+    Err(io::Error::other(format!(
+        "the sea cucumbers did not stop within {max_steps} steps"
+    )))
+}
+
+impl Visualize for Day25 {
+    fn visualize(&self, input: &Self::Input, sink: &mut dyn FrameSink) -> io::Result<()> {
+        push_herd_frames(input, sink, MAX_VISUALIZED_STEPS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utils::render::FrameBuffer;
+
+    #[test]
+    fn moving_herds() {
+        let mut sea_floor: SeaFloor = "...>>>>>...".parse().unwrap();
+        assert!(sea_floor.step());
+        assert_eq!("...>>>>.>..\n", sea_floor.to_string());
+        assert!(sea_floor.step());
+        assert_eq!("...>>>.>.>.\n", sea_floor.to_string());
+
+        let mut sea_floor: SeaFloor = "..........
+.>v....v..
+.......>..
+.........."
+            .parse()
+            .unwrap();
+        sea_floor.step();
+        assert_eq!(
+            "..........
+.>........
+..v....v>.
+..........
+",
+            sea_floor.to_string()
+        );
+
+        // the herds wrap around the edges of the map
+        let mut sea_floor: SeaFloor = "..>\nv..\n...".parse().unwrap();
+        sea_floor.step();
+        assert_eq!(">..\n...\nv..\n", sea_floor.to_string());
+    }
+
+    #[test]
+    fn part1_sample_input() {
+        let input: SeaFloor = "v...>>.vv>
+.vv>>.vv..
+>>.>v>...v
+>>v>>.>.v.
+v>v.vv.v..
+>.>>..v...
+.vv..>.>v.
+v.v..>>v.v
+....v..v.>"
+            .parse()
+            .unwrap();
+
+        let expected = 58;
+        assert_eq!(expected, part1(&input));
+        assert_eq!(expected, part1_using(&input, Algorithm::Cells));
+    }
+
+    #[test]
+    fn visualizing_herds() {
+        let input: SeaFloor = "v...>>.vv>
+.vv>>.vv..
+>>.>v>...v
+>>v>>.>.v.
+v>v.vv.v..
+>.>>..v...
+.vv..>.>v.
+v.v..>>v.v
+....v..v.>"
+            .parse()
+            .unwrap();
+
+        let mut frames = FrameBuffer::default();
+        Day25::default().visualize(&input, &mut frames).unwrap();
+        // the initial state and every step but the last one, during which nothing moves
+        assert_eq!(58, frames.frames.len());
+        assert!(frames
+            .frames
+            .iter()
+            .all(|(size, occupied)| *size == (10, 9) && occupied.len() == 49));
+
+        let mut frames = FrameBuffer::default();
+        assert!(push_herd_frames(&input, &mut frames, 10).is_err());
+        assert_eq!(10, frames.frames.len());
+    }
+
+    #[test]
+    fn bitboard_matches_cells() {
+        // the widths spanning multiple words and filling them exactly are the interesting ones
+        for (width, height) in [(10, 9), (64, 5), (70, 7), (128, 3), (139, 11)] {
+            let mut state = (width * height) as u64;
+            let map = (0..height)
+                .map(|_| {
+                    (0..width)
+                        .map(|_| {
+                            state = state
+                                .wrapping_mul(6364136223846793005)
+                                .wrapping_add(1442695040888963407);
+                            ['.', '.', '>', 'v'][(state >> 62) as usize]
+                        })
+                        .collect::<String>()
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+            let mut sea_floor: SeaFloor = map.parse().unwrap();
+            let mut board = BitBoard::new(&sea_floor.grid);
+
+            // random maps don't necessarily ever stop moving
+            for _ in 0..100 {
+                let moved = sea_floor.step();
+                assert_eq!(moved, board.step());
+                assert_eq!(BitBoard::new(&sea_floor.grid), board);
+                if !moved {
+                    break;
+                }
+            }
+
+            let mut rebuilt = sea_floor.clone();
+            board.write_to(&mut rebuilt.grid);
+            assert_eq!(sea_floor, rebuilt);
+        }
+    }
+
+    #[test]
+    fn malformed_input() {
+        assert_eq!(
+            Err(MalformedSeaFloor::InvalidCell {
+                position: (1, 1),
+                found: '<'
+            }),
+            "..\n.<".parse::<SeaFloor>()
+        );
+        assert_eq!(
+            Err(MalformedSeaFloor::Grid(GridError::RaggedRow {
+                row: 1,
+                expected: 2,
+                found: 3
+            })),
+            "..\n...".parse::<SeaFloor>()
+        );
+    }
+}
@@ -0,0 +1,66 @@
+// Copyright 2022 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::Rng;
+use std::fmt::Write;
+
+// the real inputs stay within the 1000x1000 square
+const EXTENT: isize = 999;
+
+/// Hydrothermal vent lines, either horizontal, vertical or diagonal at exactly 45 degrees.
+pub fn vent_lines(seed: u64, count: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let mut input = String::new();
+    for _ in 0..count {
+        let (x1, y1) = (rng.between(0, EXTENT), rng.between(0, EXTENT));
+        let (x2, y2) = match rng.below(3) {
+            0 => (rng.between(0, EXTENT), y1),
+            1 => (x1, rng.between(0, EXTENT)),
+            _ => {
+                // the diagonal has to fit within the square in both dimensions
+                let dx = if rng.chance(1, 2) { 1 } else { -1 };
+                let dy = if rng.chance(1, 2) { 1 } else { -1 };
+                let x_room = if dx > 0 { EXTENT - x1 } else { x1 };
+                let y_room = if dy > 0 { EXTENT - y1 } else { y1 };
+                let len = rng.between(0, x_room.min(y_room));
+                (x1 + dx * len, y1 + dy * len)
+            }
+        };
+        let _ = writeln!(input, "{x1},{y1} -> {x2},{y2}");
+    }
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lines_are_axis_aligned_or_diagonal() {
+        let input = vent_lines(5, 500);
+        assert_eq!(500, input.lines().count());
+        for line in input.lines() {
+            let coords = line
+                .split(" -> ")
+                .flat_map(|point| point.split(','))
+                .map(|coord| coord.parse::<isize>().unwrap())
+                .collect::<Vec<_>>();
+            let [x1, y1, x2, y2] = coords[..] else {
+                panic!("{line} is not a valid vent line")
+            };
+            assert!([x1, y1, x2, y2].iter().all(|c| (0..=EXTENT).contains(c)));
+            assert!(x1 == x2 || y1 == y2 || x1.abs_diff(x2) == y1.abs_diff(y2));
+        }
+    }
+}
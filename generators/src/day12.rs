@@ -0,0 +1,117 @@
+// Copyright 2022 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::Rng;
+use std::collections::HashSet;
+
+// the first 26^2 caves get two letters, the following 26^4 ones four letters and so on.
+// Sticking to pairs of letters means the small caves never clash with `start` or `end`
+fn cave_name(index: usize, big: bool) -> String {
+    let base = if big { b'A' } else { b'a' };
+    let (mut index, mut pairs) = (index, 1);
+    while let Some(names) = 676usize.checked_pow(pairs).filter(|&names| index >= names) {
+        index -= names;
+        pairs += 1;
+    }
+
+    let mut name = vec![base; 2 * pairs as usize];
+    for letter in name.iter_mut().rev() {
+        *letter += (index % 26) as u8;
+        index /= 26;
+    }
+    String::from_utf8_lossy(&name).into_owned()
+}
+
+/// Reasonable number of small caves for the generated systems. The solvers tend to take
+/// seconds with about 30 of them and become intractable soon after.
+pub const DEFAULT_SIZE: usize = 20;
+
+/// Cave system with `size` small caves and roughly a third as many big ones.
+/// Two big caves are never connected, as otherwise there would be infinitely many paths.
+/// Note that the number of paths grows exponentially, so the real inputs only have about a dozen caves
+/// and anything much larger than [`DEFAULT_SIZE`] is only useful for stressing the parsing.
+pub fn cave_system(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let small = (0..size.max(1))
+        .map(|i| cave_name(i, false))
+        .collect::<Vec<_>>();
+    let big = (0..size / 3 + 1)
+        .map(|i| cave_name(i, true))
+        .collect::<Vec<_>>();
+
+    let mut edges = Vec::new();
+    let mut seen = HashSet::new();
+    let mut connect = |a: &str, b: &str| {
+        if a != b && seen.insert((a.min(b).to_owned(), a.max(b).to_owned())) {
+            edges.push(format!("{a}-{b}"));
+        }
+    };
+    let pick_small = |rng: &mut Rng| &small[rng.below(small.len() as u64) as usize];
+
+    for terminal in ["start", "end"] {
+        for _ in 0..=rng.below(2) {
+            let cave = pick_small(&mut rng);
+            connect(terminal, cave);
+        }
+    }
+    for cave in &big {
+        for _ in 0..=rng.below(3) {
+            let other = pick_small(&mut rng);
+            connect(cave, other);
+        }
+    }
+    for cave in &small {
+        let other = if rng.chance(1, 2) {
+            &big[rng.below(big.len() as u64) as usize]
+        } else {
+            pick_small(&mut rng)
+        };
+        connect(cave, other);
+    }
+
+    edges.into_iter().map(|edge| edge + "\n").collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cave_names_are_unique() {
+        let small = (0..2000)
+            .map(|i| cave_name(i, false))
+            .collect::<HashSet<_>>();
+        assert_eq!(2000, small.len());
+        assert_eq!("zz", cave_name(675, false));
+        assert_eq!("AAAA", cave_name(676, true));
+        assert!(small.iter().all(|name| name.len() % 2 == 0));
+    }
+
+    #[test]
+    fn big_caves_are_never_adjacent() {
+        let input = cave_system(12, 40);
+        let mut edges = HashSet::new();
+        for edge in input.lines() {
+            let (a, b) = edge.split_once('-').unwrap();
+            assert_ne!(a, b);
+            assert!(
+                a.chars().any(|c| c.is_ascii_lowercase())
+                    || b.chars().any(|c| c.is_ascii_lowercase())
+            );
+            assert!(edges.insert((a.min(b), a.max(b))));
+        }
+        assert!(input.lines().any(|edge| edge.starts_with("start-")));
+        assert!(input.lines().any(|edge| edge.starts_with("end-")));
+    }
+}
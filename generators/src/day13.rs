@@ -0,0 +1,84 @@
+// Copyright 2022 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::Rng;
+
+/// Size of the sheet once all the folds are done, big enough to fit eight capital letters.
+const FOLDED_SIZE: (usize, usize) = (40, 6);
+
+/// Number of folds along each of the axes.
+const FOLDS_PER_AXIS: usize = 6;
+
+/// Transparent paper with `size` dots and alternating folds along both axes. The dots are placed
+/// on the folded sheet and then randomly reflected while unfolding it, so every fold is even
+/// and no dot ever lies on a fold line.
+pub fn transparent_paper(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+
+    // unfolding doubles the sheet alongside the fold line, so the folds are built last to first
+    let (mut width, mut height) = FOLDED_SIZE;
+    let mut unfolds = Vec::with_capacity(2 * FOLDS_PER_AXIS);
+    for _ in 0..FOLDS_PER_AXIS {
+        unfolds.push(('y', height));
+        height = 2 * height + 1;
+        unfolds.push(('x', width));
+        width = 2 * width + 1;
+    }
+
+    let mut dots = String::new();
+    for dot in 0..size.max(1) {
+        let mut x = rng.below(FOLDED_SIZE.0 as u64) as usize;
+        let mut y = rng.below(FOLDED_SIZE.1 as u64) as usize;
+        for &(axis, at) in &unfolds {
+            // the first dot always ends up past every fold line, so that the sheet has full size
+            if dot == 0 || rng.chance(1, 2) {
+                match axis {
+                    'x' => x = 2 * at - x,
+                    _ => y = 2 * at - y,
+                }
+            }
+        }
+        dots.push_str(&format!("{x},{y}\n"));
+    }
+
+    let folds = unfolds
+        .iter()
+        .rev()
+        .map(|(axis, at)| format!("fold along {axis}={at}\n"))
+        .collect::<String>();
+    format!("{dots}\n{folds}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_fold_spans_the_whole_sheet() {
+        let input = transparent_paper(3, 100);
+        let (dots, folds) = input.split_once("\n\n").unwrap();
+        assert_eq!(100, dots.lines().count());
+        assert_eq!(2 * FOLDS_PER_AXIS, folds.lines().count());
+
+        let first = folds.lines().next().unwrap();
+        let at: usize = first.rsplit('=').next().unwrap().parse().unwrap();
+        let max_x = dots
+            .lines()
+            .map(|dot| dot.split_once(',').unwrap().0.parse::<usize>().unwrap())
+            .max()
+            .unwrap();
+        assert_eq!("fold along x", &first[..12]);
+        assert!(max_x > at && max_x <= 2 * at);
+    }
+}
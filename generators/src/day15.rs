@@ -0,0 +1,43 @@
+// Copyright 2022 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::Rng;
+
+/// Square map of risk levels between 1 and 9.
+pub fn risk_map(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let mut input = String::with_capacity(size * (size + 1));
+    for _ in 0..size {
+        for _ in 0..size {
+            input.push(char::from(b'1' + rng.below(9) as u8));
+        }
+        input.push('\n');
+    }
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn map_is_square() {
+        let input = risk_map(15, 30);
+        assert_eq!(30, input.lines().count());
+        for row in input.lines() {
+            assert_eq!(30, row.len());
+            assert!(row.chars().all(|risk| ('1'..='9').contains(&risk)));
+        }
+    }
+}
@@ -0,0 +1,70 @@
+// Copyright 2022 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::Rng;
+
+// the numbers in the input are already reduced, so no pair is nested within more than 4 pairs
+const MAX_DEPTH: usize = 4;
+
+fn write_pair(rng: &mut Rng, depth: usize, out: &mut String) {
+    out.push('[');
+    write_element(rng, depth, out);
+    out.push(',');
+    write_element(rng, depth, out);
+    out.push(']');
+}
+
+fn write_element(rng: &mut Rng, depth: usize, out: &mut String) {
+    if depth < MAX_DEPTH && rng.chance(1, 2) {
+        write_pair(rng, depth + 1, out)
+    } else {
+        // and neither do they contain any values that would have to be split
+        out.push(char::from(b'0' + rng.below(10) as u8))
+    }
+}
+
+/// Reduced snailfish numbers, one per line.
+pub fn snailfish_numbers(seed: u64, count: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let mut input = String::new();
+    for _ in 0..count {
+        write_pair(&mut rng, 1, &mut input);
+        input.push('\n');
+    }
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numbers_are_reduced() {
+        let input = snailfish_numbers(18, 200);
+        assert_eq!(200, input.lines().count());
+        for number in input.lines() {
+            let mut depth = 0;
+            for c in number.chars() {
+                match c {
+                    '[' => depth += 1,
+                    ']' => depth -= 1,
+                    ',' => (),
+                    digit => assert!(digit.is_ascii_digit()),
+                }
+                assert!(depth <= MAX_DEPTH);
+            }
+            assert_eq!(0, depth);
+        }
+    }
+}
@@ -0,0 +1,71 @@
+// Copyright 2022 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::Rng;
+use std::fmt::Write;
+
+/// Number of the leading steps that, like in the real inputs, lie within the initialization area.
+const INITIALIZATION_STEPS: usize = 20;
+
+fn write_step(rng: &mut Rng, out: &mut String, start: (isize, isize), max_len: isize) {
+    let state = if rng.chance(2, 3) { "on" } else { "off" };
+    let mut range = || {
+        let from = rng.between(start.0, start.1);
+        (from, from + rng.between(0, max_len))
+    };
+    let (x, y, z) = (range(), range(), range());
+    let _ = writeln!(
+        out,
+        "{state} x={}..{},y={}..{},z={}..{}",
+        x.0, x.1, y.0, y.1, z.0, z.1
+    );
+}
+
+/// Reboot steps, the first few of which are within the initialization area, while the remaining
+/// ones span up to 20000 cubes in each dimension.
+pub fn reboot_steps(seed: u64, count: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let mut input = String::new();
+    for i in 0..count {
+        if i < INITIALIZATION_STEPS {
+            write_step(&mut rng, &mut input, (-50, 0), 50)
+        } else {
+            write_step(&mut rng, &mut input, (-100_000, 100_000), 20_000)
+        }
+    }
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn steps_start_within_initialization_area() {
+        let input = reboot_steps(22, 100);
+        assert_eq!(100, input.lines().count());
+        for (i, step) in input.lines().enumerate() {
+            let (state, ranges) = step.split_once(' ').unwrap();
+            assert!(state == "on" || state == "off");
+            for range in ranges.split(',') {
+                let (from, to) = range[2..].split_once("..").unwrap();
+                let (from, to) = (from.parse::<isize>().unwrap(), to.parse::<isize>().unwrap());
+                assert!(from <= to);
+                if i < INITIALIZATION_STEPS {
+                    assert!(from >= -50 && to <= 50);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,106 @@
+// Copyright 2022 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Deterministic generators of large, synthetic, but valid puzzle inputs, for the days whose
+//! algorithms are worth benchmarking beyond the size of the real inputs.
+//! The same seed always produces exactly the same input.
+
+pub mod day05;
+pub mod day12;
+pub mod day13;
+pub mod day15;
+pub mod day18;
+pub mod day22;
+
+/// Days for which there are input generators.
+pub const DAYS: [u8; 6] = [5, 12, 13, 15, 18, 22];
+
+/// Size of the generated input used when none is specified. Most days handle huge inputs with ease,
+/// but the number of paths through the caves of day 12 explodes with the number of caves.
+pub fn default_size(day: u8) -> usize {
+    match day {
+        12 => day12::DEFAULT_SIZE,
+        _ => 1000,
+    }
+}
+
+/// Generates the input of the specified day, with `size` determining its length,
+/// i.e. the number of lines or the width of the grid.
+pub fn generate(day: u8, seed: u64, size: usize) -> Option<String> {
+    match day {
+        5 => Some(day05::vent_lines(seed, size)),
+        12 => Some(day12::cave_system(seed, size)),
+        13 => Some(day13::transparent_paper(seed, size)),
+        15 => Some(day15::risk_map(seed, size)),
+        18 => Some(day18::snailfish_numbers(seed, size)),
+        22 => Some(day22::reboot_steps(seed, size)),
+        _ => None,
+    }
+}
+
+/// Xorshift generator, so that the inputs don't depend on any external source of randomness.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        // xorshift gets stuck on 0, and small seeds take a while to get mixed up
+        Rng(seed ^ 0x9e37_79b9_7f4a_7c15)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// Uniformly picks a value from `0..bound`.
+    pub fn below(&mut self, bound: u64) -> u64 {
+        self.next_u64() % bound
+    }
+
+    /// Uniformly picks a value from `min..=max`.
+    pub fn between(&mut self, min: isize, max: isize) -> isize {
+        min + self.below((max - min) as u64 + 1) as isize
+    }
+
+    /// Returns `true` with the probability of `numerator / denominator`.
+    pub fn chance(&mut self, numerator: u64, denominator: u64) -> bool {
+        self.below(denominator) < numerator
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generators_are_deterministic() {
+        for day in DAYS {
+            assert_eq!(generate(day, 42, 20), generate(day, 42, 20));
+            assert_ne!(generate(day, 42, 20), generate(day, 43, 20));
+        }
+        assert_eq!(None, generate(1, 42, 20));
+    }
+
+    #[test]
+    fn staying_within_bounds() {
+        let mut rng = Rng::new(0);
+        for _ in 0..1000 {
+            assert!((-3..=3).contains(&rng.between(-3, 3)));
+            assert!(rng.below(5) < 5);
+        }
+    }
+}
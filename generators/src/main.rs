@@ -0,0 +1,33 @@
+// Copyright 2022 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use generators::{default_size, generate, DAYS};
+use utils::cli::parsed_flag_value;
+
+fn main() {
+    let Some(day) = parsed_flag_value::<u8>("--day") else {
+        eprintln!("usage: generators --day <day> [--seed <seed>] [--size <size>]");
+        std::process::exit(1)
+    };
+    let seed = parsed_flag_value("--seed").unwrap_or_default();
+    let size = parsed_flag_value("--size").unwrap_or_else(|| default_size(day));
+
+    match generate(day, seed, size) {
+        Some(input) => print!("{input}"),
+        None => {
+            eprintln!("there is no input generator for day {day}, available days: {DAYS:?}");
+            std::process::exit(1)
+        }
+    }
+}
@@ -0,0 +1,254 @@
+// Copyright 2021 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Checks every registered solver against the confirmed answers recorded in `answers.toml`
+//! in the root of the workspace, so that no refactor could silently break an old day.
+
+use runner::days::{self, default_input};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use utils::execution::Part;
+use utils::input_read::InputFallback;
+
+/// Answers are recorded as integers whenever possible, and as strings otherwise,
+/// i.e. for the letters read from the manual on day 13.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(untagged)]
+pub enum RecordedAnswer {
+    Number(u64),
+    Text(String),
+}
+
+impl Display for RecordedAnswer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            RecordedAnswer::Number(number) => write!(f, "{number}"),
+            RecordedAnswer::Text(text) => write!(f, "{text}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct DayAnswers {
+    pub part1: Option<RecordedAnswer>,
+    pub part2: Option<RecordedAnswer>,
+    /// Reason for not checking the day at all, i.e. when its input was never committed.
+    pub skip: Option<String>,
+}
+
+impl DayAnswers {
+    pub fn get(&self, part: Part) -> Option<&RecordedAnswer> {
+        match part {
+            Part::One => self.part1.as_ref(),
+            Part::Two => self.part2.as_ref(),
+        }
+    }
+}
+
+pub fn workspace_root() -> PathBuf {
+    PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/.."))
+}
+
+/// Reads the recorded answers, keyed by the day, from the tables named after the days, i.e. `[day05]`.
+pub fn read_answers(path: &Path) -> io::Result<BTreeMap<u8, DayAnswers>> {
+    let invalid = |err: String| io::Error::new(io::ErrorKind::InvalidData, err);
+    let raw: BTreeMap<String, DayAnswers> =
+        toml::from_str(&fs::read_to_string(path)?).map_err(|err| invalid(err.to_string()))?;
+
+    raw.into_iter()
+        .map(|(key, answers)| {
+            key.strip_prefix("day")
+                .and_then(|day| day.parse().ok())
+                .map(|day| (day, answers))
+                .ok_or_else(|| invalid(format!("{key} is not a valid day")))
+        })
+        .collect()
+}
+
+/// Result of checking a day that didn't regress.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Checked {
+    Verified,
+    Skipped(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Regression {
+    WrongAnswer {
+        day: u8,
+        part: Part,
+        expected: String,
+        found: String,
+    },
+    SolverFailure {
+        day: u8,
+        reason: String,
+    },
+}
+
+impl Display for Regression {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Regression::WrongAnswer {
+                day,
+                part,
+                expected,
+                found,
+            } => write!(
+                f,
+                "day {day} part {part} is {found} rather than the recorded {expected}"
+            ),
+            Regression::SolverFailure { day, reason } => {
+                write!(f, "day {day} could not be solved - {reason}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for Regression {}
+
+/// Solves the recorded parts of the specified day with its registered solver and compares
+/// the results against the recorded answers. Days explicitly marked as skipped, without a solver
+/// or without their input, located according to `fallback` if it's not committed, are skipped.
+pub fn check_day(
+    day: u8,
+    recorded: &DayAnswers,
+    fallback: &InputFallback,
+) -> Result<Checked, Vec<Regression>> {
+    if let Some(reason) = &recorded.skip {
+        return Ok(Checked::Skipped(reason.clone()));
+    }
+
+    let parts = Part::BOTH
+        .into_iter()
+        .filter(|&part| recorded.get(part).is_some())
+        .collect::<Vec<_>>();
+
+    let input_file = workspace_root().join(default_input(day));
+    let report = match days::solve(day, &input_file, &parts, fallback) {
+        None => return Ok(Checked::Skipped("no solver is registered".to_owned())),
+        Some(Err(err)) if err.kind() == io::ErrorKind::NotFound => {
+            return Ok(Checked::Skipped(err.to_string()))
+        }
+        Some(Err(err)) => {
+            return Err(vec![Regression::SolverFailure {
+                day,
+                reason: err.to_string(),
+            }])
+        }
+        Some(Ok(report)) => report,
+    };
+
+    let regressions = report
+        .answers
+        .into_iter()
+        .filter_map(|answer| {
+            // only the recorded parts have been solved
+            let expected = recorded.get(answer.part)?.to_string();
+            (expected != answer.result).then_some(Regression::WrongAnswer {
+                day,
+                part: answer.part,
+                expected,
+                found: answer.result,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    if regressions.is_empty() {
+        Ok(Checked::Verified)
+    } else {
+        Err(regressions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reading_answers() {
+        let answers = read_answers(&workspace_root().join("answers.toml")).unwrap();
+        assert_eq!(
+            Some(&RecordedAnswer::Text("PCPHARKL".to_owned())),
+            answers[&13].get(Part::Two)
+        );
+        assert_eq!(
+            Some(&RecordedAnswer::Number(1791)),
+            answers[&1].get(Part::One)
+        );
+    }
+
+    #[test]
+    fn detecting_wrong_answers() {
+        let recorded = DayAnswers {
+            part1: Some(RecordedAnswer::Number(1)),
+            part2: None,
+            skip: None,
+        };
+        assert_eq!(
+            Err(vec![Regression::WrongAnswer {
+                day: 17,
+                part: Part::One,
+                expected: "1".to_owned(),
+                found: "5778".to_owned(),
+            }]),
+            check_day(17, &recorded, &InputFallback::Disabled)
+        );
+        assert!(matches!(
+            check_day(0, &recorded, &InputFallback::Disabled),
+            Ok(Checked::Skipped(_))
+        ));
+    }
+
+    #[test]
+    fn skipping_marked_days() {
+        let answers = read_answers(&workspace_root().join("answers.toml")).unwrap();
+        assert!(answers[&23].skip.is_some());
+        assert!(answers[&25].skip.is_some());
+
+        let recorded = DayAnswers {
+            part1: Some(RecordedAnswer::Number(1)),
+            part2: None,
+            skip: Some("no input".to_owned()),
+        };
+        assert_eq!(
+            Ok(Checked::Skipped("no input".to_owned())),
+            check_day(17, &recorded, &InputFallback::Disabled)
+        );
+    }
+
+    #[test]
+    fn recorded_answers_still_hold() {
+        let answers = read_answers(&workspace_root().join("answers.toml")).unwrap();
+        let mut regressions = Vec::new();
+        for (day, recorded) in &answers {
+            // only the committed inputs are used, so that the test never reaches the network
+            match check_day(*day, recorded, &InputFallback::Disabled) {
+                Ok(Checked::Verified) => (),
+                Ok(Checked::Skipped(reason)) => eprintln!("skipped day {day} - {reason}"),
+                Err(found) => regressions.extend(found),
+            }
+        }
+
+        let report = regressions
+            .iter()
+            .map(Regression::to_string)
+            .collect::<Vec<_>>();
+        assert!(regressions.is_empty(), "{}", report.join("\n"));
+    }
+}
@@ -0,0 +1,47 @@
+// Copyright 2021 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use runner::days::{self, default_input, Visitor, DAYS};
+use std::path::{Path, PathBuf};
+use utils::bench::bench_solver;
+use utils::Solver;
+
+struct Bench<'a> {
+    criterion: &'a mut Criterion,
+    input_file: PathBuf,
+}
+
+impl Visitor for Bench<'_> {
+    type Output = ();
+
+    fn visit<S: Solver>(&mut self, solver: &S) {
+        bench_solver(self.criterion, solver, &self.input_file)
+    }
+}
+
+// e.g. `cargo bench -p runner -- day19` to only benchmark a single day
+fn registered_solvers(c: &mut Criterion) {
+    let workspace_root = Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/.."));
+    for day in DAYS {
+        let mut bench = Bench {
+            criterion: c,
+            input_file: workspace_root.join(default_input(day)),
+        };
+        days::visit(day, &mut bench);
+    }
+}
+
+criterion_group!(benches, registered_solvers);
+criterion_main!(benches);
@@ -0,0 +1,248 @@
+// Copyright 2021 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io;
+use std::path::{Path, PathBuf};
+use utils::execution::{self, Part, Report};
+use utils::input_read::InputFallback;
+use utils::render::FrameSink;
+use utils::{Solver, Visualize};
+
+/// Days whose solvers are registered with the runner, in order.
+pub const DAYS: [u8; 25] = [
+    1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25,
+];
+
+/// Days whose solvers can also visualise their puzzles, in order.
+pub const VISUALIZED_DAYS: [u8; 8] = [5, 9, 11, 13, 15, 17, 20, 25];
+
+/// Location of the input of the specified day, relative to the root of the workspace.
+pub fn default_input(day: u8) -> PathBuf {
+    PathBuf::from(format!("day{day:02}/input"))
+}
+
+/// Operation performed on the solver of a particular day, so that the days could be registered
+/// in a single place regardless of their input and output types.
+pub trait Visitor {
+    type Output;
+
+    fn visit<S: Solver>(&mut self, solver: &S) -> Self::Output;
+}
+
+/// Applies the visitor to the default solver of the specified day,
+/// or returns `None` if there's no solver registered for that day.
+pub fn visit<V: Visitor>(day: u8, visitor: &mut V) -> Option<V::Output> {
+    let output = match day {
+        1 => visitor.visit(&day01::Day01),
+        2 => visitor.visit(&day02::Day02),
+        3 => visitor.visit(&day03::Day03),
+        4 => visitor.visit(&day04::Day04),
+        5 => visitor.visit(&day05::Day05),
+        6 => visitor.visit(&day06::Day06),
+        7 => visitor.visit(&day07::Day07),
+        8 => visitor.visit(&day08::Day08),
+        9 => visitor.visit(&day09::Day09),
+        10 => visitor.visit(&day10::Day10),
+        11 => visitor.visit(&day11::Day11),
+        12 => visitor.visit(&day12::Day12::default()),
+        13 => visitor.visit(&day13::Day13::default()),
+        14 => visitor.visit(&day14::Day14),
+        15 => visitor.visit(&day15::Day15::default()),
+        16 => visitor.visit(&day16::Day16),
+        17 => visitor.visit(&day17::Day17::default()),
+        18 => visitor.visit(&day18::Day18::default()),
+        19 => visitor.visit(&day19::Day19::default()),
+        20 => visitor.visit(&day20::Day20::default()),
+        21 => visitor.visit(&day21::Day21::default()),
+        22 => visitor.visit(&day22::Day22::default()),
+        23 => visitor.visit(&day23::Day23::default()),
+        24 => visitor.visit(&day24::Day24::default()),
+        25 => visitor.visit(&day25::Day25::default()),
+        _ => return None,
+    };
+    Some(output)
+}
+
+/// Equivalent of [`Visitor`] for the solvers that can also visualise their puzzles.
+pub trait VisualizeVisitor {
+    type Output;
+
+    fn visit<S: Visualize>(&mut self, solver: &S) -> Self::Output;
+}
+
+/// Applies the visitor to the default solver of the specified day,
+/// or returns `None` if there's no visualisation registered for that day.
+pub fn visit_visualized<V: VisualizeVisitor>(day: u8, visitor: &mut V) -> Option<V::Output> {
+    let output = match day {
+        5 => visitor.visit(&day05::Day05),
+        9 => visitor.visit(&day09::Day09),
+        11 => visitor.visit(&day11::Day11),
+        13 => visitor.visit(&day13::Day13::default()),
+        15 => visitor.visit(&day15::Day15::default()),
+        17 => visitor.visit(&day17::Day17::default()),
+        20 => visitor.visit(&day20::Day20::default()),
+        25 => visitor.visit(&day25::Day25::default()),
+        _ => return None,
+    };
+    Some(output)
+}
+
+struct Solve<'a> {
+    input_file: &'a Path,
+    parts: &'a [Part],
+    fallback: &'a InputFallback,
+}
+
+impl Visitor for Solve<'_> {
+    type Output = io::Result<Report>;
+
+    fn visit<S: Solver>(&mut self, solver: &S) -> Self::Output {
+        execution::solve(solver, self.input_file, self.parts, self.fallback)
+    }
+}
+
+/// Solves the requested parts of the puzzle of the specified day using its default solver,
+/// or returns `None` if there's no solver registered for that day.
+/// A missing input file is replaced by the puzzle input located according to `fallback`.
+pub fn solve(
+    day: u8,
+    input_file: &Path,
+    parts: &[Part],
+    fallback: &InputFallback,
+) -> Option<io::Result<Report>> {
+    visit(
+        day,
+        &mut Solve {
+            input_file,
+            parts,
+            fallback,
+        },
+    )
+}
+
+struct Render<'a> {
+    input_file: &'a Path,
+    fallback: &'a InputFallback,
+    sink: &'a mut dyn FrameSink,
+}
+
+impl VisualizeVisitor for Render<'_> {
+    type Output = io::Result<()>;
+
+    fn visit<S: Visualize>(&mut self, solver: &S) -> Self::Output {
+        execution::visualize(solver, self.input_file, self.fallback, self.sink)
+    }
+}
+
+/// Pushes the frames visualising the puzzle of the specified day into the sink,
+/// or returns `None` if there's no visualisation registered for that day.
+/// A missing input file is replaced by the puzzle input located according to `fallback`.
+pub fn visualize(
+    day: u8,
+    input_file: &Path,
+    fallback: &InputFallback,
+    sink: &mut dyn FrameSink,
+) -> Option<io::Result<()>> {
+    visit_visualized(
+        day,
+        &mut Render {
+            input_file,
+            fallback,
+            sink,
+        },
+    )
+}
+
+/// Solves the requested parts of the day 16 puzzle like [`solve`], additionally handing back
+/// the decoded transmission so that its packet tree could be dumped.
+pub fn solve_transmission(
+    input_file: &Path,
+    parts: &[Part],
+    fallback: &InputFallback,
+) -> io::Result<(Report, day16::Transmission)> {
+    execution::solve_keeping_input(&day16::Day16, input_file, parts, fallback)
+}
+
+/// Solves the requested parts of the day 12 puzzle like [`solve`], additionally handing back
+/// the cave system so that its paths could be listed.
+pub fn solve_cave_system(
+    input_file: &Path,
+    parts: &[Part],
+    fallback: &InputFallback,
+) -> io::Result<(Report, day12::Graph)> {
+    execution::solve_keeping_input(&day12::Day12::default(), input_file, parts, fallback)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utils::render::FrameBuffer;
+
+    #[test]
+    fn dispatching_registered_days() {
+        let missing = Path::new("definitely/missing/input");
+        let fallback = InputFallback::Disabled;
+        for day in DAYS {
+            assert!(matches!(
+                solve(day, missing, &Part::BOTH, &fallback),
+                Some(Err(_))
+            ));
+        }
+        assert!(solve(0, missing, &Part::BOTH, &fallback).is_none());
+        assert!(solve_transmission(missing, &Part::BOTH, &fallback).is_err());
+        assert!(solve_cave_system(missing, &Part::BOTH, &fallback).is_err());
+    }
+
+    struct Day;
+
+    impl Visitor for Day {
+        type Output = u8;
+
+        fn visit<S: Solver>(&mut self, _: &S) -> u8 {
+            S::DAY
+        }
+    }
+
+    #[test]
+    fn registered_solvers_match_their_days() {
+        for day in DAYS {
+            assert_eq!(Some(day), visit(day, &mut Day));
+        }
+    }
+
+    impl VisualizeVisitor for Day {
+        type Output = u8;
+
+        fn visit<S: Visualize>(&mut self, _: &S) -> u8 {
+            S::DAY
+        }
+    }
+
+    #[test]
+    fn dispatching_visualized_days() {
+        let missing = Path::new("definitely/missing/input");
+        let fallback = InputFallback::Disabled;
+        let mut frames = FrameBuffer::default();
+        for day in DAYS {
+            let visualized = visualize(day, missing, &fallback, &mut frames);
+            if VISUALIZED_DAYS.contains(&day) {
+                assert!(matches!(visualized, Some(Err(_))));
+                assert_eq!(Some(day), visit_visualized(day, &mut Day));
+            } else {
+                assert!(visualized.is_none());
+            }
+        }
+        assert!(frames.frames.is_empty());
+    }
+}
@@ -0,0 +1,232 @@
+// Copyright 2021 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use runner::days::{self, default_input, DAYS, VISUALIZED_DAYS};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use utils::cli::{flag_value, has_flag, parsed_flag_value};
+use utils::execution::{Part, Report};
+use utils::input_read::{
+    session_token, submissions_log_path, InputFallback, SESSION_TOKEN_VAR, YEAR,
+};
+use utils::render::{DirectorySink, ImageFormat};
+use utils::submission::{record_submission, recorded_verdict, submit_answer};
+use utils::Solver;
+
+const USAGE: &str = "usage: aoc2021 run --day <day> [--part <part>] [--input <path>]
+       aoc2021 run --day 12 [--part <part>] [--input <path>] --show-paths [--allow-double-small]
+       aoc2021 run --day 16 [--part <part>] [--input <path>] --tree
+       aoc2021 run --all
+       aoc2021 submit --day <day> --part <part> [--input <path>]
+       aoc2021 viz --day <day> [--input <path>] [--output <dir>] [--svg]";
+
+fn usage_error(message: &str) -> ! {
+    eprintln!("{message}\n{USAGE}");
+    std::process::exit(1)
+}
+
+fn solve(day: u8, input_file: &Path, parts: &[Part]) -> Option<Report> {
+    match days::solve(day, input_file, parts, &InputFallback::default()) {
+        Some(Ok(report)) => Some(report),
+        Some(Err(err)) => {
+            eprintln!("failed to solve day {day} - {err}");
+            None
+        }
+        None => usage_error(&format!(
+            "there is no solver for day {day}, available days: {DAYS:?}"
+        )),
+    }
+}
+
+fn input_file(day: u8) -> PathBuf {
+    parsed_flag_value("--input").unwrap_or_else(|| default_input(day))
+}
+
+fn solved<T>(day: u8, solved: io::Result<(Report, T)>) -> (Report, T) {
+    solved.unwrap_or_else(|err| {
+        eprintln!("failed to solve day {day} - {err}");
+        std::process::exit(1)
+    })
+}
+
+// e.g. `aoc2021 run --day 16 --tree`
+fn run_with_tree(day: u8, input_file: &Path, parts: &[Part]) {
+    if day != day16::Day16::DAY {
+        usage_error("--tree is only available for day 16")
+    }
+    let fallback = InputFallback::default();
+    let (report, transmission) =
+        solved(day, days::solve_transmission(input_file, parts, &fallback));
+    println!("{report}\n\n{:#}", transmission.packet)
+}
+
+// e.g. `aoc2021 run --day 12 --show-paths --allow-double-small`
+fn run_with_paths(day: u8, input_file: &Path, parts: &[Part]) {
+    if day != day12::Day12::DAY {
+        usage_error("--show-paths is only available for day 12")
+    }
+    let fallback = InputFallback::default();
+    let (report, graph) = solved(day, days::solve_cave_system(input_file, parts, &fallback));
+    println!("{report}\n");
+    for path in graph.all_paths(has_flag("--allow-double-small")) {
+        let names = path.iter().map(|node| node.name()).collect::<Vec<_>>();
+        println!("{}", names.join(","))
+    }
+}
+
+// e.g. `aoc2021 run --day 19 --part 2 --input day19/input`
+fn run_day(day: u8) {
+    let parts = match parsed_flag_value::<Part>("--part") {
+        Some(part) => vec![part],
+        None => Part::BOTH.to_vec(),
+    };
+    let input_file = input_file(day);
+    if has_flag("--tree") {
+        return run_with_tree(day, &input_file, &parts);
+    }
+    if has_flag("--show-paths") {
+        return run_with_paths(day, &input_file, &parts);
+    }
+
+    match solve(day, &input_file, &parts) {
+        Some(report) => println!("{report}"),
+        None => std::process::exit(1),
+    }
+}
+
+// e.g. `aoc2021 run --all`, from the root of the workspace
+fn run_all() {
+    let mut total = Duration::ZERO;
+    let mut failed = Vec::new();
+    for day in DAYS {
+        println!("Day {day}");
+        match solve(day, &default_input(day), &Part::BOTH) {
+            Some(report) => {
+                total += report.total_time_taken();
+                println!("{report}\n");
+            }
+            None => failed.push(day),
+        }
+    }
+
+    println!(
+        "It took {total:?} to solve {} days",
+        DAYS.len() - failed.len()
+    );
+    if !failed.is_empty() {
+        eprintln!("failed to solve days {failed:?}");
+        std::process::exit(1)
+    }
+}
+
+fn run() {
+    #[cfg(feature = "parallel")]
+    if let Err(err) = utils::parallel::configure_thread_pool() {
+        eprintln!("failed to configure the thread pool - {err}");
+        std::process::exit(1)
+    }
+
+    if has_flag("--all") {
+        run_all()
+    } else if let Some(day) = parsed_flag_value("--day") {
+        run_day(day)
+    } else {
+        usage_error("either --day or --all has to be specified")
+    }
+}
+
+// e.g. `aoc2021 submit --day 19 --part 2`
+fn submit() {
+    let Some(day) = parsed_flag_value("--day") else {
+        usage_error("--day has to be specified")
+    };
+    let Some(part) = parsed_flag_value::<Part>("--part") else {
+        usage_error("--part has to be specified")
+    };
+    let Some(token) = session_token() else {
+        eprintln!("{SESSION_TOKEN_VAR} has to be set in order to submit answers");
+        std::process::exit(1)
+    };
+
+    let Some(report) = solve(day, &input_file(day), &[part]) else {
+        std::process::exit(1)
+    };
+    // the report contains exactly the requested part
+    let answer = report.answer(part).unwrap();
+    println!("{report}\n");
+
+    let log = submissions_log_path(YEAR);
+    match recorded_verdict(&log, day, part, answer) {
+        Ok(Some(verdict)) if verdict.is_final() => {
+            println!("{answer} has already been submitted, the verdict was: {verdict}");
+            return;
+        }
+        Ok(_) => (),
+        Err(err) => eprintln!("failed to read the previous submissions - {err}"),
+    }
+
+    let verdict = match submit_answer(YEAR, day, part, answer, &token) {
+        Ok(verdict) => verdict,
+        Err(err) => {
+            eprintln!("failed to submit the answer - {err}");
+            std::process::exit(1)
+        }
+    };
+    println!("The answer is {verdict}");
+    if let Err(err) = record_submission(&log, day, part, answer, &verdict) {
+        eprintln!("failed to record the submission - {err}")
+    }
+}
+
+// e.g. `aoc2021 viz --day 25 --output viz/day25 --svg`
+fn viz() {
+    let Some(day) = parsed_flag_value("--day") else {
+        usage_error("--day has to be specified")
+    };
+    let dir = flag_value("--output").unwrap_or_else(|| format!("viz/day{day:02}"));
+    let format = if has_flag("--svg") {
+        ImageFormat::Svg
+    } else {
+        ImageFormat::Pbm
+    };
+
+    let mut sink = match DirectorySink::new(&dir, "frame", format) {
+        Ok(sink) => sink,
+        Err(err) => {
+            eprintln!("failed to create {dir} - {err}");
+            std::process::exit(1)
+        }
+    };
+    match days::visualize(day, &input_file(day), &InputFallback::default(), &mut sink) {
+        Some(Ok(())) => println!("rendered {} frames of day {day} into {dir}", sink.written()),
+        Some(Err(err)) => {
+            eprintln!("failed to visualize day {day} - {err}");
+            std::process::exit(1)
+        }
+        None => usage_error(&format!(
+            "there is no visualization for day {day}, available days: {VISUALIZED_DAYS:?}"
+        )),
+    }
+}
+
+#[cfg(not(tarpaulin))]
+fn main() {
+    match std::env::args().nth(1).as_deref() {
+        Some("run") => run(),
+        Some("submit") => submit(),
+        Some("viz") => viz(),
+        _ => usage_error("unknown command"),
+    }
+}
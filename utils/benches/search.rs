@@ -0,0 +1,74 @@
+// Copyright 2022 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use utils::search::{dial, dijkstra};
+
+// same dimensions as the fully tiled cave of day 15
+const SIZE: usize = 500;
+const MAX_RISK: usize = 9;
+
+fn risk_levels() -> Vec<usize> {
+    // xorshift, so that the grid is the same for every run
+    let mut state = 0x2545_f491_4f6c_dd1d_u64;
+    (0..SIZE * SIZE)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state % MAX_RISK as u64) as usize + 1
+        })
+        .collect()
+}
+
+fn neighbours(index: usize) -> impl Iterator<Item = usize> {
+    let (x, y) = (index % SIZE, index / SIZE);
+    [
+        (x.wrapping_sub(1), y),
+        (x + 1, y),
+        (x, y.wrapping_sub(1)),
+        (x, y + 1),
+    ]
+    .into_iter()
+    .filter(|&(x, y)| x < SIZE && y < SIZE)
+    .map(|(x, y)| y * SIZE + x)
+}
+
+fn shortest_path(c: &mut Criterion) {
+    let risks = risk_levels();
+    let risks = &risks;
+    let goal = SIZE * SIZE - 1;
+
+    let mut group = c.benchmark_group("shortest_path");
+    group.sample_size(10);
+    group.bench_function("heap", |b| {
+        b.iter(|| {
+            dijkstra(
+                black_box(&0),
+                |&index| neighbours(index).map(move |next| (next, risks[next])),
+                |&index| index == goal,
+            )
+        })
+    });
+    group.bench_function("bucket", |b| {
+        b.iter(|| {
+            dial(SIZE * SIZE, black_box(0), goal, MAX_RISK, |index| {
+                neighbours(index).map(move |next| (next, risks[next]))
+            })
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, shortest_path);
+criterion_main!(benches);
@@ -0,0 +1,45 @@
+// Copyright 2021 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::input_read::{locate_input, InputFallback};
+use crate::solver::Solver;
+use criterion::{black_box, Criterion};
+use std::path::Path;
+
+/// Benchmarks parsing the input and solving both parts of the puzzle with the provided solver,
+/// within the `dayNN` group. Days whose input can't be read are skipped.
+pub fn bench_solver<S: Solver>(c: &mut Criterion, solver: &S, input_file: &Path) {
+    let input_file = &match locate_input(input_file, S::DAY, &InputFallback::default()) {
+        Ok(input_file) => input_file,
+        Err(err) => {
+            eprintln!("skipping day {} - {err}", S::DAY);
+            return;
+        }
+    };
+    let input = match solver.parse(input_file) {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("skipping day {} - {err}", S::DAY);
+            return;
+        }
+    };
+
+    let mut group = c.benchmark_group(format!("day{:02}", S::DAY));
+    // some of the days take seconds to solve
+    group.sample_size(10);
+    group.bench_function("parse", |b| b.iter(|| solver.parse(black_box(input_file))));
+    group.bench_function("part1", |b| b.iter(|| solver.part1(black_box(&input))));
+    group.bench_function("part2", |b| b.iter(|| solver.part2(black_box(&input))));
+    group.finish();
+}
@@ -0,0 +1,51 @@
+// Copyright 2022 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::env;
+use std::str::FromStr;
+
+/// Checks whether the specified flag, i.e. `--verbose`, has been passed to the binary.
+pub fn has_flag(flag: &str) -> bool {
+    env::args().skip(1).any(|arg| arg == flag)
+}
+
+/// Returns the value passed alongside the specified flag, either as `--flag value` or `--flag=value`.
+pub fn flag_value(flag: &str) -> Option<String> {
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == flag {
+            return args.next();
+        }
+        if let Some(value) = arg
+            .strip_prefix(flag)
+            .and_then(|remaining| remaining.strip_prefix('='))
+        {
+            return Some(value.to_owned());
+        }
+    }
+    None
+}
+
+/// Attempts to parse the value passed alongside the specified flag into the desired type.
+/// Panics if the value has been provided, but it's malformed.
+pub fn parsed_flag_value<T>(flag: &str) -> Option<T>
+where
+    T: FromStr,
+    <T as FromStr>::Err: std::fmt::Debug,
+{
+    flag_value(flag).map(|raw| {
+        raw.parse()
+            .unwrap_or_else(|err| panic!("invalid value provided for {flag} - {err:?}"))
+    })
+}
@@ -12,87 +12,272 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::fmt::Display;
+use crate::input_read::{locate_input, InputFallback};
+use crate::render::FrameSink;
+use crate::solver::{SharedSolver, Solver, Visualize};
+use std::fmt::{self, Display, Formatter};
 use std::io;
 use std::path::Path;
+use std::str::FromStr;
 use std::time::{Duration, Instant};
 
-pub fn execute_slice_with_timing<F, T, U>(func: F, args: &[T]) -> (U, Duration)
+/// Runs the provided closure, measuring how long it took.
+pub fn execute_with_timing<F, U>(func: F) -> (U, Duration)
 where
-    F: Fn(&[T]) -> U,
+    F: FnOnce() -> U,
 {
     let start = Instant::now();
-    let res = func(args);
+    let res = func();
     let time_taken = start.elapsed();
     (res, time_taken)
 }
 
-pub fn execute_struct_with_timing<F, T, U>(func: F, args: T) -> (U, Duration)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const BOTH: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(&self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            other => Err(format!("{other} is not a valid part")),
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Answer {
+    pub part: Part,
+    pub result: String,
+    pub time_taken: Duration,
+}
+
+/// Answers to the requested parts of the puzzle alongside the time it took to compute them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    pub parsing_time_taken: Duration,
+    /// Time taken by the computation shared by both parts, if it was timed separately from the parsing.
+    pub preparation_time_taken: Option<Duration>,
+    pub answers: Vec<Answer>,
+}
+
+impl Report {
+    pub fn answer(&self, part: Part) -> Option<&str> {
+        self.answers
+            .iter()
+            .find(|answer| answer.part == part)
+            .map(|answer| answer.result.as_str())
+    }
+
+    pub fn total_time_taken(&self) -> Duration {
+        self.parsing_time_taken
+            + self.preparation_time_taken.unwrap_or_default()
+            + self
+                .answers
+                .iter()
+                .map(|answer| answer.time_taken)
+                .sum::<Duration>()
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "It took {:?} to parse the input",
+            self.parsing_time_taken
+        )?;
+        if let Some(preparation_time_taken) = self.preparation_time_taken {
+            write!(
+                f,
+                "\nIt took {preparation_time_taken:?} to prepare the shared input"
+            )?;
+        }
+        for answer in &self.answers {
+            write!(
+                f,
+                "\n\nPart {} result is {}\nIt took {:?} to compute",
+                answer.part, answer.result, answer.time_taken
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses the input file and solves the specified parts of the puzzle with the provided solver,
+/// measuring the time each step took.
+/// A missing input file is replaced by the puzzle input located according to `fallback`.
+pub fn solve<S: Solver>(
+    solver: &S,
+    input_file: &Path,
+    parts: &[Part],
+    fallback: &InputFallback,
+) -> io::Result<Report> {
+    solve_keeping_input(solver, input_file, parts, fallback).map(|(report, _)| report)
+}
+
+/// Equivalent of [`solve`] that hands the parsed input back to the caller,
+/// so that it can be inspected further without parsing it again.
+pub fn solve_keeping_input<S: Solver>(
+    solver: &S,
+    input_file: &Path,
+    parts: &[Part],
+    fallback: &InputFallback,
+) -> io::Result<(Report, S::Input)> {
+    let input_file = &locate_input(input_file, S::DAY, fallback)?;
+
+    let (input, parsing_time_taken) = execute_with_timing(|| solver.parse(input_file));
+    let input = input?;
+
+    let report = Report {
+        parsing_time_taken,
+        preparation_time_taken: None,
+        answers: solve_parsed(solver, &input, parts),
+    };
+    Ok((report, input))
+}
+
+/// Parses the input file and pushes the frames of its visualisation into the sink.
+/// A missing input file is replaced by the puzzle input located according to `fallback`.
+pub fn visualize<S: Visualize>(
+    solver: &S,
+    input_file: &Path,
+    fallback: &InputFallback,
+    sink: &mut dyn FrameSink,
+) -> io::Result<()> {
+    let input_file = &locate_input(input_file, S::DAY, fallback)?;
+    let input = solver.parse(input_file)?;
+    solver.visualize(&input, sink)
+}
+
+fn solve_parsed<S: Solver>(solver: &S, input: &S::Input, parts: &[Part]) -> Vec<Answer> {
+    parts
+        .iter()
+        .map(|&part| {
+            let (result, time_taken) = match part {
+                Part::One => execute_with_timing(|| solver.part1(input).to_string()),
+                Part::Two => execute_with_timing(|| solver.part2(input).to_string()),
+            };
+            Answer {
+                part,
+                result,
+                time_taken,
+            }
+        })
+        .collect()
+}
+
+/// Parses the input file and solves both parts of the puzzle with the provided solver,
+/// printing the results alongside the time each step took.
+/// A missing input file is replaced by the cached puzzle input, downloaded if needed.
+/// With the `parallel` feature, it also configures the rayon thread pool according to
+/// the `--threads` flag.
+pub fn try_execute<S, P>(solver: &S, input_file: P) -> io::Result<()>
 where
-    F: Fn(T) -> U,
+    S: Solver,
+    P: AsRef<Path>,
 {
-    let start = Instant::now();
-    let res = func(args);
-    let time_taken = start.elapsed();
-    (res, time_taken)
+    try_execute_keeping_input(solver, input_file).map(|_| ())
+}
+
+fn try_execute_keeping_input<S, P>(solver: &S, input_file: P) -> io::Result<S::Input>
+where
+    S: Solver,
+    P: AsRef<Path>,
+{
+    #[cfg(feature = "parallel")]
+    crate::parallel::configure_thread_pool().map_err(io::Error::other)?;
+
+    let (report, input) = solve_keeping_input(
+        solver,
+        input_file.as_ref(),
+        &Part::BOTH,
+        &InputFallback::default(),
+    )?;
+    println!("{report}");
+    Ok(input)
 }
 
-// We'll see how it evolves with variety of inputs we get
-pub fn execute_slice<P, T, F, G, H, U, S>(input_file: P, input_parser: F, part1_fn: G, part2_fn: H)
+/// Equivalent of [`try_execute`] that reports the failure to read or parse the input
+/// and terminates the process.
+pub fn execute<S, P>(solver: &S, input_file: P)
 where
+    S: Solver,
     P: AsRef<Path>,
-    F: Fn(P) -> io::Result<Vec<T>>,
-    G: Fn(&[T]) -> U,
-    H: Fn(&[T]) -> S,
-    U: Display,
-    S: Display,
 {
-    let parsing_start = Instant::now();
-    let input = input_parser(input_file).expect("failed to read input file");
-    let parsing_time_taken = parsing_start.elapsed();
-
-    let (part1_result, part1_time_taken) = execute_slice_with_timing(part1_fn, &input);
-    let (part2_result, part2_time_taken) = execute_slice_with_timing(part2_fn, &input);
-
-    println!("It took {parsing_time_taken:?} to parse the input");
-    println!();
-    println!(
-        "Part 1 result is {}\nIt took {:?} to compute",
-        part1_result, part1_time_taken
-    );
-    println!();
-    println!(
-        "Part 2 result is {}\nIt took {:?} to compute",
-        part2_result, part2_time_taken
-    );
-}
-
-pub fn execute_struct<P, T, F, G, H, U, S>(input_file: P, input_parser: F, part1_fn: G, part2_fn: H)
+    execute_keeping_input(solver, input_file);
+}
+
+/// Equivalent of [`execute`] that hands the parsed input back to the caller,
+/// so that it can be inspected further without parsing it again.
+pub fn execute_keeping_input<S, P>(solver: &S, input_file: P) -> S::Input
+where
+    S: Solver,
+    P: AsRef<Path>,
+{
+    try_execute_keeping_input(solver, input_file).unwrap_or_else(|err| {
+        eprintln!("failed to solve the puzzle - {err}");
+        std::process::exit(1)
+    })
+}
+
+/// Equivalent of [`try_execute`] for a [`SharedSolver`], reporting the time taken by
+/// the shared computation separately from reading the input. The prepared input is returned
+/// for any further use by the caller.
+pub fn try_execute_shared<S, P>(solver: &S, input_file: P) -> io::Result<S::Input>
+where
+    S: SharedSolver,
+    P: AsRef<Path>,
+{
+    #[cfg(feature = "parallel")]
+    crate::parallel::configure_thread_pool().map_err(io::Error::other)?;
+
+    let input_file = &locate_input(input_file.as_ref(), S::DAY, &InputFallback::default())?;
+
+    let (raw, parsing_time_taken) = execute_with_timing(|| solver.read(input_file));
+    let (input, preparation_time_taken) = execute_with_timing(|| solver.prepare(raw?));
+    let input = input?;
+
+    let report = Report {
+        parsing_time_taken,
+        preparation_time_taken: Some(preparation_time_taken),
+        answers: solve_parsed(solver, &input, &Part::BOTH),
+    };
+    println!("{report}");
+    Ok(input)
+}
+
+/// Equivalent of [`try_execute_shared`] that reports the failure to read or prepare the input
+/// and terminates the process.
+pub fn execute_shared<S, P>(solver: &S, input_file: P) -> S::Input
 where
+    S: SharedSolver,
     P: AsRef<Path>,
-    F: Fn(P) -> io::Result<T>,
-    G: Fn(T) -> U,
-    H: Fn(T) -> S,
-    U: Display,
-    S: Display,
-    T: Clone,
 {
-    let parsing_start = Instant::now();
-    let input = input_parser(input_file).expect("failed to read input file");
-    let parsing_time_taken = parsing_start.elapsed();
-
-    let (part1_result, part1_time_taken) = execute_struct_with_timing(part1_fn, input.clone());
-    let (part2_result, part2_time_taken) = execute_struct_with_timing(part2_fn, input);
-
-    println!("It took {parsing_time_taken:?} to parse the input");
-    println!();
-    println!(
-        "Part 1 result is {}\nIt took {:?} to compute",
-        part1_result, part1_time_taken
-    );
-    println!();
-    println!(
-        "Part 2 result is {}\nIt took {:?} to compute",
-        part2_result, part2_time_taken
-    );
+    try_execute_shared(solver, input_file).unwrap_or_else(|err| {
+        eprintln!("failed to solve the puzzle - {err}");
+        std::process::exit(1)
+    })
 }
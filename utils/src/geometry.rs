@@ -0,0 +1,641 @@
+// Copyright 2022 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::{self, Display, Formatter};
+use std::num::ParseIntError;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParsePointError {
+    MissingCoordinate(char),
+    InvalidCoordinate(char, ParseIntError),
+    TrailingData(String),
+}
+
+impl Display for ParsePointError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ParsePointError::MissingCoordinate(axis) => write!(f, "no {axis} value present"),
+            ParsePointError::InvalidCoordinate(axis, err) => {
+                write!(f, "invalid {axis} value - {err}")
+            }
+            ParsePointError::TrailingData(data) => {
+                write!(f, "unexpected data after the coordinates: {data}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParsePointError {}
+
+/// Parses the comma-separated coordinates of the specified axes, e.g. `x,y` for `['x', 'y']`.
+fn parse_coordinates<const N: usize>(
+    s: &str,
+    axes: [char; N],
+) -> Result<[isize; N], ParsePointError> {
+    let mut split = s.split(',');
+    let mut coordinates = [0; N];
+    for (coordinate, axis) in coordinates.iter_mut().zip(axes) {
+        *coordinate = split
+            .next()
+            .ok_or(ParsePointError::MissingCoordinate(axis))?
+            .parse()
+            .map_err(|err| ParsePointError::InvalidCoordinate(axis, err))?;
+    }
+
+    let remaining = split.collect::<Vec<_>>();
+    if !remaining.is_empty() {
+        return Err(ParsePointError::TrailingData(remaining.join(",")));
+    }
+    Ok(coordinates)
+}
+
+/// Point, or a displacement, on the 2D plane.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Hash, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Vec2 {
+    pub x: isize,
+    pub y: isize,
+}
+
+impl From<(isize, isize)> for Vec2 {
+    fn from((x, y): (isize, isize)) -> Self {
+        Vec2 { x, y }
+    }
+}
+
+impl Add<Vec2> for Vec2 {
+    type Output = Vec2;
+
+    fn add(self, rhs: Vec2) -> Self::Output {
+        Vec2 {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y,
+        }
+    }
+}
+
+impl AddAssign<Vec2> for Vec2 {
+    fn add_assign(&mut self, rhs: Vec2) {
+        *self = *self + rhs
+    }
+}
+
+impl Sub<Vec2> for Vec2 {
+    type Output = Vec2;
+
+    fn sub(self, rhs: Vec2) -> Self::Output {
+        Vec2 {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+        }
+    }
+}
+
+impl Neg for Vec2 {
+    type Output = Vec2;
+
+    fn neg(self) -> Self::Output {
+        Vec2 {
+            x: -self.x,
+            y: -self.y,
+        }
+    }
+}
+
+impl Mul<isize> for Vec2 {
+    type Output = Vec2;
+
+    fn mul(self, rhs: isize) -> Self::Output {
+        Vec2 {
+            x: self.x * rhs,
+            y: self.y * rhs,
+        }
+    }
+}
+
+impl Display for Vec2 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+/// Parses the point out of its comma-separated coordinates, i.e. `x,y`.
+impl FromStr for Vec2 {
+    type Err = ParsePointError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let [x, y] = parse_coordinates(s, ['x', 'y'])?;
+        Ok(Vec2 { x, y })
+    }
+}
+
+impl Vec2 {
+    #[inline]
+    pub const fn new(x: isize, y: isize) -> Self {
+        Vec2 { x, y }
+    }
+
+    #[inline]
+    pub const fn origin() -> Self {
+        Vec2 { x: 0, y: 0 }
+    }
+
+    /// Unit step towards the direction of the vector along each of the axes.
+    #[inline]
+    pub const fn signum(&self) -> Self {
+        Vec2 {
+            x: self.x.signum(),
+            y: self.y.signum(),
+        }
+    }
+
+    /// Rotates the vector counterclockwise about the origin.
+    #[inline]
+    pub const fn rot_90(&self) -> Self {
+        Vec2 {
+            x: -self.y,
+            y: self.x,
+        }
+    }
+
+    #[inline]
+    pub const fn rot_180(&self) -> Self {
+        Vec2 {
+            x: -self.x,
+            y: -self.y,
+        }
+    }
+
+    #[inline]
+    pub const fn rot_270(&self) -> Self {
+        Vec2 {
+            x: self.y,
+            y: -self.x,
+        }
+    }
+
+    /// The vector in each of the 4 orientations reachable with 90 degree rotations,
+    /// starting with the identity.
+    #[inline]
+    pub const fn all_rotations(&self) -> [Self; 4] {
+        [*self, self.rot_90(), self.rot_180(), self.rot_270()]
+    }
+
+    #[inline]
+    pub const fn manhattan_distance(&self, other: &Self) -> usize {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+}
+
+/// Point, or a displacement, in the 3D space, with the axes following the right-hand rule.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Hash, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Vec3 {
+    pub x: isize,
+    pub y: isize,
+    pub z: isize,
+}
+
+impl From<(isize, isize, isize)> for Vec3 {
+    fn from((x, y, z): (isize, isize, isize)) -> Self {
+        Vec3 { x, y, z }
+    }
+}
+
+impl Add<Vec3> for Vec3 {
+    type Output = Vec3;
+
+    fn add(self, rhs: Vec3) -> Self::Output {
+        Vec3 {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y,
+            z: self.z + rhs.z,
+        }
+    }
+}
+
+impl Sub<Vec3> for Vec3 {
+    type Output = Vec3;
+
+    fn sub(self, rhs: Vec3) -> Self::Output {
+        Vec3 {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+            z: self.z - rhs.z,
+        }
+    }
+}
+
+impl Neg for Vec3 {
+    type Output = Vec3;
+
+    fn neg(self) -> Self::Output {
+        Vec3 {
+            x: -self.x,
+            y: -self.y,
+            z: -self.z,
+        }
+    }
+}
+
+impl Mul<isize> for Vec3 {
+    type Output = Vec3;
+
+    fn mul(self, rhs: isize) -> Self::Output {
+        Vec3 {
+            x: self.x * rhs,
+            y: self.y * rhs,
+            z: self.z * rhs,
+        }
+    }
+}
+
+impl Display for Vec3 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{},{}", self.x, self.y, self.z)
+    }
+}
+
+/// Parses the point out of its comma-separated coordinates, i.e. `x,y,z`.
+impl FromStr for Vec3 {
+    type Err = ParsePointError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let [x, y, z] = parse_coordinates(s, ['x', 'y', 'z'])?;
+        Ok(Vec3 { x, y, z })
+    }
+}
+
+impl Vec3 {
+    #[inline]
+    pub const fn new(x: isize, y: isize, z: isize) -> Self {
+        Vec3 { x, y, z }
+    }
+
+    #[inline]
+    pub const fn origin() -> Self {
+        Vec3 { x: 0, y: 0, z: 0 }
+    }
+
+    #[inline]
+    pub const fn rot_90x(&self) -> Self {
+        Vec3 {
+            x: self.x,
+            y: -self.z,
+            z: self.y,
+        }
+    }
+
+    #[inline]
+    pub const fn rot_180x(&self) -> Self {
+        Vec3 {
+            x: self.x,
+            y: -self.y,
+            z: -self.z,
+        }
+    }
+
+    #[inline]
+    pub const fn rot_270x(&self) -> Self {
+        Vec3 {
+            x: self.x,
+            y: self.z,
+            z: -self.y,
+        }
+    }
+
+    #[inline]
+    pub const fn rot_90y(&self) -> Self {
+        Vec3 {
+            x: self.z,
+            y: self.y,
+            z: -self.x,
+        }
+    }
+
+    #[inline]
+    pub const fn rot_180y(&self) -> Self {
+        Vec3 {
+            x: -self.x,
+            y: self.y,
+            z: -self.z,
+        }
+    }
+
+    #[inline]
+    pub const fn rot_270y(&self) -> Self {
+        Vec3 {
+            x: -self.z,
+            y: self.y,
+            z: self.x,
+        }
+    }
+
+    #[inline]
+    pub const fn rot_90z(&self) -> Self {
+        Vec3 {
+            x: -self.y,
+            y: self.x,
+            z: self.z,
+        }
+    }
+
+    #[inline]
+    pub const fn rot_180z(&self) -> Self {
+        Vec3 {
+            x: -self.x,
+            y: -self.y,
+            z: self.z,
+        }
+    }
+
+    #[inline]
+    pub const fn rot_270z(&self) -> Self {
+        Vec3 {
+            x: self.y,
+            y: -self.x,
+            z: self.z,
+        }
+    }
+
+    /// The point in each of the 24 orientations reachable with 90 degree rotations,
+    /// starting with the identity.
+    #[inline]
+    pub const fn all_rotations(&self) -> [Self; 24] {
+        [
+            // x0:
+            *self,
+            self.rot_90y(),
+            self.rot_180y(),
+            self.rot_270y(),
+            self.rot_90z(),
+            self.rot_270z(),
+            // x90:
+            self.rot_90x(),
+            self.rot_90x().rot_90y(),
+            self.rot_90x().rot_180y(),
+            self.rot_90x().rot_270y(),
+            self.rot_90x().rot_90z(),
+            self.rot_90x().rot_270z(),
+            // x180:
+            self.rot_180x(),
+            self.rot_180x().rot_90y(),
+            self.rot_180x().rot_180y(),
+            self.rot_180x().rot_270y(),
+            self.rot_180x().rot_90z(),
+            self.rot_180x().rot_270z(),
+            // x270:
+            self.rot_270x(),
+            self.rot_270x().rot_90y(),
+            self.rot_270x().rot_180y(),
+            self.rot_270x().rot_270y(),
+            self.rot_270x().rot_90z(),
+            self.rot_270x().rot_270z(),
+        ]
+    }
+
+    #[inline]
+    pub const fn squared_distance(&self, other: &Self) -> isize {
+        let dx = self.x - other.x;
+        let dy = self.y - other.y;
+        let dz = self.z - other.z;
+        dx * dx + dy * dy + dz * dz
+    }
+
+    #[inline]
+    pub const fn manhattan_distance(&self, other: &Self) -> usize {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y) + self.z.abs_diff(other.z)
+    }
+}
+
+/// One of the 24 rotations mapping the axes onto the axes while preserving the handedness,
+/// i.e. the orientations reachable with 90 degree rotations. Stored as the rotation matrix.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(transparent))]
+pub struct Rotation3 {
+    matrix: [[isize; 3]; 3],
+}
+
+impl Rotation3 {
+    pub const IDENTITY: Rotation3 = Rotation3 {
+        matrix: [[1, 0, 0], [0, 1, 0], [0, 0, 1]],
+    };
+
+    /// All the rotations, in the same order as [`Vec3::all_rotations`].
+    pub const ALL: [Rotation3; 24] = {
+        let xs = Vec3::new(1, 0, 0).all_rotations();
+        let ys = Vec3::new(0, 1, 0).all_rotations();
+        let zs = Vec3::new(0, 0, 1).all_rotations();
+
+        let mut all = [Rotation3::IDENTITY; 24];
+        let mut i = 0;
+        while i < all.len() {
+            all[i] = Rotation3::from_columns(xs[i], ys[i], zs[i]);
+            i += 1;
+        }
+        all
+    };
+
+    // columns of the matrix are the images of the unit vectors
+    const fn from_columns(x: Vec3, y: Vec3, z: Vec3) -> Self {
+        Rotation3 {
+            matrix: [[x.x, y.x, z.x], [x.y, y.y, z.y], [x.z, y.z, z.z]],
+        }
+    }
+
+    #[inline]
+    pub const fn matrix(&self) -> [[isize; 3]; 3] {
+        self.matrix
+    }
+
+    #[inline]
+    pub const fn apply(&self, v: Vec3) -> Vec3 {
+        let m = &self.matrix;
+        Vec3 {
+            x: m[0][0] * v.x + m[0][1] * v.y + m[0][2] * v.z,
+            y: m[1][0] * v.x + m[1][1] * v.y + m[1][2] * v.z,
+            z: m[2][0] * v.x + m[2][1] * v.y + m[2][2] * v.z,
+        }
+    }
+
+    /// Rotation undoing this one.
+    #[inline]
+    pub const fn inverse(&self) -> Self {
+        // rotation matrices are orthogonal, so the inverse is just the transpose
+        let m = &self.matrix;
+        Rotation3 {
+            matrix: [
+                [m[0][0], m[1][0], m[2][0]],
+                [m[0][1], m[1][1], m[2][1]],
+                [m[0][2], m[1][2], m[2][2]],
+            ],
+        }
+    }
+
+    /// Rotation equivalent to applying this one followed by the `next` one.
+    #[inline]
+    pub const fn then(&self, next: &Rotation3) -> Self {
+        let columns = [
+            Vec3::new(self.matrix[0][0], self.matrix[1][0], self.matrix[2][0]),
+            Vec3::new(self.matrix[0][1], self.matrix[1][1], self.matrix[2][1]),
+            Vec3::new(self.matrix[0][2], self.matrix[1][2], self.matrix[2][2]),
+        ];
+        Rotation3::from_columns(
+            next.apply(columns[0]),
+            next.apply(columns[1]),
+            next.apply(columns[2]),
+        )
+    }
+}
+
+impl Default for Rotation3 {
+    fn default() -> Self {
+        Rotation3::IDENTITY
+    }
+}
+
+impl From<Rotation3> for [[isize; 3]; 3] {
+    fn from(rotation: Rotation3) -> Self {
+        rotation.matrix
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn parsing_points() {
+        assert_eq!(Ok(Vec3::from((-1, 2, 30))), "-1,2,30".parse());
+        assert_eq!(
+            Err(ParsePointError::MissingCoordinate('z')),
+            "1,2".parse::<Vec3>()
+        );
+        assert!(matches!(
+            "1,a,3".parse::<Vec3>(),
+            Err(ParsePointError::InvalidCoordinate('y', _))
+        ));
+        assert_eq!(
+            Err(ParsePointError::TrailingData("4".to_string())),
+            "1,2,3,4".parse::<Vec3>()
+        );
+
+        let point = Vec3::from((5, -6, 7));
+        assert_eq!(Ok(point), point.to_string().parse());
+    }
+
+    #[test]
+    fn rotations_are_distinct() {
+        let point = Vec3::from((1, 2, 3));
+        let rotations = point.all_rotations();
+        assert_eq!(point, rotations[0]);
+        assert_eq!(24, rotations.iter().collect::<HashSet<_>>().len());
+        for rotated in rotations {
+            assert_eq!(
+                point.squared_distance(&Vec3::origin()),
+                rotated.squared_distance(&Vec3::origin())
+            );
+        }
+    }
+
+    #[test]
+    fn planar_vectors() {
+        assert_eq!(Ok(Vec2::new(-1, 20)), "-1,20".parse());
+        assert_eq!(
+            Err(ParsePointError::MissingCoordinate('y')),
+            "1".parse::<Vec2>()
+        );
+        assert_eq!(
+            Err(ParsePointError::TrailingData("3".to_string())),
+            "1,2,3".parse::<Vec2>()
+        );
+
+        let a = Vec2::new(3, -4);
+        let b = Vec2::new(-2, 1);
+        assert_eq!(Vec2::new(1, -3), a + b);
+        assert_eq!(Vec2::new(5, -5), a - b);
+        assert_eq!(Vec2::new(-3, 4), -a);
+        assert_eq!(Vec2::new(6, -8), a * 2);
+        assert_eq!(Vec2::new(1, -1), a.signum());
+        assert_eq!(10, a.manhattan_distance(&b));
+
+        let rotations = a.all_rotations();
+        assert_eq!(Vec2::new(4, 3), rotations[1]);
+        assert_eq!(a, rotations[3].rot_90());
+    }
+
+    #[test]
+    fn axis_rotations() {
+        let points = [
+            Vec3::new(230, 43, 780),
+            Vec3::new(-230, -43, -780),
+            Vec3::new(0, -43, 780),
+            Vec3::new(-230, 0, -780),
+            Vec3::new(-230, 43, 0),
+        ];
+        for pos in points {
+            assert_eq!(pos.rot_90x().rot_90x(), pos.rot_180x());
+            assert_eq!(pos.rot_90x().rot_90x().rot_90x(), pos.rot_270x());
+            assert_eq!(pos.rot_180x().rot_90x(), pos.rot_270x());
+
+            assert_eq!(pos.rot_90y().rot_90y(), pos.rot_180y());
+            assert_eq!(pos.rot_90y().rot_90y().rot_90y(), pos.rot_270y());
+            assert_eq!(pos.rot_180y().rot_90y(), pos.rot_270y());
+
+            assert_eq!(pos.rot_90z().rot_90z(), pos.rot_180z());
+            assert_eq!(pos.rot_90z().rot_90z().rot_90z(), pos.rot_270z());
+            assert_eq!(pos.rot_180z().rot_90z(), pos.rot_270z());
+        }
+    }
+
+    #[test]
+    fn rotation_matrices() {
+        let point = Vec3::new(1, 2, 3);
+        assert_eq!(Rotation3::IDENTITY, Rotation3::ALL[0]);
+        assert_eq!(24, Rotation3::ALL.iter().collect::<HashSet<_>>().len());
+
+        for (rotation, rotated) in Rotation3::ALL.iter().zip(point.all_rotations()) {
+            assert_eq!(rotated, rotation.apply(point));
+            assert_eq!(point, rotation.inverse().apply(rotated));
+            assert_eq!(Rotation3::IDENTITY, rotation.then(&rotation.inverse()));
+        }
+
+        // the rotations form a group, so composing any two of them yields another one
+        for a in Rotation3::ALL {
+            for b in Rotation3::ALL {
+                let composed = a.then(&b);
+                assert!(Rotation3::ALL.contains(&composed));
+                assert_eq!(b.apply(a.apply(point)), composed.apply(point));
+            }
+        }
+
+        // 90 degrees about the y axis
+        let matrix: [[isize; 3]; 3] = Rotation3::ALL[1].into();
+        assert_eq!([[0, 0, 1], [0, 1, 0], [-1, 0, 0]], matrix);
+    }
+
+    #[test]
+    fn distances() {
+        let a = Vec3::from((1105, -1205, 1229));
+        let b = Vec3::from((-92, -2380, -20));
+        assert_eq!(3621, a.manhattan_distance(&b));
+        assert_eq!(a.manhattan_distance(&b), b.manhattan_distance(&a));
+        assert_eq!(b, a + (b - a));
+        assert_eq!(Vec3::origin(), a + -a);
+        assert_eq!(Vec3::new(2210, -2410, 2458), a * 2);
+    }
+}
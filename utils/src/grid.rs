@@ -0,0 +1,297 @@
+// Copyright 2022 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::{self, Display, Formatter};
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+
+/// `(x, y)` position within a grid, with `(0, 0)` being the top left corner.
+pub type Position = (usize, usize);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GridError {
+    Empty,
+    RaggedRow {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    InvalidDigit {
+        position: Position,
+        found: char,
+    },
+}
+
+impl Display for GridError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            GridError::Empty => write!(f, "the grid does not contain any cells"),
+            GridError::RaggedRow {
+                row,
+                expected,
+                found,
+            } => write!(
+                f,
+                "row {row} has {found} cells while {expected} were expected"
+            ),
+            GridError::InvalidDigit { position, found } => {
+                write!(f, "{found} at {position:?} is not a valid digit")
+            }
+        }
+    }
+}
+
+impl std::error::Error for GridError {}
+
+/// Rectangular grid of cells stored in a flat, row-major vector.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Builds the grid out of its rows, making sure all of them are of the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self, GridError> {
+        let width = rows.first().map(Vec::len).unwrap_or_default();
+        if width == 0 {
+            return Err(GridError::Empty);
+        }
+        let height = rows.len();
+
+        let mut cells = Vec::with_capacity(width * height);
+        for (row, values) in rows.into_iter().enumerate() {
+            if values.len() != width {
+                return Err(GridError::RaggedRow {
+                    row,
+                    expected: width,
+                    found: values.len(),
+                });
+            }
+            cells.extend(values)
+        }
+
+        Ok(Grid {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, (x, y): Position) -> Option<&T> {
+        if x < self.width && y < self.height {
+            self.cells.get(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, (x, y): Position) -> Option<&mut T> {
+        if x < self.width && y < self.height {
+            self.cells.get_mut(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    /// Orthogonally adjacent positions that are still within the grid.
+    pub fn neighbours4(&self, position: Position) -> impl Iterator<Item = Position> {
+        orthogonal_neighbours(position, self.width, self.height)
+    }
+
+    /// Orthogonally and diagonally adjacent positions that are still within the grid,
+    /// in row-major order.
+    pub fn neighbours8(&self, (x, y): Position) -> impl Iterator<Item = Position> {
+        let xs = x.saturating_sub(1)..=(x + 1).min(self.width - 1);
+        let ys = y.saturating_sub(1)..=(y + 1).min(self.height - 1);
+        ys.flat_map(move |ny| xs.clone().map(move |nx| (nx, ny)))
+            .filter(move |&neighbour| neighbour != (x, y))
+    }
+
+    /// All the cells alongside their positions, in row-major order.
+    pub fn iter_with_pos(&self) -> impl Iterator<Item = (Position, &T)> {
+        let width = self.width;
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(i, cell)| ((i % width, i / width), cell))
+    }
+
+    /// All the cells in row-major order.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.cells.iter_mut()
+    }
+
+    /// Grid of the same dimensions with the function applied to each of the cells.
+    pub fn map<U, F>(&self, f: F) -> Grid<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl Grid<u8> {
+    /// Parses grid of single digit cells out of its rows, such as `["123", "456"]`.
+    pub fn from_digit_lines<I, S>(lines: I) -> Result<Self, GridError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let rows = lines
+            .into_iter()
+            .enumerate()
+            .map(|(y, row)| {
+                row.as_ref()
+                    .chars()
+                    .enumerate()
+                    .map(|(x, c)| {
+                        c.to_digit(10)
+                            .map(|digit| digit as u8)
+                            .ok_or(GridError::InvalidDigit {
+                                position: (x, y),
+                                found: c,
+                            })
+                    })
+                    .collect()
+            })
+            .collect::<Result<_, _>>()?;
+
+        Grid::from_rows(rows)
+    }
+}
+
+impl FromStr for Grid<u8> {
+    type Err = GridError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Grid::from_digit_lines(s.lines())
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &Self::Output {
+        self.get(position).unwrap_or_else(|| {
+            panic!(
+                "{position:?} is outside the {}x{} grid",
+                self.width, self.height
+            )
+        })
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut Self::Output {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(position)
+            .unwrap_or_else(|| panic!("{position:?} is outside the {width}x{height} grid"))
+    }
+}
+
+/// Orthogonally adjacent positions (left, right, top and bottom, in that order) that fit within
+/// the area of the specified dimensions.
+pub fn orthogonal_neighbours(
+    (x, y): Position,
+    width: usize,
+    height: usize,
+) -> impl Iterator<Item = Position> {
+    let left = (x > 0).then(|| (x - 1, y));
+    let right = (x + 1 < width).then_some((x + 1, y));
+    let top = (y > 0).then(|| (x, y - 1));
+    let bottom = (y + 1 < height).then_some((x, y + 1));
+
+    [left, right, top, bottom].into_iter().flatten()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parsing_digits() {
+        let grid: Grid<u8> = "123\n456".parse().unwrap();
+        assert_eq!(3, grid.width());
+        assert_eq!(2, grid.height());
+        assert_eq!(Some(&6), grid.get((2, 1)));
+        assert_eq!(None, grid.get((3, 0)));
+        assert_eq!(4, grid[(0, 1)]);
+
+        assert_eq!(Err(GridError::Empty), "".parse::<Grid<u8>>());
+        assert_eq!(
+            Err(GridError::InvalidDigit {
+                position: (1, 1),
+                found: 'x'
+            }),
+            "12\n3x".parse::<Grid<u8>>()
+        );
+        assert_eq!(
+            Err(GridError::RaggedRow {
+                row: 1,
+                expected: 2,
+                found: 3
+            }),
+            "12\n345".parse::<Grid<u8>>()
+        );
+    }
+
+    #[test]
+    fn neighbours_stay_within_bounds() {
+        let grid: Grid<u8> = "123\n456".parse().unwrap();
+        assert_eq!(
+            vec![(1, 0), (0, 1)],
+            grid.neighbours4((0, 0)).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![(0, 1), (2, 1), (1, 0)],
+            grid.neighbours4((1, 1)).collect::<Vec<_>>()
+        );
+
+        assert_eq!(
+            vec![(1, 0), (0, 1), (1, 1)],
+            grid.neighbours8((0, 0)).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![(0, 0), (2, 0), (0, 1), (1, 1), (2, 1)],
+            grid.neighbours8((1, 0)).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn iterating_and_mapping_cells() {
+        let grid = Grid::from_digit_lines(["12", "34"]).unwrap();
+        assert_eq!(
+            vec![((0, 0), &1), ((1, 0), &2), ((0, 1), &3), ((1, 1), &4)],
+            grid.iter_with_pos().collect::<Vec<_>>()
+        );
+
+        let doubled = grid.map(|&cell| cell as usize * 2);
+        assert_eq!(2, doubled.width());
+        assert_eq!(8, doubled[(1, 1)]);
+    }
+}
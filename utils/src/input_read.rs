@@ -12,11 +12,11 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::fmt::Debug;
+use std::fmt::Display;
 use std::fs;
 use std::fs::File;
 use std::io::{self, BufRead};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
 pub fn read_input_lines<P>(path: P) -> io::Result<Vec<String>>
@@ -44,7 +44,7 @@ pub fn read_parsed_line_input<T, P>(path: P) -> io::Result<Vec<T>>
 where
     P: AsRef<Path>,
     T: FromStr,
-    <T as FromStr>::Err: Debug,
+    <T as FromStr>::Err: Display,
 {
     read_input_lines(path)?
         .into_iter()
@@ -53,7 +53,7 @@ where
         .map_err(|err| {
             io::Error::new(
                 io::ErrorKind::InvalidData,
-                format!("input could not be parsed into desired type - {err:?}"),
+                format!("input could not be parsed into desired type - {err}"),
             )
         })
 }
@@ -73,7 +73,7 @@ pub fn read_parsed_groups<T, P>(path: P) -> io::Result<Vec<T>>
 where
     P: AsRef<Path>,
     T: FromStr,
-    <T as FromStr>::Err: Debug,
+    <T as FromStr>::Err: Display,
 {
     read_into_string_groups(path)?
         .into_iter()
@@ -82,7 +82,7 @@ where
         .map_err(|err| {
             io::Error::new(
                 io::ErrorKind::InvalidData,
-                format!("input could not be parsed into desired type - {err:?}"),
+                format!("input could not be parsed into desired type - {err}"),
             )
         })
 }
@@ -92,7 +92,7 @@ pub fn read_parsed_comma_separated_values<T, P>(path: P) -> io::Result<Vec<T>>
 where
     P: AsRef<Path>,
     T: FromStr,
-    <T as FromStr>::Err: Debug,
+    <T as FromStr>::Err: Display,
 {
     fs::read_to_string(path)?
         .split(',')
@@ -101,7 +101,7 @@ where
         .map_err(|err| {
             io::Error::new(
                 io::ErrorKind::InvalidData,
-                format!("input could not be parsed into desired type - {err:?}"),
+                format!("input could not be parsed into desired type - {err}"),
             )
         })
 }
@@ -110,12 +110,193 @@ pub fn read_parsed<T, P>(path: P) -> io::Result<T>
 where
     P: AsRef<Path>,
     T: FromStr,
-    <T as FromStr>::Err: Debug,
+    <T as FromStr>::Err: Display,
 {
     fs::read_to_string(path).map(|s| s.parse())?.map_err(|err| {
         io::Error::new(
             io::ErrorKind::InvalidData,
-            format!("input could not be parsed into desired type - {err:?}"),
+            format!("input could not be parsed into desired type - {err}"),
         )
     })
 }
+
+/// Year of the puzzles whose inputs are fetched.
+pub const YEAR: u16 = 2021;
+
+/// Environment variable holding the session cookie used for fetching the inputs.
+pub const SESSION_TOKEN_VAR: &str = "AOC_SESSION";
+
+/// Identifies the requests made to the Advent of Code website, as requested by its author.
+#[cfg(feature = "download")]
+pub(crate) const USER_AGENT: &str = "github.com/jstuczyn/AdventOfCode2021";
+
+// the inputs are personal, so they're cached outside of the day directories and aren't committed
+pub(crate) fn workspace_root() -> &'static Path {
+    Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/.."))
+}
+
+/// Location of the log of all the answers submitted for the puzzles of the specified year.
+pub fn submissions_log_path(year: u16) -> PathBuf {
+    workspace_root()
+        .join("inputs")
+        .join(year.to_string())
+        .join("submissions")
+}
+
+/// Location of the cached input of the specified puzzle, under `inputs/` in the root of the workspace.
+pub fn cached_input_path(year: u16, day: u8) -> PathBuf {
+    workspace_root()
+        .join("inputs")
+        .join(year.to_string())
+        .join(format!("day{day:02}"))
+}
+
+/// Reads the session token from the `AOC_SESSION` environment variable or, failing that,
+/// from the `.aoc-session` file in the root of the workspace.
+pub fn session_token() -> Option<String> {
+    std::env::var(SESSION_TOKEN_VAR)
+        .ok()
+        .or_else(|| fs::read_to_string(workspace_root().join(".aoc-session")).ok())
+        .map(|token| token.trim().to_owned())
+        .filter(|token| !token.is_empty())
+}
+
+/// Downloads the input of the specified puzzle, unless it has already been cached,
+/// returning the path to the cached file.
+#[cfg(feature = "download")]
+pub fn fetch_input(year: u16, day: u8, session_token: &str) -> io::Result<PathBuf> {
+    let cached = cached_input_path(year, day);
+    if cached.exists() {
+        return Ok(cached);
+    }
+    download_input(year, day, session_token, &cached)?;
+    Ok(cached)
+}
+
+#[cfg(feature = "download")]
+fn download_input(year: u16, day: u8, session_token: &str, destination: &Path) -> io::Result<()> {
+    let url = format!("https://adventofcode.com/{year}/day/{day}/input");
+    let input = ureq::get(&url)
+        .set("Cookie", &format!("session={session_token}"))
+        .set("User-Agent", USER_AGENT)
+        .call()
+        .map_err(io::Error::other)?
+        .into_string()?;
+
+    if let Some(dir) = destination.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(destination, input)
+}
+
+/// Where the puzzle input is looked for when the specified input file doesn't exist.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputFallback {
+    /// Nowhere, the specified input file has to exist.
+    Disabled,
+    /// In the directory holding the inputs named after their days, i.e. `day05`.
+    /// With the `download` feature and `download` set, the inputs missing from it
+    /// are fetched using the session token.
+    Cached { dir: PathBuf, download: bool },
+}
+
+impl InputFallback {
+    /// Inputs cached under `inputs/` in the root of the workspace, downloading the missing ones.
+    pub fn workspace() -> Self {
+        InputFallback::Cached {
+            dir: workspace_root().join("inputs").join(YEAR.to_string()),
+            download: true,
+        }
+    }
+}
+
+impl Default for InputFallback {
+    fn default() -> Self {
+        InputFallback::workspace()
+    }
+}
+
+/// Returns the specified input file if it exists, falling back to the input of the puzzle
+/// located according to `fallback` otherwise.
+pub fn locate_input(input_file: &Path, day: u8, fallback: &InputFallback) -> io::Result<PathBuf> {
+    if input_file.exists() {
+        return Ok(input_file.to_path_buf());
+    }
+    let missing = |reason: &str| {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("{} does not exist{reason}", input_file.display()),
+        )
+    };
+
+    let InputFallback::Cached { dir, download } = fallback else {
+        return Err(missing(""));
+    };
+    let cached = dir.join(format!("day{day:02}"));
+    if cached.exists() {
+        return Ok(cached);
+    }
+    if !download {
+        return Err(missing(" and neither does the cached input"));
+    }
+
+    #[cfg(feature = "download")]
+    {
+        let token = session_token().ok_or_else(|| {
+            missing(&format!(
+                " and {SESSION_TOKEN_VAR} is not set to download it"
+            ))
+        })?;
+        download_input(YEAR, day, &token, &cached)?;
+        Ok(cached)
+    }
+    #[cfg(not(feature = "download"))]
+    Err(missing(" and downloading the inputs is disabled"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn caching_inputs_in_workspace() {
+        let cached = cached_input_path(2021, 5);
+        assert!(cached.ends_with("inputs/2021/day05"));
+        assert!(cached.starts_with(workspace_root()));
+    }
+
+    #[test]
+    fn preferring_local_input() {
+        let input_file = std::env::temp_dir().join("utils_local_input");
+        fs::write(&input_file, "42\n").unwrap();
+        for fallback in [InputFallback::Disabled, InputFallback::workspace()] {
+            assert_eq!(input_file, locate_input(&input_file, 1, &fallback).unwrap());
+        }
+        fs::remove_file(input_file).unwrap();
+    }
+
+    #[test]
+    fn falling_back_to_cached_input() {
+        let dir = std::env::temp_dir().join("utils_cached_inputs");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("day07"), "42\n").unwrap();
+        let missing = dir.join("input");
+        let fallback = InputFallback::Cached {
+            dir: dir.clone(),
+            download: false,
+        };
+
+        assert_eq!(
+            dir.join("day07"),
+            locate_input(&missing, 7, &fallback).unwrap()
+        );
+        // nothing gets downloaded without being asked to
+        let err = locate_input(&missing, 8, &fallback).unwrap_err();
+        assert_eq!(io::ErrorKind::NotFound, err.kind());
+        assert!(!dir.join("day08").exists());
+
+        let err = locate_input(&missing, 7, &InputFallback::Disabled).unwrap_err();
+        assert_eq!(io::ErrorKind::NotFound, err.kind());
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -12,8 +12,23 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(feature = "bench")]
+pub mod bench;
+pub mod cli;
+pub mod disjoint_set;
 pub mod execution;
+pub mod geometry;
+pub mod grid;
 pub mod input_read;
+pub mod memo;
+pub mod ocr;
+#[cfg(feature = "parallel")]
+pub mod parallel;
 pub mod parsing;
+pub mod render;
+pub mod search;
+pub mod solver;
+pub mod submission;
 
-pub use execution::execute_slice;
+pub use execution::{execute, execute_keeping_input, execute_shared};
+pub use solver::{SharedSolver, Solver, Visualize};
@@ -0,0 +1,80 @@
+// Copyright 2022 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Cache of the values computed for the already visited states, meant for the top-down
+/// dynamic programming, where computing the value of a state requires the values of its successors.
+#[derive(Debug, Clone)]
+pub struct Memo<S, V> {
+    cache: HashMap<S, V>,
+}
+
+impl<S, V> Default for Memo<S, V> {
+    fn default() -> Self {
+        Memo {
+            cache: HashMap::new(),
+        }
+    }
+}
+
+impl<S: Eq + Hash, V: Clone> Memo<S, V> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the cached value of the state or computes it otherwise.
+    /// The computation is given the memo itself, so that it could recurse into other states.
+    pub fn get_or_insert_with<F>(&mut self, state: S, compute: F) -> V
+    where
+        F: FnOnce(&mut Self) -> V,
+    {
+        if let Some(value) = self.cache.get(&state) {
+            return value.clone();
+        }
+
+        let value = compute(self);
+        self.cache.insert(state, value.clone());
+        value
+    }
+
+    /// Number of the distinct states that got evaluated.
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fibonacci(memo: &mut Memo<u64, u64>, n: u64) -> u64 {
+        if n < 2 {
+            return n;
+        }
+        memo.get_or_insert_with(n, |memo| fibonacci(memo, n - 1) + fibonacci(memo, n - 2))
+    }
+
+    #[test]
+    fn memoized_recursion() {
+        let mut memo = Memo::new();
+        assert_eq!(12586269025, fibonacci(&mut memo, 50));
+        assert_eq!(49, memo.len());
+    }
+}
@@ -0,0 +1,121 @@
+// Copyright 2022 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Recognition of the capital letters rendered by the puzzles using the 4x6 Advent of Code font,
+//! where each letter is 4 pixels wide and separated from the next one by a single empty column.
+
+use std::collections::HashSet;
+
+pub const GLYPH_WIDTH: usize = 4;
+pub const GLYPH_HEIGHT: usize = 6;
+const GLYPH_SPACING: usize = 1;
+
+const GLYPHS: &[(char, &str)] = &[
+    ('A', ".##.#..##..######..##..#"),
+    ('B', "###.#..####.#..##..####."),
+    ('C', ".##.#..##...#...#..#.##."),
+    ('E', "#####...###.#...#...####"),
+    ('F', "#####...###.#...#...#..."),
+    ('G', ".##.#..##...#.###..#.###"),
+    ('H', "#..##..######..##..##..#"),
+    ('I', ".###..#...#...#...#..###"),
+    ('J', "..##...#...#...##..#.##."),
+    ('K', "#..##.#.##..#.#.#.#.#..#"),
+    ('L', "#...#...#...#...#...####"),
+    ('O', ".##.#..##..##..##..#.##."),
+    ('P', "###.#..##..####.#...#..."),
+    ('R', "###.#..##..####.#.#.#..#"),
+    ('S', ".####...#....##....####."),
+    ('U', "#..##..##..##..##..#.##."),
+    ('Z', "####...#..#..#..#...####"),
+];
+
+fn recognise_glyph(lit: &HashSet<(usize, usize)>, x_offset: usize) -> Option<char> {
+    let glyph = (0..GLYPH_HEIGHT)
+        .flat_map(|y| (0..GLYPH_WIDTH).map(move |x| (x + x_offset, y)))
+        .map(|pos| if lit.contains(&pos) { '#' } else { '.' })
+        .collect::<String>();
+
+    GLYPHS
+        .iter()
+        .find(|(_, pattern)| *pattern == glyph)
+        .map(|(letter, _)| *letter)
+}
+
+/// Attempts to read the text formed by the provided lit pixels, given as `(x, y)` coordinates,
+/// with the text starting at the origin.
+/// Returns `None` if the image is not exactly 6 pixels tall or any of the glyphs is not recognised.
+pub fn recognise_letters<I>(lit: I) -> Option<String>
+where
+    I: IntoIterator<Item = (usize, usize)>,
+{
+    let lit = lit.into_iter().collect::<HashSet<_>>();
+    let max_x = lit.iter().map(|(x, _)| *x).max()?;
+    let max_y = lit.iter().map(|(_, y)| *y).max()?;
+    if max_y + 1 != GLYPH_HEIGHT {
+        return None;
+    }
+
+    let letters = (max_x + 1 + GLYPH_SPACING).div_ceil(GLYPH_WIDTH + GLYPH_SPACING);
+    (0..letters)
+        .map(|i| recognise_glyph(&lit, i * (GLYPH_WIDTH + GLYPH_SPACING)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(rows: &[&str]) -> Vec<(usize, usize)> {
+        rows.iter()
+            .enumerate()
+            .flat_map(|(y, row)| {
+                row.chars()
+                    .enumerate()
+                    .filter(|(_, c)| *c == '#')
+                    .map(move |(x, _)| (x, y))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn recognising_letters() {
+        let image = render(&[
+            "###...##..###..#..#..##..###..#..#.#...",
+            "#..#.#..#.#..#.#..#.#..#.#..#.#.#..#...",
+            "#..#.#....#..#.####.#..#.#..#.##...#...",
+            "###..#....###..#..#.####.###..#.#..#...",
+            "#....#..#.#....#..#.#..#.#.#..#.#..#...",
+            "#.....##..#....#..#.#..#.#..#.#..#.####",
+        ]);
+        assert_eq!(Some("PCPHARKL".to_owned()), recognise_letters(image));
+
+        let image = render(&[
+            "..##.#..#",
+            "...#.#..#",
+            "...#.####",
+            "...#.#..#",
+            "#..#.#..#",
+            ".##..#..#",
+        ]);
+        assert_eq!(Some("JH".to_owned()), recognise_letters(image));
+    }
+
+    #[test]
+    fn rejecting_unknown_glyphs() {
+        let square = render(&["#####", "#...#", "#...#", "#...#", "#####"]);
+        assert_eq!(None, recognise_letters(square));
+        assert_eq!(None, recognise_letters(Vec::new()));
+    }
+}
@@ -0,0 +1,62 @@
+// Copyright 2022 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::cli::parsed_flag_value;
+use rayon::{ThreadPoolBuildError, ThreadPoolBuilder};
+use std::sync::Once;
+
+/// Flag specifying the number of threads used by the parallel variants, i.e. `--threads 4`.
+pub const THREADS_FLAG: &str = "--threads";
+
+/// Configures the global rayon thread pool with the number of threads passed via `--threads`.
+/// Without the flag, rayon's default of one thread per logical core is used.
+/// The global pool can only be built once per process, so only the first call has any effect.
+pub fn configure_thread_pool() -> Result<(), ThreadPoolBuildError> {
+    static CONFIGURED: Once = Once::new();
+
+    let mut result = Ok(());
+    CONFIGURED.call_once(|| {
+        if let Some(threads) = parsed_flag_value(THREADS_FLAG) {
+            result = ThreadPoolBuilder::new().num_threads(threads).build_global()
+        }
+    });
+    result
+}
+
+/// Runs the operation on a dedicated pool of the specified number of threads, so that any rayon
+/// work it performs is limited to them. Useful for comparing thread counts within a single
+/// process, i.e. in benchmarks, as the global pool can't be rebuilt.
+pub fn with_threads<R, F>(threads: usize, op: F) -> Result<R, ThreadPoolBuildError>
+where
+    R: Send,
+    F: FnOnce() -> R + Send,
+{
+    let pool = ThreadPoolBuilder::new().num_threads(threads).build()?;
+    Ok(pool.install(op))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn running_with_threads() {
+        for threads in [1, 3] {
+            assert_eq!(
+                Ok(threads),
+                with_threads(threads, rayon::current_num_threads).map_err(|err| err.to_string())
+            );
+        }
+    }
+}
@@ -0,0 +1,334 @@
+// Copyright 2022 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::grid::Grid;
+use std::collections::BTreeSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+// size of a single pixel in the produced svg images
+const SVG_PIXEL_SIZE: usize = 10;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ImageFormat {
+    Svg,
+    /// Plain (ASCII) portable bitmap.
+    Pbm,
+}
+
+impl ImageFormat {
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Option<Self> {
+        let extension = path.as_ref().extension()?.to_str()?;
+        match extension.to_ascii_lowercase().as_str() {
+            "svg" => Some(ImageFormat::Svg),
+            "pbm" => Some(ImageFormat::Pbm),
+            _ => None,
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ImageFormat::Svg => "svg",
+            ImageFormat::Pbm => "pbm",
+        }
+    }
+
+    fn render(&self, lit: &[(usize, usize)], size: (usize, usize)) -> String {
+        match self {
+            ImageFormat::Svg => svg_with_size(lit, size),
+            ImageFormat::Pbm => pbm_with_size(lit, size),
+        }
+    }
+}
+
+fn dimensions(lit: &[(usize, usize)]) -> (usize, usize) {
+    let width = lit.iter().map(|(x, _)| x + 1).max().unwrap_or_default();
+    let height = lit.iter().map(|(_, y)| y + 1).max().unwrap_or_default();
+    (width, height)
+}
+
+/// Renders the lit pixels, given as `(x, y)` coordinates, as a black-on-white svg image.
+pub fn to_svg(lit: &[(usize, usize)]) -> String {
+    svg_with_size(lit, dimensions(lit))
+}
+
+fn svg_with_size(lit: &[(usize, usize)], (width, height): (usize, usize)) -> String {
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\">\n",
+        width * SVG_PIXEL_SIZE,
+        height * SVG_PIXEL_SIZE
+    );
+    svg.push_str("<rect width=\"100%\" height=\"100%\" fill=\"white\"/>\n");
+    for (x, y) in lit {
+        svg.push_str(&format!(
+            "<rect x=\"{}\" y=\"{}\" width=\"{SVG_PIXEL_SIZE}\" height=\"{SVG_PIXEL_SIZE}\" fill=\"black\"/>\n",
+            x * SVG_PIXEL_SIZE,
+            y * SVG_PIXEL_SIZE,
+        ));
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Renders the lit pixels, given as `(x, y)` coordinates, as a plain portable bitmap.
+pub fn to_pbm(lit: &[(usize, usize)]) -> String {
+    pbm_with_size(lit, dimensions(lit))
+}
+
+fn pbm_with_size(lit: &[(usize, usize)], (width, height): (usize, usize)) -> String {
+    let mut pixels = vec![vec!['0'; width]; height];
+    for (x, y) in lit {
+        pixels[*y][*x] = '1';
+    }
+
+    let mut pbm = format!("P1\n{width} {height}\n");
+    for row in pixels {
+        pbm.push_str(&row.into_iter().collect::<String>());
+        pbm.push('\n');
+    }
+    pbm
+}
+
+/// Writes the lit pixels into the specified file, with the format deduced from its extension.
+pub fn write_image<P, I>(path: P, lit: I) -> io::Result<()>
+where
+    P: AsRef<Path>,
+    I: IntoIterator<Item = (usize, usize)>,
+{
+    let lit = lit.into_iter().collect::<Vec<_>>();
+    let size = dimensions(&lit);
+    write_image_with_size(path, size, lit)
+}
+
+/// Writes the lit pixels into the image of the specified `(width, height)`, which unlike
+/// [`write_image`] allows the image to have unlit margins on its right and bottom.
+/// Pixels outside of the image are ignored.
+pub fn write_image_with_size<P, I>(path: P, size: (usize, usize), lit: I) -> io::Result<()>
+where
+    P: AsRef<Path>,
+    I: IntoIterator<Item = (usize, usize)>,
+{
+    let (width, height) = size;
+    let lit = lit
+        .into_iter()
+        .filter(|&(x, y)| x < width && y < height)
+        .collect::<Vec<_>>();
+    let format = ImageFormat::from_path(&path)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "unsupported image format"))?;
+    fs::write(path, format.render(&lit, size))
+}
+
+/// Destination of the consecutive frames of an animation, each being the lit pixels,
+/// given as `(x, y)` coordinates, on a canvas of the specified `(width, height)`.
+pub trait FrameSink {
+    fn push_frame(&mut self, size: (usize, usize), lit: &[(usize, usize)]) -> io::Result<()>;
+}
+
+/// Writes every frame into a separate image within the directory, i.e. `step_00.pbm`,
+/// `step_01.pbm` and so on.
+#[derive(Debug)]
+pub struct DirectorySink {
+    dir: PathBuf,
+    prefix: String,
+    format: ImageFormat,
+    written: usize,
+}
+
+impl DirectorySink {
+    /// Creates the sink, alongside the directory if it doesn't exist yet.
+    pub fn new<P: AsRef<Path>>(dir: P, prefix: &str, format: ImageFormat) -> io::Result<Self> {
+        fs::create_dir_all(&dir)?;
+        Ok(DirectorySink {
+            dir: dir.as_ref().to_path_buf(),
+            prefix: prefix.to_owned(),
+            format,
+            written: 0,
+        })
+    }
+
+    pub fn written(&self) -> usize {
+        self.written
+    }
+}
+
+impl FrameSink for DirectorySink {
+    fn push_frame(&mut self, size: (usize, usize), lit: &[(usize, usize)]) -> io::Result<()> {
+        let name = format!(
+            "{}_{:02}.{}",
+            self.prefix,
+            self.written,
+            self.format.extension()
+        );
+        let (width, height) = size;
+        let lit = lit
+            .iter()
+            .copied()
+            .filter(|&(x, y)| x < width && y < height)
+            .collect::<Vec<_>>();
+        fs::write(self.dir.join(name), self.format.render(&lit, size))?;
+        self.written += 1;
+        Ok(())
+    }
+}
+
+/// Canvas size alongside the lit pixels of a single frame.
+pub type BufferedFrame = ((usize, usize), Vec<(usize, usize)>);
+
+/// Keeps all the frames in memory rather than writing them out.
+#[derive(Debug, Default)]
+pub struct FrameBuffer {
+    pub frames: Vec<BufferedFrame>,
+}
+
+impl FrameSink for FrameBuffer {
+    fn push_frame(&mut self, size: (usize, usize), lit: &[(usize, usize)]) -> io::Result<()> {
+        self.frames.push((size, lit.to_vec()));
+        Ok(())
+    }
+}
+
+/// Characters used for the lit and unlit cells when rendering into text.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Glyphs {
+    pub lit: char,
+    pub unlit: char,
+}
+
+impl Glyphs {
+    /// Full blocks on blank braille patterns, which have the same width in most terminal fonts.
+    pub const BLOCKS: Glyphs = Glyphs {
+        lit: '█',
+        unlit: '⠀',
+    };
+
+    /// The `#` and `.` characters used by the puzzle descriptions.
+    pub const ASCII: Glyphs = Glyphs {
+        lit: '#',
+        unlit: '.',
+    };
+
+    fn of(&self, lit: bool) -> char {
+        if lit {
+            self.lit
+        } else {
+            self.unlit
+        }
+    }
+}
+
+impl Default for Glyphs {
+    fn default() -> Self {
+        Glyphs::BLOCKS
+    }
+}
+
+/// Renders the lit points, given as `(x, y)` coordinates, into lines of text spanning
+/// their bounding box. Returns an empty string if there are no points.
+pub fn render_points(points: &BTreeSet<(i64, i64)>, glyphs: Glyphs) -> String {
+    let Some(min_x) = points.iter().map(|&(x, _)| x).min() else {
+        return String::new();
+    };
+    let max_x = points.iter().map(|&(x, _)| x).max().unwrap_or(min_x);
+    // the points are ordered by their x coordinate first
+    let (min_y, max_y) = points
+        .iter()
+        .fold((i64::MAX, i64::MIN), |(min, max), &(_, y)| {
+            (min.min(y), max.max(y))
+        });
+
+    (min_y..=max_y)
+        .map(|y| {
+            (min_x..=max_x)
+                .map(|x| glyphs.of(points.contains(&(x, y))))
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Renders the grid into lines of text, one per its row.
+pub fn render_grid(grid: &Grid<bool>, glyphs: Glyphs) -> String {
+    let mut rendered = String::new();
+    for ((x, y), &lit) in grid.iter_with_pos() {
+        if x == 0 && y > 0 {
+            rendered.push('\n');
+        }
+        rendered.push(glyphs.of(lit));
+    }
+    rendered
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rendering_text() {
+        let points = BTreeSet::from([(-1, 2), (1, 3), (0, 4)]);
+        assert_eq!("#..\n..#\n.#.", render_points(&points, Glyphs::ASCII));
+        assert_eq!("", render_points(&BTreeSet::new(), Glyphs::ASCII));
+
+        let grid = Grid::from_rows(vec![vec![true, false], vec![false, false]]).unwrap();
+        assert_eq!("█⠀\n⠀⠀", render_grid(&grid, Glyphs::default()));
+        let glyphs = Glyphs {
+            lit: 'x',
+            unlit: ' ',
+        };
+        assert_eq!("x \n  ", render_grid(&grid, glyphs));
+    }
+
+    #[test]
+    fn rendering_pbm() {
+        let lit = [(0, 0), (2, 0), (1, 1)];
+        assert_eq!("P1\n3 2\n101\n010\n", to_pbm(&lit));
+    }
+
+    #[test]
+    fn rendering_with_margins() {
+        let lit = [(0, 0), (2, 0), (1, 1)];
+        assert_eq!("P1\n4 3\n1010\n0100\n0000\n", pbm_with_size(&lit, (4, 3)));
+    }
+
+    #[test]
+    fn deducing_image_format() {
+        assert_eq!(
+            Some(ImageFormat::Svg),
+            ImageFormat::from_path("out/code.SVG")
+        );
+        assert_eq!(Some(ImageFormat::Pbm), ImageFormat::from_path("code.pbm"));
+        assert_eq!(None, ImageFormat::from_path("code.png"));
+        assert_eq!(None, ImageFormat::from_path("code"));
+    }
+
+    #[test]
+    fn writing_frames_into_directory() {
+        let dir = std::env::temp_dir().join(format!("aoc-frames-{}", std::process::id()));
+        let mut sink = DirectorySink::new(&dir, "step", ImageFormat::Pbm).unwrap();
+        sink.push_frame((2, 1), &[(0, 0)]).unwrap();
+        // pixels outside of the canvas are ignored
+        sink.push_frame((2, 1), &[(1, 0), (5, 5)]).unwrap();
+        assert_eq!(2, sink.written());
+
+        assert_eq!(
+            "P1\n2 1\n10\n",
+            fs::read_to_string(dir.join("step_00.pbm")).unwrap()
+        );
+        assert_eq!(
+            "P1\n2 1\n01\n",
+            fs::read_to_string(dir.join("step_01.pbm")).unwrap()
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,413 @@
+// Copyright 2022 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::memo::Memo;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::ops::{AddAssign, Mul};
+
+/// Breadth-first search, generic over the node type. Returns the path with the fewest steps
+/// from the `start` node to the first reached node satisfying `success`.
+pub fn bfs<N, FN, IN, FS>(start: &N, successors: FN, success: FS) -> Option<Vec<N>>
+where
+    N: Eq + Hash + Clone,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = N>,
+    FS: FnMut(&N) -> bool,
+{
+    traverse(start, successors, success, false)
+}
+
+/// Depth-first search, generic over the node type. Returns the path from the `start` node
+/// to the first reached node satisfying `success`, which isn't necessarily the shortest one.
+pub fn dfs<N, FN, IN, FS>(start: &N, successors: FN, success: FS) -> Option<Vec<N>>
+where
+    N: Eq + Hash + Clone,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = N>,
+    FS: FnMut(&N) -> bool,
+{
+    traverse(start, successors, success, true)
+}
+
+// visits every node at most once, taking the most recently discovered node from the frontier
+// when going depth-first and the least recently discovered one otherwise
+fn traverse<N, FN, IN, FS>(
+    start: &N,
+    mut successors: FN,
+    mut success: FS,
+    depth_first: bool,
+) -> Option<Vec<N>>
+where
+    N: Eq + Hash + Clone,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = N>,
+    FS: FnMut(&N) -> bool,
+{
+    let mut nodes = Vec::new();
+    let mut parents = Vec::new();
+    let mut visited = HashSet::new();
+    let mut frontier = VecDeque::from([(start.clone(), usize::MAX)]);
+
+    loop {
+        let (node, parent) = if depth_first {
+            frontier.pop_back()?
+        } else {
+            frontier.pop_front()?
+        };
+        if !visited.insert(node.clone()) {
+            continue;
+        }
+
+        let index = nodes.len();
+        nodes.push(node);
+        parents.push(parent);
+        if success(&nodes[index]) {
+            let path = reconstruct_path(index, |i| parents[i])
+                .into_iter()
+                .map(|i| nodes[i].clone())
+                .collect();
+            return Some(path);
+        }
+
+        for successor in successors(&nodes[index]) {
+            if !visited.contains(&successor) {
+                frontier.push_back((successor, index));
+            }
+        }
+    }
+}
+
+/// Returns all the nodes reachable from the `start` node, including the node itself.
+pub fn flood_fill<N, FN, IN>(start: &N, mut successors: FN) -> HashSet<N>
+where
+    N: Eq + Hash + Clone,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = N>,
+{
+    let mut reached = HashSet::from([start.clone()]);
+    let mut to_visit = vec![start.clone()];
+    while let Some(node) = to_visit.pop() {
+        for successor in successors(&node) {
+            if reached.insert(successor.clone()) {
+                to_visit.push(successor);
+            }
+        }
+    }
+    reached
+}
+
+/// Dijkstra's shortest path algorithm backed by a binary heap, generic over the node type.
+/// Returns the path from the `start` node to the first reached node satisfying `success`,
+/// alongside its total cost.
+pub fn dijkstra<N, FN, IN, FS>(start: &N, successors: FN, success: FS) -> Option<(Vec<N>, usize)>
+where
+    N: Eq + Hash + Clone,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, usize)>,
+    FS: FnMut(&N) -> bool,
+{
+    astar(start, successors, |_| 0, success)
+}
+
+/// A* shortest path algorithm, generic over the node type. The `heuristic` must never overestimate
+/// the remaining cost to the goal for the returned path to be optimal.
+/// Returns the path from the `start` node to the first reached node satisfying `success`,
+/// alongside its total cost.
+pub fn astar<N, FN, IN, FH, FS>(
+    start: &N,
+    mut successors: FN,
+    mut heuristic: FH,
+    mut success: FS,
+) -> Option<(Vec<N>, usize)>
+where
+    N: Eq + Hash + Clone,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, usize)>,
+    FH: FnMut(&N) -> usize,
+    FS: FnMut(&N) -> bool,
+{
+    // nodes are stored in a separate vector so that the heap only has to deal with indices
+    let mut nodes = vec![start.clone()];
+    let mut indices = HashMap::from([(start.clone(), 0)]);
+    // (cost, parent index) for every node discovered so far
+    let mut costs = vec![(0usize, usize::MAX)];
+
+    // the heap is ordered by the estimated total cost, i.e. the cost so far plus the heuristic,
+    // with ties broken in favour of the nodes estimated to be closest to the goal
+    let mut to_visit = BinaryHeap::new();
+    let start_estimate = heuristic(start);
+    to_visit.push(Reverse((start_estimate, start_estimate, 0usize, 0usize)));
+
+    while let Some(Reverse((_, _, cost, index))) = to_visit.pop() {
+        if cost > costs[index].0 {
+            // we have already found a better way of getting here
+            continue;
+        }
+        if success(&nodes[index]) {
+            let path = reconstruct_path(index, |i| costs[i].1)
+                .into_iter()
+                .map(|i| nodes[i].clone())
+                .collect();
+            return Some((path, cost));
+        }
+
+        for (successor, move_cost) in successors(&nodes[index]) {
+            let new_cost = cost + move_cost;
+            let remaining = heuristic(&successor);
+            let estimate = new_cost + remaining;
+            let successor_index = match indices.get(&successor) {
+                Some(&existing) => {
+                    if costs[existing].0 <= new_cost {
+                        continue;
+                    }
+                    costs[existing] = (new_cost, index);
+                    existing
+                }
+                None => {
+                    let new_index = nodes.len();
+                    indices.insert(successor.clone(), new_index);
+                    nodes.push(successor);
+                    costs.push((new_cost, index));
+                    new_index
+                }
+            };
+            to_visit.push(Reverse((estimate, remaining, new_cost, successor_index)));
+        }
+    }
+
+    None
+}
+
+/// Dial's shortest path algorithm, i.e. Dijkstra's algorithm using a bucket queue, for graphs
+/// whose nodes are dense indices in `0..nodes` and whose edge weights never exceed `max_weight`.
+/// Returns the path from `start` to `goal` alongside its total cost.
+pub fn dial<FN, IN>(
+    nodes: usize,
+    start: usize,
+    goal: usize,
+    max_weight: usize,
+    mut successors: FN,
+) -> Option<(Vec<usize>, usize)>
+where
+    FN: FnMut(usize) -> IN,
+    IN: IntoIterator<Item = (usize, usize)>,
+{
+    // since all weights are bounded, all the pending nodes have costs within `max_weight`
+    // of the currently processed one and thus the buckets can be reused in circular manner
+    let bucket_count = max_weight + 1;
+    let mut buckets = vec![Vec::new(); bucket_count];
+    let mut costs = vec![usize::MAX; nodes];
+    let mut parents = vec![usize::MAX; nodes];
+
+    costs[start] = 0;
+    buckets[0].push(start);
+    let mut pending = 1;
+    let mut cost = 0;
+
+    while pending > 0 {
+        let bucket = cost % bucket_count;
+        while let Some(node) = buckets[bucket].pop() {
+            pending -= 1;
+            if costs[node] != cost {
+                // stale entry, the node has already been reached with lower cost
+                continue;
+            }
+            if node == goal {
+                return Some((reconstruct_path(node, |i| parents[i]), cost));
+            }
+
+            for (successor, move_cost) in successors(node) {
+                debug_assert!(move_cost <= max_weight);
+                let new_cost = cost + move_cost;
+                if new_cost < costs[successor] {
+                    costs[successor] = new_cost;
+                    parents[successor] = node;
+                    buckets[new_cost % bucket_count].push(successor);
+                    pending += 1;
+                }
+            }
+        }
+        cost += 1;
+    }
+
+    None
+}
+
+/// Counts the weighted paths leading from `start` to each of the `N` possible outcomes,
+/// caching the counts of every visited state. `terminal` returns the outcome counts of the states
+/// ending the process, while `expand` lists the successors of the remaining ones alongside
+/// the number of ways of reaching them. The state graph must be acyclic.
+/// The counts are generic, so that a wider integer type could be used if they grow too large.
+pub fn memoized_count<S, C, FT, FE, IE, const N: usize>(
+    start: S,
+    terminal: FT,
+    expand: FE,
+) -> [C; N]
+where
+    S: Eq + Hash + Clone,
+    C: Copy + Default + AddAssign + Mul<Output = C>,
+    FT: FnMut(&S) -> Option<[C; N]>,
+    FE: FnMut(&S) -> IE,
+    IE: IntoIterator<Item = (S, C)>,
+{
+    fn count<S, C, FT, FE, IE, const N: usize>(
+        memo: &mut Memo<S, [C; N]>,
+        state: S,
+        terminal: &mut FT,
+        expand: &mut FE,
+    ) -> [C; N]
+    where
+        S: Eq + Hash + Clone,
+        C: Copy + Default + AddAssign + Mul<Output = C>,
+        FT: FnMut(&S) -> Option<[C; N]>,
+        FE: FnMut(&S) -> IE,
+        IE: IntoIterator<Item = (S, C)>,
+    {
+        if let Some(outcomes) = terminal(&state) {
+            return outcomes;
+        }
+        memo.get_or_insert_with(state.clone(), |memo| {
+            let mut outcomes = [C::default(); N];
+            for (next, ways) in expand(&state) {
+                let next_outcomes = count(memo, next, terminal, expand);
+                for (total, next) in outcomes.iter_mut().zip(next_outcomes) {
+                    *total += ways * next;
+                }
+            }
+            outcomes
+        })
+    }
+
+    let (mut terminal, mut expand) = (terminal, expand);
+    count(&mut Memo::new(), start, &mut terminal, &mut expand)
+}
+
+// follows the parent links, where `usize::MAX` denotes the lack of parent, back to the start
+fn reconstruct_path<F>(end: usize, parent: F) -> Vec<usize>
+where
+    F: Fn(usize) -> usize,
+{
+    let mut path = vec![end];
+    let mut current = end;
+    while parent(current) != usize::MAX {
+        current = parent(current);
+        path.push(current);
+    }
+    path.reverse();
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0 --1--> 1 --1--> 2
+    //  \               ^
+    //   -------5-------|
+    //    \--2--> 3 --1-/
+    fn successors(node: usize) -> Vec<(usize, usize)> {
+        match node {
+            0 => vec![(1, 1), (2, 5), (3, 2)],
+            1 => vec![(2, 1)],
+            3 => vec![(2, 1)],
+            _ => vec![],
+        }
+    }
+
+    fn unweighted_successors(node: usize) -> Vec<usize> {
+        successors(node).into_iter().map(|(n, _)| n).collect()
+    }
+
+    #[test]
+    fn unweighted_traversals() {
+        assert_eq!(
+            Some(vec![0, 2]),
+            bfs(&0, |&n| unweighted_successors(n), |&n| n == 2)
+        );
+        assert_eq!(
+            Some(vec![0, 3, 2]),
+            dfs(&0, |&n| unweighted_successors(n), |&n| n == 2)
+        );
+        assert_eq!(
+            Some(vec![1]),
+            bfs(&1, |&n| unweighted_successors(n), |&n| n == 1)
+        );
+        assert_eq!(None, bfs(&1, |&n| unweighted_successors(n), |&n| n == 0));
+        assert_eq!(None, dfs(&0, |&n| unweighted_successors(n), |&n| n == 4));
+
+        assert_eq!(
+            HashSet::from([0, 1, 2, 3]),
+            flood_fill(&0, |&n| unweighted_successors(n))
+        );
+        assert_eq!(
+            HashSet::from([3, 2]),
+            flood_fill(&3, |&n| unweighted_successors(n))
+        );
+    }
+
+    #[test]
+    fn dijkstra_shortest_path() {
+        let (path, cost) = dijkstra(&0, |&n| successors(n), |&n| n == 2).unwrap();
+        assert_eq!(vec![0, 1, 2], path);
+        assert_eq!(2, cost);
+
+        assert_eq!(
+            Some((vec![0], 0)),
+            dijkstra(&0, |&n| successors(n), |&n| n == 0)
+        );
+        assert_eq!(None, dijkstra(&0, |&n| successors(n), |&n| n == 4));
+    }
+
+    #[test]
+    fn astar_shortest_path() {
+        // admissible heuristic: the number of remaining hops, as every edge costs at least 1
+        let heuristic = |&n: &usize| usize::from(n != 2);
+        let (path, cost) = astar(&0, |&n| successors(n), heuristic, |&n| n == 2).unwrap();
+        assert_eq!(vec![0, 1, 2], path);
+        assert_eq!(2, cost);
+
+        assert_eq!(None, astar(&0, |&n| successors(n), |_| 0, |&n| n == 4));
+    }
+
+    #[test]
+    fn dial_shortest_path() {
+        let (path, cost) = dial(5, 0, 2, 5, successors).unwrap();
+        assert_eq!(vec![0, 1, 2], path);
+        assert_eq!(2, cost);
+
+        assert_eq!(Some((vec![3, 2], 1)), dial(5, 3, 2, 5, successors));
+        assert_eq!(None, dial(5, 0, 4, 5, successors));
+    }
+
+    #[test]
+    fn counting_paths_to_outcomes() {
+        // ways of climbing the stairs taking one or two steps at a time,
+        // split by whether the last step was a single or a double one
+        let stairs = |steps: usize| {
+            memoized_count(
+                (steps, 0),
+                |&(remaining, last)| {
+                    (remaining == 0).then_some(if last == 1 { [1, 0] } else { [0, 1] })
+                },
+                |&(remaining, _)| {
+                    (1..=remaining.min(2)).map(move |step| ((remaining - step, step), 1))
+                },
+            )
+        };
+        assert_eq!([3, 2], stairs(4));
+        assert_eq!([55, 34], stairs(10));
+    }
+}
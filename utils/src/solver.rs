@@ -0,0 +1,154 @@
+// Copyright 2021 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::FrameSink;
+use std::fmt::Display;
+use std::io;
+use std::path::Path;
+
+/// Common interface of all the daily solutions, so that they could be run, timed and checked
+/// uniformly. Any configuration of the solution, such as the chosen algorithm, lives in the
+/// implementing type itself.
+pub trait Solver {
+    /// Day of the puzzle, used for locating its input.
+    const DAY: u8;
+
+    type Input;
+    type Output1: Display;
+    type Output2: Display;
+
+    /// Reads the puzzle input from the specified file and parses it.
+    fn parse(&self, input_file: &Path) -> io::Result<Self::Input>;
+
+    fn part1(&self, input: &Self::Input) -> Self::Output1;
+
+    fn part2(&self, input: &Self::Input) -> Self::Output2;
+}
+
+/// A [`Solver`] whose parsing consists of reading the raw puzzle input followed by
+/// an expensive computation on it that both parts rely on. Splitting the two allows
+/// [`execute_shared`](crate::execution::execute_shared) to time them separately.
+pub trait SharedSolver: Solver {
+    type Raw;
+
+    /// Reads the raw puzzle input from the specified file.
+    fn read(&self, input_file: &Path) -> io::Result<Self::Raw>;
+
+    /// Performs the computation shared by both parts on the raw input.
+    fn prepare(&self, raw: Self::Raw) -> io::Result<Self::Input>;
+}
+
+/// A [`Solver`] whose puzzle can also be visualised, by rendering the consecutive stages
+/// of its solution as frames pushed into the provided sink.
+pub trait Visualize: Solver {
+    fn visualize(&self, input: &Self::Input, sink: &mut dyn FrameSink) -> io::Result<()>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::execution::{solve, try_execute, try_execute_shared, Part};
+    use crate::input_read::{read_parsed_line_input, InputFallback};
+
+    struct Sum;
+
+    impl Solver for Sum {
+        const DAY: u8 = 0;
+
+        type Input = Vec<usize>;
+        type Output1 = usize;
+        type Output2 = usize;
+
+        fn parse(&self, input_file: &Path) -> io::Result<Self::Input> {
+            read_parsed_line_input(input_file)
+        }
+
+        fn part1(&self, input: &Self::Input) -> usize {
+            input.iter().sum()
+        }
+
+        fn part2(&self, input: &Self::Input) -> usize {
+            input.iter().product()
+        }
+    }
+
+    struct SortedSum;
+
+    impl Solver for SortedSum {
+        const DAY: u8 = 0;
+
+        type Input = Vec<usize>;
+        type Output1 = usize;
+        type Output2 = usize;
+
+        fn parse(&self, input_file: &Path) -> io::Result<Self::Input> {
+            self.prepare(self.read(input_file)?)
+        }
+
+        fn part1(&self, input: &Self::Input) -> usize {
+            input.iter().sum()
+        }
+
+        fn part2(&self, input: &Self::Input) -> usize {
+            input[0]
+        }
+    }
+
+    impl SharedSolver for SortedSum {
+        type Raw = Vec<usize>;
+
+        fn read(&self, input_file: &Path) -> io::Result<Self::Raw> {
+            read_parsed_line_input(input_file)
+        }
+
+        fn prepare(&self, mut raw: Self::Raw) -> io::Result<Self::Input> {
+            if raw.is_empty() {
+                return Err(io::Error::new(io::ErrorKind::InvalidData, "no numbers"));
+            }
+            raw.sort_unstable();
+            Ok(raw)
+        }
+    }
+
+    #[test]
+    fn executing_solver() {
+        let input_file = std::env::temp_dir().join("utils_solver_test_input");
+        std::fs::write(&input_file, "1\n2\n3\n4\n").unwrap();
+        let input = Sum.parse(&input_file).unwrap();
+        assert_eq!(10, Sum.part1(&input));
+        assert_eq!(24, Sum.part2(&input));
+        assert!(try_execute(&Sum, &input_file).is_ok());
+
+        let report = solve(&Sum, &input_file, &[Part::Two], &InputFallback::Disabled).unwrap();
+        assert_eq!(Some("24"), report.answer(Part::Two));
+        assert_eq!(None, report.answer(Part::One));
+
+        std::fs::write(&input_file, "1\nfoo\n").unwrap();
+        assert!(try_execute(&Sum, &input_file).is_err());
+        std::fs::remove_file(input_file).unwrap();
+    }
+    #[test]
+    fn executing_shared_solver() {
+        let input_file = std::env::temp_dir().join("utils_shared_solver_test_input");
+        std::fs::write(&input_file, "3\n1\n2\n").unwrap();
+        assert_eq!(
+            vec![1, 2, 3],
+            try_execute_shared(&SortedSum, &input_file).unwrap()
+        );
+
+        std::fs::write(&input_file, "").unwrap();
+        assert!(try_execute_shared(&SortedSum, &input_file).is_err());
+        std::fs::remove_file(input_file).unwrap();
+    }
+}
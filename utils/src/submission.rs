@@ -0,0 +1,250 @@
+// Copyright 2021 Jedrzej Stuczynski
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::execution::Part;
+use std::fmt::{self, Display, Formatter};
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+}
+
+/// Response of the Advent of Code website to a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Incorrect(Option<Hint>),
+    /// The previous answer was submitted too recently, alongside the remaining time to wait,
+    /// i.e. `4m 32s`, if it could be found.
+    TooRecent(Option<String>),
+    /// The part has already been solved, or it isn't unlocked yet.
+    WrongLevel,
+    Unrecognised,
+}
+
+impl Verdict {
+    /// Interprets the page returned after submitting an answer.
+    pub fn from_response(response: &str) -> Self {
+        if response.contains("That's the right answer") {
+            Verdict::Correct
+        } else if response.contains("That's not the right answer") {
+            if response.contains("your answer is too high") {
+                Verdict::Incorrect(Some(Hint::TooHigh))
+            } else if response.contains("your answer is too low") {
+                Verdict::Incorrect(Some(Hint::TooLow))
+            } else {
+                Verdict::Incorrect(None)
+            }
+        } else if response.contains("You gave an answer too recently") {
+            let wait = response
+                .split_once("You have ")
+                .and_then(|(_, remaining)| remaining.split_once(" left to wait"))
+                .map(|(wait, _)| wait.to_owned());
+            Verdict::TooRecent(wait)
+        } else if response.contains("You don't seem to be solving the right level") {
+            Verdict::WrongLevel
+        } else {
+            Verdict::Unrecognised
+        }
+    }
+
+    /// Checks whether submitting the same answer again would be pointless.
+    pub fn is_final(&self) -> bool {
+        matches!(self, Verdict::Correct | Verdict::Incorrect(_))
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::Incorrect(None) => write!(f, "incorrect"),
+            Verdict::Incorrect(Some(Hint::TooHigh)) => write!(f, "incorrect, too high"),
+            Verdict::Incorrect(Some(Hint::TooLow)) => write!(f, "incorrect, too low"),
+            Verdict::TooRecent(Some(wait)) => write!(f, "too recent, wait {wait}"),
+            Verdict::TooRecent(None) => write!(f, "too recent"),
+            Verdict::WrongLevel => write!(f, "already solved or locked"),
+            Verdict::Unrecognised => write!(f, "unrecognised response"),
+        }
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Verdict::Correct),
+            "incorrect" => Ok(Verdict::Incorrect(None)),
+            "incorrect, too high" => Ok(Verdict::Incorrect(Some(Hint::TooHigh))),
+            "incorrect, too low" => Ok(Verdict::Incorrect(Some(Hint::TooLow))),
+            "too recent" => Ok(Verdict::TooRecent(None)),
+            "already solved or locked" => Ok(Verdict::WrongLevel),
+            "unrecognised response" => Ok(Verdict::Unrecognised),
+            other => match other.strip_prefix("too recent, wait ") {
+                Some(wait) => Ok(Verdict::TooRecent(Some(wait.to_owned()))),
+                None => Err(format!("{other} is not a valid verdict")),
+            },
+        }
+    }
+}
+
+/// Posts the answer to the specified part of the puzzle.
+#[cfg(feature = "download")]
+pub fn submit_answer(
+    year: u16,
+    day: u8,
+    part: Part,
+    answer: &str,
+    session_token: &str,
+) -> io::Result<Verdict> {
+    let url = format!("https://adventofcode.com/{year}/day/{day}/answer");
+    let level = part.to_string();
+    let response = ureq::post(&url)
+        .set("Cookie", &format!("session={session_token}"))
+        .set("User-Agent", crate::input_read::USER_AGENT)
+        .send_form(&[("level", &level), ("answer", answer)])
+        .map_err(io::Error::other)?
+        .into_string()?;
+
+    Ok(Verdict::from_response(&response))
+}
+
+/// Appends the submission to the log of all the submitted answers, one tab-separated
+/// `day part answer verdict` entry per line.
+pub fn record_submission(
+    log: &Path,
+    day: u8,
+    part: Part,
+    answer: &str,
+    verdict: &Verdict,
+) -> io::Result<()> {
+    if let Some(dir) = log.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(log)?;
+    writeln!(file, "{day}\t{part}\t{answer}\t{verdict}")
+}
+
+/// Looks up the recorded verdict of the last submission of exactly this answer, if any.
+pub fn recorded_verdict(
+    log: &Path,
+    day: u8,
+    part: Part,
+    answer: &str,
+) -> io::Result<Option<Verdict>> {
+    let submissions = match fs::read_to_string(log) {
+        Ok(submissions) => submissions,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err),
+    };
+
+    let expected = [day.to_string(), part.to_string(), answer.to_owned()];
+    submissions
+        .lines()
+        .rev()
+        .filter_map(|line| line.rsplit_once('\t'))
+        .filter(|(submission, _)| {
+            submission
+                .split('\t')
+                .eq(expected.iter().map(String::as_str))
+        })
+        .map(|(_, verdict)| {
+            verdict
+                .parse()
+                .map_err(|err: String| io::Error::new(io::ErrorKind::InvalidData, err))
+        })
+        .next()
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interpreting_responses() {
+        assert_eq!(
+            Verdict::Correct,
+            Verdict::from_response("<p>That's the right answer! You are one gold star closer.</p>")
+        );
+        assert_eq!(
+            Verdict::Incorrect(Some(Hint::TooHigh)),
+            Verdict::from_response(
+                "<p>That's not the right answer; your answer is too high. Please wait one minute.</p>"
+            )
+        );
+        assert_eq!(
+            Verdict::Incorrect(None),
+            Verdict::from_response("<p>That's not the right answer. If you're stuck...</p>")
+        );
+        assert_eq!(
+            Verdict::TooRecent(Some("4m 32s".to_owned())),
+            Verdict::from_response(
+                "<p>You gave an answer too recently; you have to wait after submitting an answer \
+                before trying again.  You have 4m 32s left to wait.</p>"
+            )
+        );
+        assert_eq!(
+            Verdict::WrongLevel,
+            Verdict::from_response("<p>You don't seem to be solving the right level.</p>")
+        );
+        assert_eq!(
+            Verdict::Unrecognised,
+            Verdict::from_response("<html></html>")
+        );
+    }
+
+    #[test]
+    fn verdicts_round_trip() {
+        for verdict in [
+            Verdict::Correct,
+            Verdict::Incorrect(None),
+            Verdict::Incorrect(Some(Hint::TooHigh)),
+            Verdict::Incorrect(Some(Hint::TooLow)),
+            Verdict::TooRecent(None),
+            Verdict::TooRecent(Some("34s".to_owned())),
+            Verdict::WrongLevel,
+            Verdict::Unrecognised,
+        ] {
+            assert_eq!(Ok(verdict.clone()), verdict.to_string().parse());
+        }
+    }
+
+    #[test]
+    fn recording_submissions() {
+        let log = std::env::temp_dir().join("utils_submission_log");
+        let _ = fs::remove_file(&log);
+        assert_eq!(None, recorded_verdict(&log, 1, Part::One, "42").unwrap());
+
+        let too_low = Verdict::Incorrect(Some(Hint::TooLow));
+        record_submission(&log, 1, Part::One, "42", &too_low).unwrap();
+        record_submission(&log, 1, Part::One, "100", &Verdict::Correct).unwrap();
+        assert_eq!(
+            Some(too_low),
+            recorded_verdict(&log, 1, Part::One, "42").unwrap()
+        );
+        assert_eq!(
+            Some(Verdict::Correct),
+            recorded_verdict(&log, 1, Part::One, "100").unwrap()
+        );
+        assert_eq!(None, recorded_verdict(&log, 1, Part::Two, "100").unwrap());
+        fs::remove_file(log).unwrap();
+    }
+}